@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use datafusion::datasource::{MemTable, TableProvider};
@@ -476,11 +476,14 @@ impl TempCatalog {
                 },
                 options: TableOptionsInternal {
                     columns: columns.clone(),
+                    partition_by: Vec::new(),
                 }
                 .into(),
                 tunnel_id: None,
                 access_mode: SourceAccessMode::ReadWrite,
                 columns: Some(columns),
+                comment: None,
+                column_comments: BTreeMap::new(),
             }
         })
     }
@@ -521,11 +524,14 @@ impl TempCatalog {
                 },
                 options: TableOptionsInternal {
                     columns: Vec::new(),
+                    partition_by: Vec::new(),
                 }
                 .into(),
                 tunnel_id: None,
                 access_mode: SourceAccessMode::ReadWrite,
                 columns: None,
+                comment: None,
+                column_comments: BTreeMap::new(),
             });
         }
 
@@ -5,6 +5,9 @@ use crate::args::{PathBuf, StorageConfigArgs};
 #[derive(Args)]
 pub struct ServerArgs {
     /// TCP address to bind to for the Postgres interface.
+    ///
+    /// May be provided multiple times to listen on multiple addresses, e.g.
+    /// both IPv4 and IPv6.
     #[arg(
         id = "PORT",
         short = 'b',
@@ -12,12 +15,19 @@ pub struct ServerArgs {
         value_parser,
         conflicts_with = "disable_postgres_api"
     )]
-    pub bind: Option<String>,
+    pub bind: Vec<String>,
 
     /// TCP address to bind to for the RPC/Flight SQL interface.
     #[arg(id= "RPC_PORT", long="rpc-bind", value_parser, aliases=&["flight-bind"])]
     pub rpc_bind: Option<String>,
 
+    /// TCP address to serve Prometheus metrics on.
+    ///
+    /// Metrics are served over HTTP at `/metrics`. If unset, metrics are not
+    /// served.
+    #[arg(long, value_parser)]
+    pub metrics_bind: Option<String>,
+
     /// Set the user used for authentication.
     ///
     /// Only has an affect if a password is also provided. If a password is
@@ -51,6 +61,40 @@ pub struct ServerArgs {
     #[arg(long, value_parser)]
     pub spill_path: Option<PathBuf>,
 
+    /// Maximum number of concurrent Postgres connections.
+    ///
+    /// Connections beyond this limit are refused. If unset, the number of
+    /// connections is unbounded.
+    #[arg(long, value_parser)]
+    pub max_connections: Option<usize>,
+
+    /// Maximum number of concurrent GET requests made against each object
+    /// store (e.g. an S3 or GCS bucket) when reading external tables.
+    ///
+    /// Lowering this avoids being rate limited during scans over many
+    /// objects. If unset, the number of requests is unbounded.
+    #[arg(long, value_parser)]
+    pub object_store_max_concurrent_gets: Option<usize>,
+
+    /// Close Postgres connections that haven't sent a message within this
+    /// many seconds.
+    ///
+    /// If unset, idle connections are kept open until the client
+    /// disconnects.
+    #[arg(long, value_parser)]
+    pub idle_timeout_secs: Option<u64>,
+
+    /// Path to the SSL server cert to use for the Postgres interface.
+    ///
+    /// When provided along with `--ssl-server-key`, clients may connect
+    /// using SSL/TLS (e.g. `sslmode=require`).
+    #[arg(long, value_parser, requires = "ssl_server_key")]
+    pub ssl_server_cert: Option<String>,
+
+    /// Path to the SSL server key to use for the Postgres interface.
+    #[arg(long, value_parser, requires = "ssl_server_cert")]
+    pub ssl_server_key: Option<String>,
+
     /// Ignore authentication messages.
     ///
     /// (Internal)
@@ -103,6 +147,12 @@ pub struct ServerArgs {
     pub disable_postgres_api: bool,
 
     /// Bucket to use for database catalogs.
+    ///
+    /// When provided alongside a service account, the bucket is assumed to be
+    /// in GCS. Otherwise the bucket is assumed to be in S3 (or an S3
+    /// compatible store), configured using the `AWS_ACCESS_KEY_ID`,
+    /// `AWS_SECRET_ACCESS_KEY`, `AWS_REGION` and `AWS_ENDPOINT` environment
+    /// variables.
     #[clap(long, value_parser)]
     pub metastore_bucket: Option<String>,
 }
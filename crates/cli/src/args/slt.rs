@@ -1,13 +1,18 @@
 use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{anyhow, Result};
-use clap::Args;
+use clap::{Args, ValueEnum};
 use pgsrv::auth::SingleUserAuthenticator;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::Serialize;
 use slt::clients::flightsql::FlightSqlTestClient;
-use slt::clients::postgres::PgTestClient;
+use slt::clients::postgres::{PgTestClient, PgTlsConfig};
 use slt::clients::rpc::RpcTestClient;
 use slt::clients::{ClientProtocol, TestClient};
 use slt::test::{Test, TestHooks};
@@ -15,7 +20,7 @@ use tokio::net::TcpListener;
 use tokio::runtime::Builder;
 use tokio::sync::mpsc;
 use tokio::time::Instant;
-use tokio_postgres::config::Config as ClientConfig;
+use tokio_postgres::config::{Config as ClientConfig, SslMode};
 use uuid::Uuid;
 
 use crate::args::StorageConfigArgs;
@@ -43,6 +48,15 @@ pub struct SltArgs {
     #[arg(long, value_parser)]
     keep_running: bool,
 
+    /// Store the embedded server's data in this directory instead of a
+    /// temporary one, and keep it after the run.
+    ///
+    /// Each test runs against its own database, stored under
+    /// `<dir>/databases/<id>`. The directory of each failing test is printed
+    /// to allow inspecting its storage state.
+    #[arg(long, value_parser, conflicts_with_all = ["connection_string", "location"])]
+    keep_db: Option<PathBuf>,
+
     /// Connection string to use for connecting to the database.
     ///
     /// If provided, an embedded server won't be started.
@@ -61,20 +75,102 @@ pub struct SltArgs {
     jobs: u8,
 
     /// Timeout (exit) after this number of seconds.
+    ///
+    /// Tests that haven't finished by then are reported as timed out.
     #[arg(long, value_parser, default_value_t = 5 * 60)]
     timeout: u64,
 
+    /// Timeout for individual tests, in the form of `<pattern>=<seconds>`.
+    ///
+    /// The pattern is a glob matched against the test name. A test that runs
+    /// for longer than its timeout will fail. If a test matches multiple
+    /// patterns, the first one is used. Can be provided multiple times.
+    #[arg(long, value_parser = parse_test_timeout)]
+    test_timeout: Vec<(glob::Pattern, Duration)>,
+
+    /// Number of times to retry a failed test before recording the failure.
+    ///
+    /// Each attempt uses a fresh client connection. When running against the
+    /// embedded server, each attempt also uses a fresh database.
+    #[arg(long, value_parser, default_value_t = 0)]
+    retries: usize,
+
+    /// Keep running the remaining batches of tests after a failure.
+    ///
+    /// By default, no further tests are run once a batch has a failing test.
+    #[arg(long, value_parser)]
+    no_fail_fast: bool,
+
+    /// Fail tests that cause the server to emit warnings.
+    ///
+    /// Warnings are only surfaced through the postgres and rpc protocols.
+    #[arg(long, value_parser)]
+    fail_on_warning: bool,
+
+    /// Show a row-by-row diff of the expected and actual results of failing
+    /// queries.
+    #[arg(long, value_parser)]
+    diff: bool,
+
     /// Exclude these tests from the run.
     #[arg(short, long, value_parser)]
     exclude: Vec<String>,
 
-    /// Client protocol to use. (rpc, postgres, flightsql)
-    #[arg(long, short, value_enum, default_value_t=ClientProtocol::Postgres)]
-    protocol: ClientProtocol,
+    /// Client protocols to use. (rpc, postgres, flightsql)
+    ///
+    /// Can be provided multiple times, or as a comma separated list, to run
+    /// every test once per protocol. Results are tagged with the protocol
+    /// name when more than one protocol is used.
+    #[arg(
+        long,
+        short,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "postgres"
+    )]
+    protocol: Vec<ClientProtocol>,
+
+    /// SSL mode to use when connecting with the postgres protocol.
+    ///
+    /// Overrides the ssl mode of the connection string. If omitted, tests
+    /// connect without TLS.
+    #[arg(long, value_enum)]
+    sslmode: Option<SslModeArg>,
+
+    /// Path to a PEM encoded CA certificate to trust when connecting with TLS.
+    #[arg(long, value_parser)]
+    ssl_root_cert: Option<PathBuf>,
 
     #[command(flatten)]
     storage_config: StorageConfigArgs,
 
+    /// Write a JSON report of the test results to this path.
+    ///
+    /// The report contains an entry for every test that was run, including
+    /// its status, duration, and error message (on failure).
+    #[arg(long, value_parser)]
+    output_json: Option<PathBuf>,
+
+    /// Shuffle the tests using this seed before running them.
+    ///
+    /// Tests otherwise run in alphabetical order. Shuffling helps surface
+    /// tests that depend on state left behind by other tests, and reusing the
+    /// same seed reproduces the same order.
+    #[arg(long, value_parser)]
+    seed: Option<u64>,
+
+    /// Only run a subset of the tests, in the form of `<index>/<total>`.
+    ///
+    /// Tests are assigned to shards using a stable hash of the test name, so
+    /// running every index from `0` to `total - 1` runs each test exactly
+    /// once.
+    #[arg(long, value_parser = parse_shard)]
+    shard: Option<(u64, u64)>,
+
+    /// Print the N slowest tests after the run (10 if N is omitted).
+    #[arg(long, value_parser, num_args = 0..=1, default_missing_value = "10")]
+    timings: Option<usize>,
+
     /// Tests to run.
     ///
     /// Provide glob like regexes for test names. If omitted, runs all the
@@ -144,6 +240,10 @@ impl SltArgs {
             tests.retain(|(k, _v)| !pattern.matches(k));
         }
 
+        if let Some((index, total)) = self.shard {
+            tests.retain(|(k, _v)| stable_hash(k) % total == index);
+        }
+
         if tests.is_empty() {
             return Err(anyhow!("No tests to run. Exiting..."));
         }
@@ -151,25 +251,65 @@ impl SltArgs {
         Ok(tests)
     }
 
+    /// The distinct protocols to run the tests with.
+    fn protocols(&self) -> Vec<ClientProtocol> {
+        let mut protocols = self.protocol.clone();
+        protocols.sort();
+        protocols.dedup();
+        protocols
+    }
+
     /// Run all provided tests, in batches of size `batch_size`.
     ///
-    /// Batches will be ran sequentially, and an error resulting from a batch
-    /// will halt further execution.
+    /// Each test is run once per selected protocol. Batches will be ran
+    /// sequentially, and an error resulting from a batch will halt further
+    /// execution unless `--no-fail-fast` is set.
     async fn run_tests_batched(
         &self,
         batch_size: usize,
-        mut tests: Vec<(String, Test)>,
+        tests: Vec<(String, Test)>,
         hooks: TestHooks,
     ) -> Result<()> {
-        // Temp directory for metastore
-        let temp_dir = tempfile::tempdir()?;
+        // Directory for the metastore and table storage. A temp directory is
+        // used (and removed on exit) unless the databases should be kept.
+        let (_temp_dir, data_dir) = match &self.keep_db {
+            Some(dir) => {
+                std::fs::create_dir_all(dir)?;
+                (None, dir.clone())
+            }
+            None => {
+                let temp_dir = tempfile::tempdir()?;
+                let data_dir = temp_dir.path().to_path_buf();
+                (Some(temp_dir), data_dir)
+            }
+        };
+
+        let protocols = self.protocols();
+        let tagged = protocols.len() > 1;
+
+        let mut runs: Vec<TestRun> = tests
+            .into_iter()
+            .flat_map(|(test_name, test)| {
+                let test = Arc::new(test);
+                protocols.iter().map(move |&protocol| TestRun {
+                    name: if tagged {
+                        format!("{test_name} ({protocol})")
+                    } else {
+                        test_name.clone()
+                    },
+                    test_name: test_name.clone(),
+                    protocol,
+                    test: test.clone(),
+                })
+            })
+            .collect();
 
-        let configs: HashMap<String, ClientConfig> =
+        let mut configs: HashMap<String, ClientConfig> =
             if let Some(connection_string) = &self.connection_string {
                 let config: ClientConfig = connection_string.parse()?;
-                let mut configs = HashMap::with_capacity(tests.len());
-                tests.iter().for_each(|(name, _)| {
-                    configs.insert(name.clone(), config.clone());
+                let mut configs = HashMap::with_capacity(runs.len());
+                runs.iter().for_each(|run| {
+                    configs.insert(run.name.clone(), config.clone());
                 });
                 configs
             } else {
@@ -178,19 +318,30 @@ impl SltArgs {
                     .clone()
                     .unwrap_or_else(|| "0.0.0.0:0".to_string());
 
-                let (pg_listener, rpc_listener, socket_addr) = match self.protocol {
-                    ClientProtocol::Postgres => {
-                        let listener = TcpListener::bind(bind_addr.clone()).await?;
-                        let addr = listener.local_addr().unwrap();
-                        (Some(listener), None, addr)
-                    }
-                    ClientProtocol::Rpc | ClientProtocol::FlightSql => {
-                        let listener = TcpListener::bind(bind_addr.clone()).await?;
-                        let addr = listener.local_addr().unwrap();
-                        (None, Some(listener), addr)
+                let needs_pg = protocols.contains(&ClientProtocol::Postgres);
+                let needs_rpc = protocols
+                    .iter()
+                    .any(|p| matches!(p, ClientProtocol::Rpc | ClientProtocol::FlightSql));
+
+                let pg_listener = if needs_pg {
+                    Some(TcpListener::bind(bind_addr.clone()).await?)
+                } else {
+                    None
+                };
+                let rpc_listener = match &pg_listener {
+                    // Both listeners can't share the same port, so the rpc
+                    // listener gets any available port on the same host.
+                    Some(pg_listener) if needs_rpc => {
+                        let ip = pg_listener.local_addr()?.ip();
+                        Some(TcpListener::bind((ip, 0)).await?)
                     }
+                    None if needs_rpc => Some(TcpListener::bind(bind_addr.clone()).await?),
+                    _ => None,
                 };
 
+                let pg_addr = pg_listener.as_ref().map(|l| l.local_addr()).transpose()?;
+                let rpc_addr = rpc_listener.as_ref().map(|l| l.local_addr()).transpose()?;
+
                 let mut builder = ComputeServer::builder()
                     .with_authenticator(SingleUserAuthenticator {
                         user: "glaredb".to_string(),
@@ -198,7 +349,7 @@ impl SltArgs {
                     })
                     .with_pg_listener_opt(pg_listener)
                     .with_rpc_listener_opt(rpc_listener)
-                    .with_data_dir(temp_dir.path().to_path_buf())
+                    .with_data_dir(data_dir.clone())
                     .with_location_opt(self.storage_config.location.clone())
                     .with_storage_options(HashMap::from_iter(
                         self.storage_config.storage_options.clone(),
@@ -206,7 +357,7 @@ impl SltArgs {
                     .integration_testing_mode(true)
                     .enable_flight_api(true);
 
-                if matches!(self.protocol, ClientProtocol::Rpc) {
+                if protocols.contains(&ClientProtocol::Rpc) {
                     builder = builder.disable_rpc_auth(true);
                 }
 
@@ -215,35 +366,45 @@ impl SltArgs {
                 tokio::spawn(server.serve());
 
                 let mut configs = HashMap::new();
-                let host = socket_addr.ip().to_string();
-                let port = socket_addr.port();
                 let mut config = ClientConfig::new();
-                config
-                    .user("glaredb")
-                    .password("glaredb")
-                    .dbname("glaredb")
-                    .host(&host)
-                    .port(port);
-
-                tests.iter().for_each(|(name, _)| {
+                config.user("glaredb").password("glaredb").dbname("glaredb");
+
+                runs.iter().for_each(|run| {
+                    let socket_addr = match run.protocol {
+                        ClientProtocol::Postgres => pg_addr,
+                        ClientProtocol::Rpc | ClientProtocol::FlightSql => rpc_addr,
+                    }
+                    .expect("listener to be bound for protocol");
+
                     let mut cfg = config.clone();
                     let db_id = Uuid::new_v4().to_string();
-                    cfg.dbname(&db_id);
-                    configs.insert(name.clone(), cfg);
+                    cfg.dbname(&db_id)
+                        .host(&socket_addr.ip().to_string())
+                        .port(socket_addr.port());
+                    configs.insert(run.name.clone(), cfg);
                 });
 
                 configs
             };
 
+        if let Some(seed) = self.seed {
+            eprintln!("Shuffling tests with seed {seed}");
+            runs.shuffle(&mut StdRng::seed_from_u64(seed));
+        }
+
+        if let Some(sslmode) = self.sslmode {
+            for config in configs.values_mut() {
+                config.ssl_mode(sslmode.into());
+            }
+        }
+
         // Break up into batches.
         //
         // Rust doesn't have a good way of breaking a Vec into a Vec of Vecs
         // with owned references, so do it manually.
         let mut batches = Vec::new();
         loop {
-            let batch: Vec<_> = tests
-                .drain(0..usize::min(batch_size, tests.len()))
-                .collect();
+            let batch: Vec<_> = runs.drain(0..usize::min(batch_size, runs.len())).collect();
             if batch.is_empty() {
                 break;
             }
@@ -252,24 +413,58 @@ impl SltArgs {
 
         let start = Instant::now();
 
+        let mut results = Vec::new();
         for batch in batches {
-            self.run_tests(&configs, batch, hooks.clone(), temp_dir.path())
+            let batch_results = self
+                .run_tests(&configs, batch, hooks.clone(), &data_dir)
                 .await?;
+
+            let batch_errored = batch_results.iter().any(|res| res.result.is_err());
+            results.extend(batch_results);
+
+            if batch_errored && !self.no_fail_fast {
+                break;
+            }
         }
 
         let time_taken = Instant::now().duration_since(start);
         eprintln!("Tests took {time_taken:?} to run");
 
-        Ok(())
+        if let Some(n) = self.timings {
+            print_timings(&results, n);
+        }
+
+        if let Some(path) = &self.output_json {
+            write_json_report(path, &results)?;
+        }
+
+        let failed: Vec<_> = results
+            .iter()
+            .filter(|res| res.result.is_err())
+            .map(|res| res.name.as_str())
+            .collect();
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "{} test(s) failed: {}",
+                failed.len(),
+                failed.join(", ")
+            ))
+        }
     }
 
+    /// Run a batch of tests, returning the result for each test.
+    ///
+    /// Failed tests are reported, but do not cause this to return an error.
     async fn run_tests(
         &self,
         configs: &HashMap<String, ClientConfig>,
-        tests: Vec<(String, Test)>,
+        tests: Vec<TestRun>,
         hooks: TestHooks,
         data_dir: &Path,
-    ) -> Result<()> {
+    ) -> Result<Vec<TestResult>> {
         let (jobs_tx, mut jobs_rx) = mpsc::unbounded_channel();
         let mut total_jobs = if self.jobs > 0 { self.jobs } else { u8::MAX };
 
@@ -278,64 +473,167 @@ impl SltArgs {
 
         let timeout_at = Instant::now() + Duration::from_secs(self.timeout);
 
-        type Res = (String, Result<()>);
+        /// Receive the next result, returning `None` if the deadline passes
+        /// first.
         async fn recv(
-            rx: &mut mpsc::UnboundedReceiver<Res>,
+            rx: &mut mpsc::UnboundedReceiver<TestResult>,
             deadline: Instant,
-        ) -> Result<Option<Res>> {
-            let res = tokio::time::timeout_at(deadline, rx.recv()).await?;
-            Ok(res)
+        ) -> Option<TestResult> {
+            // The sender outlives the receiver, so the channel never closes.
+            tokio::time::timeout_at(deadline, rx.recv())
+                .await
+                .ok()
+                .flatten()
         }
 
+        // Tests that have been spawned but haven't sent a result yet, along
+        // with when they were spawned.
+        let mut running: Vec<(String, ClientProtocol, Instant)> = Vec::new();
+        // Tests that couldn't be spawned before the deadline passed.
+        let mut not_started = Vec::new();
+
         let hooks = Arc::new(hooks);
 
-        for (test_name, test) in tests {
+        let tls = match self.sslmode {
+            Some(SslModeArg::Prefer | SslModeArg::Require) => {
+                Some(PgTlsConfig::new(self.ssl_root_cert.as_deref())?)
+            }
+            Some(SslModeArg::Disable) | None => None,
+        };
+
+        let mut tests = tests.into_iter();
+        for run in tests.by_ref() {
             if total_jobs == 0 {
                 // Wait to receive a result
-                let res = recv(&mut jobs_rx, timeout_at).await?.unwrap();
-                total_jobs += 1;
-                results.push(res);
+                match recv(&mut jobs_rx, timeout_at).await {
+                    Some(res) => {
+                        running.retain(|(name, _, _)| name != &res.name);
+                        total_jobs += 1;
+                        results.push(res);
+                    }
+                    None => {
+                        not_started.push(run);
+                        break;
+                    }
+                }
             }
 
             // Spawn a new job.
             total_jobs -= 1;
-            let cfg = configs.get(&test_name).unwrap().clone();
+            let mut cfg = configs.get(&run.name).unwrap().clone();
             let tx = jobs_tx.clone();
             let hooks = Arc::clone(&hooks);
 
-            let protocol = self.protocol;
             let data_dir = data_dir.to_path_buf();
-
+            let test_timeout = self.test_timeout_for(&run.test_name);
+            let retries = self.retries;
+            let fail_on_warning = self.fail_on_warning;
+            let diff = self.diff;
+            let tls = tls.clone();
+            // Databases can only be created on demand by the embedded server.
+            let fresh_db = self.connection_string.is_none();
+
+            running.push((run.name.clone(), run.protocol, Instant::now()));
             tokio::spawn(async move {
-                let res = Self::run_test(protocol, data_dir, &test_name, test, cfg, hooks).await;
-                tx.send((test_name.clone(), res)).unwrap();
+                let start = Instant::now();
+                let mut attempts = 0;
+                let result = loop {
+                    attempts += 1;
+                    let result = Self::run_test(
+                        run.protocol,
+                        data_dir.clone(),
+                        &run.test_name,
+                        &run.test,
+                        cfg.clone(),
+                        hooks.clone(),
+                        test_timeout,
+                        tls.as_ref(),
+                        fail_on_warning,
+                        diff,
+                    )
+                    .await;
+
+                    match result {
+                        Err(error) if attempts <= retries => {
+                            tracing::warn!(%error, %attempts, "Retrying failed test `{}`", run.name);
+                            tokio::time::sleep(RETRY_BACKOFF * attempts as u32).await;
+                            // Don't retry against state left behind by the
+                            // failed attempt.
+                            if fresh_db {
+                                cfg.dbname(&Uuid::new_v4().to_string());
+                            }
+                        }
+                        result => break result,
+                    }
+                };
+                // The receiver is closed if the deadline passed, in which
+                // case the test has already been recorded as timed out.
+                let _ = tx.send(TestResult {
+                    name: run.name,
+                    protocol: run.protocol,
+                    time_taken: Instant::now().duration_since(start),
+                    attempts,
+                    config: cfg,
+                    result,
+                });
             });
         }
+        not_started.extend(tests);
 
         // Drain all the results.
-        while let Some(res) = recv(&mut jobs_rx, timeout_at).await? {
-            results.push(res);
-
-            // Received everything? Close the channel and exit!
-            if results.len() == num_tests {
-                jobs_rx.close();
-                break;
+        while not_started.is_empty() && !running.is_empty() {
+            match recv(&mut jobs_rx, timeout_at).await {
+                Some(res) => {
+                    running.retain(|(name, _, _)| name != &res.name);
+                    results.push(res);
+                }
+                None => break,
             }
         }
+        jobs_rx.close();
+
+        // Record everything that didn't finish before the deadline as timed
+        // out so that it still shows up in reports.
+        let timeout = Duration::from_secs(self.timeout);
+        let timed_out = running
+            .into_iter()
+            .map(|(name, protocol, start)| {
+                (name, protocol, Instant::now().duration_since(start), 1)
+            })
+            .chain(
+                not_started
+                    .into_iter()
+                    .map(|run| (run.name, run.protocol, Duration::ZERO, 0)),
+            );
+        for (name, protocol, time_taken, attempts) in timed_out {
+            let config = configs.get(&name).unwrap().clone();
+            results.push(TestResult {
+                name,
+                protocol,
+                time_taken,
+                attempts,
+                config,
+                result: Err(TestTimeoutError { timeout }.into()),
+            });
+        }
 
         let mut errored = false;
-        let errors = results.iter().filter_map(|(name, res)| match res {
+        let errors = results.iter().filter_map(|res| match &res.result {
             Ok(_) => None,
-            Err(e) => Some((name, e)),
+            Err(e) => Some((&res.name, res.attempts, &res.config, e)),
         });
 
-        for (name, error) in errors {
+        for (name, attempts, conf, error) in errors {
             errored = true;
-            tracing::error!(%error, "Error while running test `{name}`");
+            tracing::error!(%error, %attempts, "Error while running test `{name}`");
+
+            if self.keep_db.is_some() {
+                let db_dir = data_dir.join("databases").join(conf.get_dbname().unwrap());
+                println!("database for `{name}` kept at:\n  {}\n", db_dir.display());
+            }
 
             // If keep running, then connect to the client and do it!
             if self.connection_string.is_none() && self.keep_running {
-                let conf = configs.get(name).unwrap();
                 let port = conf.get_ports().first().unwrap();
                 let password = String::from_utf8_lossy(conf.get_password().unwrap()).into_owned();
                 let conn_string = format!(
@@ -356,23 +654,37 @@ impl SltArgs {
                 println!("CTRL-C to exit");
                 tokio::signal::ctrl_c().await?;
             }
-            Err(anyhow!("Test failures"))
-        } else {
-            Ok(())
         }
+
+        Ok(results)
     }
 
+    /// Get the timeout for a single test, if one was provided.
+    fn test_timeout_for(&self, test_name: &str) -> Option<Duration> {
+        self.test_timeout
+            .iter()
+            .find(|(pattern, _)| pattern.matches(test_name))
+            .map(|(_, timeout)| *timeout)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn run_test(
         mode: ClientProtocol,
         data_dir: PathBuf,
         test_name: &str,
-        test: Test,
+        test: &Test,
         client_config: ClientConfig,
         hooks: Arc<TestHooks>,
+        timeout: Option<Duration>,
+        tls: Option<&PgTlsConfig>,
+        fail_on_warning: bool,
+        diff: bool,
     ) -> Result<()> {
         tracing::info!("Running test: `{}`", test_name);
         let client = match mode {
-            ClientProtocol::Postgres => TestClient::Pg(PgTestClient::new(&client_config).await?),
+            ClientProtocol::Postgres => {
+                TestClient::Pg(PgTestClient::new(&client_config, tls).await?)
+            }
             ClientProtocol::Rpc => {
                 TestClient::Rpc(RpcTestClient::new(data_dir, &client_config).await?)
             }
@@ -381,7 +693,21 @@ impl SltArgs {
             }
         };
 
-        let res = Self::run_test_inner(&client, test_name, test, &client_config, hooks).await;
+        let fut = Self::run_test_inner(
+            &client,
+            test_name,
+            test,
+            &client_config,
+            hooks,
+            fail_on_warning,
+            diff,
+        );
+        let res = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, fut)
+                .await
+                .unwrap_or_else(|_| Err(TestTimeoutError { timeout }.into())),
+            None => fut.await,
+        };
         // No need to wait for session's close handler since we don't wait for
         // sessions to end in integration testing mode while closing the server.
         let _ = client.close().await;
@@ -391,9 +717,11 @@ impl SltArgs {
     async fn run_test_inner(
         client: &TestClient,
         test_name: &str,
-        test: Test,
+        test: &Test,
         client_config: &ClientConfig,
         hooks: Arc<TestHooks>,
+        fail_on_warning: bool,
+        diff: bool,
     ) -> Result<()> {
         let start = Instant::now();
 
@@ -416,10 +744,26 @@ impl SltArgs {
             }
         }
 
+        if fail_on_warning {
+            // Only count warnings from the test itself, not the hooks.
+            client.take_warnings().await;
+        }
+
         // Run the actual test
-        test.execute(client_config, client.clone(), &mut local_vars)
+        test.execute(client_config, client.clone(), &mut local_vars, diff)
             .await?;
 
+        if fail_on_warning {
+            let warnings = client.take_warnings().await;
+            if !warnings.is_empty() {
+                return Err(anyhow!(
+                    "Test emitted {} warning(s):\n{}",
+                    warnings.len(),
+                    warnings.join("\n")
+                ));
+            }
+        }
+
         // Run the post-test hooks
         for (pattern, hook) in hooks {
             tracing::debug!(%pattern, %test_name, "Running post hook for test");
@@ -433,3 +777,168 @@ impl SltArgs {
         Ok(())
     }
 }
+
+/// SSL modes supported by the test runner.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SslModeArg {
+    Disable,
+    Prefer,
+    Require,
+}
+
+impl From<SslModeArg> for SslMode {
+    fn from(value: SslModeArg) -> Self {
+        match value {
+            SslModeArg::Disable => SslMode::Disable,
+            SslModeArg::Prefer => SslMode::Prefer,
+            SslModeArg::Require => SslMode::Require,
+        }
+    }
+}
+
+/// Time to wait before retrying a failed test. Scaled by the number of
+/// attempts made so far.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// A single test to run using a specific protocol.
+struct TestRun {
+    /// Name used when reporting results, tagged with the protocol if running
+    /// with multiple protocols.
+    name: String,
+    /// Name of the test, used for matching hooks and timeouts.
+    test_name: String,
+    protocol: ClientProtocol,
+    test: Arc<Test>,
+}
+
+/// The result of running a single test.
+struct TestResult {
+    name: String,
+    protocol: ClientProtocol,
+    /// Total time taken across all attempts.
+    time_taken: Duration,
+    /// Number of attempts made to run the test.
+    attempts: usize,
+    /// Client config used for the last attempt.
+    config: ClientConfig,
+    result: Result<()>,
+}
+
+impl TestResult {
+    fn status(&self) -> &'static str {
+        match &self.result {
+            Ok(_) => "pass",
+            Err(e) if e.is::<TestTimeoutError>() => "timeout",
+            Err(_) => "fail",
+        }
+    }
+}
+
+/// Error returned when a single test exceeds its timeout.
+#[derive(Debug)]
+struct TestTimeoutError {
+    timeout: Duration,
+}
+
+impl fmt::Display for TestTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "test timed out after {:?}", self.timeout)
+    }
+}
+
+impl std::error::Error for TestTimeoutError {}
+
+fn parse_test_timeout(s: &str) -> Result<(glob::Pattern, Duration)> {
+    let (pattern, secs) = s.split_once('=').ok_or_else(|| {
+        anyhow!("Expected test timeout in the form of `<pattern>=<seconds>`, got '{s}'")
+    })?;
+    let pattern = glob::Pattern::new(pattern.trim_end_matches(".slt"))
+        .map_err(|e| anyhow!("Invalid glob pattern `{pattern}`: {e}"))?;
+    let secs: u64 = secs
+        .parse()
+        .map_err(|e| anyhow!("Invalid timeout seconds `{secs}`: {e}"))?;
+    Ok((pattern, Duration::from_secs(secs)))
+}
+
+/// Print the `n` slowest tests, slowest first.
+fn print_timings(results: &[TestResult], n: usize) {
+    let mut results: Vec<_> = results.iter().collect();
+    results.sort_by(|a, b| b.time_taken.cmp(&a.time_taken));
+
+    let width = results
+        .iter()
+        .take(n)
+        .map(|res| res.name.len())
+        .max()
+        .unwrap_or_default();
+
+    eprintln!("Slowest tests:");
+    for res in results.into_iter().take(n) {
+        eprintln!(
+            "  {:<width$}  {:>10.3}s  {}",
+            res.name,
+            res.time_taken.as_secs_f64(),
+            res.status(),
+        );
+    }
+}
+
+fn parse_shard(s: &str) -> Result<(u64, u64)> {
+    let (index, total) = s
+        .split_once('/')
+        .ok_or_else(|| anyhow!("Expected shard in the form of `<index>/<total>`, got '{s}'"))?;
+    let index: u64 = index
+        .parse()
+        .map_err(|e| anyhow!("Invalid shard index `{index}`: {e}"))?;
+    let total: u64 = total
+        .parse()
+        .map_err(|e| anyhow!("Invalid shard total `{total}`: {e}"))?;
+    if index >= total {
+        return Err(anyhow!(
+            "Shard index must be less than the total number of shards, got '{s}'"
+        ));
+    }
+    Ok((index, total))
+}
+
+/// FNV-1a hash of a test name.
+///
+/// Unlike the std hashers, this is stable across platforms and compiler
+/// versions so that every machine agrees on the tests in a shard.
+fn stable_hash(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// A single test entry in the JSON report.
+#[derive(Serialize)]
+struct TestReportEntry<'a> {
+    name: &'a str,
+    protocol: String,
+    status: &'static str,
+    duration_ms: u128,
+    attempts: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn write_json_report(path: &Path, results: &[TestResult]) -> Result<()> {
+    let entries: Vec<_> = results
+        .iter()
+        .map(|res| TestReportEntry {
+            name: &res.name,
+            protocol: res.protocol.to_string(),
+            status: res.status(),
+            duration_ms: res.time_taken.as_millis(),
+            attempts: res.attempts,
+            error: res.result.as_ref().err().map(|e| e.to_string()),
+        })
+        .collect();
+
+    let file = std::fs::File::create(path)
+        .map_err(|e| anyhow!("Failed to create report file `{}`: {e}", path.display()))?;
+    serde_json::to_writer_pretty(file, &entries)?;
+
+    Ok(())
+}
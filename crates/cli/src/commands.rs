@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use atty::Stream;
@@ -160,11 +161,13 @@ impl RunCommand for ServerArgs {
         let runtime = build_runtime("server")?;
 
         runtime.block_on(async move {
-            let pg_listener = match self.bind {
-                Some(bind) => Some(TcpListener::bind(bind).await?),
-                None if self.disable_postgres_api => None,
-                None => Some(TcpListener::bind(DEFAULT_PG_BIND_ADDR).await?),
-            };
+            let mut pg_listeners = Vec::with_capacity(self.bind.len());
+            for bind in &self.bind {
+                pg_listeners.push(TcpListener::bind(bind).await?);
+            }
+            if pg_listeners.is_empty() && !self.disable_postgres_api {
+                pg_listeners.push(TcpListener::bind(DEFAULT_PG_BIND_ADDR).await?);
+            }
             let rpc_listener = match self.rpc_bind {
                 Some(bind) => Some(TcpListener::bind(bind).await?),
                 None if self.enable_flight_api => {
@@ -172,11 +175,16 @@ impl RunCommand for ServerArgs {
                 }
                 None => None,
             };
+            let metrics_listener = match self.metrics_bind {
+                Some(bind) => Some(TcpListener::bind(bind).await?),
+                None => None,
+            };
 
             let server = ComputeServer::builder()
                 .with_authenticator(auth)
-                .with_pg_listener_opt(pg_listener)
+                .with_pg_listeners(pg_listeners)
                 .with_rpc_listener_opt(rpc_listener)
+                .with_metrics_listener_opt(metrics_listener)
                 .with_segment_key_opt(segment_key)
                 .with_data_dir_opt(self.data_dir)
                 .with_service_account_path_opt(self.service_account_path)
@@ -185,6 +193,11 @@ impl RunCommand for ServerArgs {
                     self.storage_config.storage_options.clone(),
                 ))
                 .with_spill_path_opt(self.spill_path)
+                .with_max_connections_opt(self.max_connections)
+                .with_object_store_max_concurrent_gets_opt(self.object_store_max_concurrent_gets)
+                .with_idle_timeout_opt(self.idle_timeout_secs.map(Duration::from_secs))
+                .with_ssl_server_cert_opt(self.ssl_server_cert)
+                .with_ssl_server_key_opt(self.ssl_server_key)
                 .with_metastore_bucket_opt(self.metastore_bucket)
                 .disable_rpc_auth(self.disable_rpc_auth)
                 .enable_simple_query_rpc(self.enable_simple_query_rpc)
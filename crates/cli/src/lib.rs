@@ -2,6 +2,7 @@ pub mod args;
 pub mod commands;
 mod highlighter;
 pub mod local;
+mod metrics;
 mod prompt;
 pub mod proxy;
 pub mod server;
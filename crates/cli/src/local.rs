@@ -241,13 +241,19 @@ impl LocalSession {
                     )
                     .await?
                 }
-                res @ (ExecutionResult::CopySuccess
+                res @ (ExecutionResult::CopySuccess { .. }
                 | ExecutionResult::DeleteSuccess { .. }
                 | ExecutionResult::InsertSuccess { .. }
+                | ExecutionResult::OptimizeSuccess { .. }
+                | ExecutionResult::OverwriteSuccess { .. }
                 | ExecutionResult::UpdateSuccess { .. }) => {
                     println!("{}", res);
                     print_time_elapsed(now);
                 }
+                res @ ExecutionResult::Explain { analyze: true, .. } => {
+                    println!("{}", res);
+                    print_time_elapsed(now);
+                }
 
                 other => {
                     println!("{}", other);
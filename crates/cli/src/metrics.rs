@@ -0,0 +1,210 @@
+//! A minimal HTTP endpoint exposing server metrics in the Prometheus text
+//! format, along with a health check.
+
+use std::fmt::Write as _;
+use std::future::Future;
+use std::sync::Arc;
+
+use anyhow::Result;
+use datafusion_ext::session_metrics::ServerMetrics;
+use futures::future::BoxFuture;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, error};
+
+/// Path metrics are served from.
+const METRICS_PATH: &str = "/metrics";
+
+/// Path the health check is served from.
+const HEALTH_PATH: &str = "/health";
+
+/// Checks if the server is able to serve queries, run for every request to
+/// the health check.
+pub type HealthCheck = Arc<dyn Fn() -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+/// Content type for version 0.0.4 of the Prometheus text format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Serve metrics on the given listener until `shutdown` completes.
+///
+/// Only `GET /metrics` and `GET /health` are handled. Every other request
+/// gets a 404. The health check responds with a 503 if `health` errors.
+pub async fn serve_metrics(
+    listener: TcpListener,
+    metrics: Arc<ServerMetrics>,
+    health: HealthCheck,
+    shutdown: impl Future<Output = ()>,
+) {
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            result = listener.accept() => {
+                let (conn, client_addr) = match result {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        error!(%e, "failed to accept metrics connection");
+                        continue;
+                    }
+                };
+                let metrics = metrics.clone();
+                let health = health.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_request(conn, &metrics, &health).await {
+                        debug!(%e, %client_addr, "failed to handle metrics request");
+                    }
+                });
+            }
+        }
+    }
+}
+
+async fn handle_request(
+    mut conn: TcpStream,
+    metrics: &ServerMetrics,
+    health: &HealthCheck,
+) -> Result<()> {
+    // We only care about the request line, so a single read is enough.
+    let mut buf = [0; 1024];
+    let n = conn.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+
+    let mut parts = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(METRICS_PATH)) => {
+            let body = render_metrics(metrics);
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+        }
+        (Some("GET"), Some(HEALTH_PATH)) => match health().await {
+            Ok(()) => {
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok".to_string()
+            }
+            Err(e) => {
+                let body = format!("{e:#}");
+                format!(
+                    "HTTP/1.1 503 Service Unavailable\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+            }
+        },
+        _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+
+    conn.write_all(response.as_bytes()).await?;
+    conn.shutdown().await?;
+    Ok(())
+}
+
+/// Render the metrics in the Prometheus text format.
+fn render_metrics(metrics: &ServerMetrics) -> String {
+    let metrics = [
+        (
+            "glaredb_active_connections",
+            "gauge",
+            "Number of open Postgres client connections.",
+            metrics.active_connections(),
+        ),
+        (
+            "glaredb_queries_total",
+            "counter",
+            "Total number of queries executed.",
+            metrics.queries_total(),
+        ),
+        (
+            "glaredb_query_errors_total",
+            "counter",
+            "Total number of queries that failed.",
+            metrics.query_errors_total(),
+        ),
+        (
+            "glaredb_bytes_read_total",
+            "counter",
+            "Total number of bytes read by data sources.",
+            metrics.bytes_read_total(),
+        ),
+    ];
+
+    let mut out = String::new();
+    for (name, kind, help, value) in metrics {
+        writeln!(out, "# HELP {name} {help}").unwrap();
+        writeln!(out, "# TYPE {name} {kind}").unwrap();
+        writeln!(out, "{name} {value}").unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn serves_metrics() {
+        let listener = TcpListener::bind("localhost:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let metrics = Arc::new(ServerMetrics::default());
+        metrics.connection_opened();
+        let healthy = Arc::new(AtomicBool::new(true));
+        let health: HealthCheck = {
+            let healthy = healthy.clone();
+            Arc::new(move || {
+                let healthy = healthy.load(Ordering::Relaxed);
+                Box::pin(async move {
+                    if healthy {
+                        Ok(())
+                    } else {
+                        Err(anyhow!("metastore unreachable"))
+                    }
+                })
+            })
+        };
+        tokio::spawn(serve_metrics(
+            listener,
+            metrics,
+            health,
+            std::future::pending(),
+        ));
+
+        let get = |path: &'static str| async move {
+            let mut conn = TcpStream::connect(addr).await.unwrap();
+            conn.write_all(format!("GET {path} HTTP/1.1\r\n\r\n").as_bytes())
+                .await
+                .unwrap();
+            let mut resp = String::new();
+            conn.read_to_string(&mut resp).await.unwrap();
+            resp
+        };
+
+        let resp = get("/metrics").await;
+        assert!(resp.starts_with("HTTP/1.1 200 OK"), "{resp}");
+        assert!(resp
+            .contains("# TYPE glaredb_active_connections gauge\nglaredb_active_connections 1\n"));
+        assert!(resp.contains("glaredb_queries_total 0\n"));
+
+        let resp = get("/health").await;
+        assert!(resp.starts_with("HTTP/1.1 200 OK"), "{resp}");
+        assert!(resp.ends_with("\r\n\r\nok"), "{resp}");
+
+        healthy.store(false, Ordering::Relaxed);
+        let resp = get("/health").await;
+        assert!(
+            resp.starts_with("HTTP/1.1 503 Service Unavailable"),
+            "{resp}"
+        );
+        assert!(resp.ends_with("metastore unreachable"), "{resp}");
+
+        let resp = get("/other").await;
+        assert!(resp.starts_with("HTTP/1.1 404 Not Found"), "{resp}");
+    }
+}
@@ -1,14 +1,21 @@
 use std::collections::HashMap;
+use std::future::{self, Future};
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::{env, fs};
+use std::time::{Duration, Instant};
+use std::{env, fs, io};
 
 use anyhow::{anyhow, Result};
+use futures::future::select_all;
 use ioutil::ensure_dir;
 use metastore::local::start_inprocess;
+use object_store::aws::AmazonS3ConfigKey;
+use object_store::ObjectStore;
 use object_store_util::conf::StorageConfig;
-use pgsrv::auth::LocalAuthenticator;
+use object_store_util::registry::ObjectStoreRegistry;
+use pgsrv::auth::{LocalAuthenticator, PasswordlessAuthenticator};
 use pgsrv::handler::{ProtocolHandler, ProtocolHandlerConfig};
+use pgsrv::ssl::SslConfig;
 use protogen::gen::rpcsrv::service::execution_service_server::ExecutionServiceServer;
 use protogen::gen::rpcsrv::simple::simple_service_server::SimpleServiceServer;
 use rpcsrv::flight::handler::{FlightServiceServer, FlightSessionHandler};
@@ -18,16 +25,44 @@ use sqlexec::engine::{Engine, EngineStorageConfig};
 use telemetry::{SegmentTracker, Tracker};
 use tokio::net::TcpListener;
 use tokio::signal;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, Semaphore};
+use tokio::task::JoinSet;
 use tonic::transport::server::{Router, TcpIncoming};
 use tonic::transport::Server;
-use tracing::{debug, debug_span, error, info, Instrument};
+use tracing::{debug, debug_span, error, info, warn, Instrument};
 use uuid::Uuid;
 
+use crate::metrics::{serve_metrics, HealthCheck};
+
+/// How long to wait for active connections and sessions to complete once
+/// shutdown has been triggered.
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// How long to wait for a client refused because of the connection limit to
+/// send its startup message before closing the connection.
+const REFUSE_CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait for the metastore's object store to respond on startup
+/// before giving up.
+const OBJECT_STORE_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Number of attempts made to reach the metastore's object store on startup
+/// when it returns transient errors.
+const OBJECT_STORE_CHECK_ATTEMPTS: u32 = 4;
+
+/// Backoff before the first retry of the object store check. Doubled for every
+/// following retry.
+const OBJECT_STORE_CHECK_BACKOFF: Duration = Duration::from_millis(500);
+
+/// How long a health check may take before the server is considered
+/// unhealthy.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Configuration for initializing the postgres api
 pub struct PostgresProtocolConfig {
-    /// Listener to use for pg handler.
-    listener: TcpListener,
+    /// Listeners to use for pg handler. Connections are accepted on all of
+    /// them.
+    listeners: Vec<TcpListener>,
     /// Handler to use for pg connections.
     handler: Arc<ProtocolHandler>,
 }
@@ -40,13 +75,22 @@ pub struct ComputeServer {
     engine: Arc<Engine>,
     pg_config: Option<PostgresProtocolConfig>,
     rpc_listener: Option<TcpListener>,
+    /// Listener to serve Prometheus metrics on, if set.
+    metrics_listener: Option<TcpListener>,
+    /// Limits the number of concurrent pg connections, if set.
+    connection_limit: Option<Arc<Semaphore>>,
+    /// Object store backing the metastore, if it's separate from the engine's
+    /// storage.
+    metastore_store: Option<Arc<dyn ObjectStore>>,
 }
 
 pub struct ComputeServerBuilder {
-    /// Listener to use for pg handler.
-    pg_listener: Option<TcpListener>,
+    /// Listeners to use for pg handler.
+    pg_listeners: Vec<TcpListener>,
     /// Listener to use for rpc handler.
     rpc_listener: Option<TcpListener>,
+    /// Listener to serve Prometheus metrics on.
+    metrics_listener: Option<TcpListener>,
     segment_key: Option<String>,
     authenticator: Option<Box<dyn LocalAuthenticator>>,
     data_dir: Option<PathBuf>,
@@ -54,18 +98,31 @@ pub struct ComputeServerBuilder {
     location: Option<String>,
     storage_options: HashMap<String, String>,
     spill_path: Option<PathBuf>,
+    max_connections: Option<usize>,
+    /// Maximum number of concurrent GET requests per object store.
+    object_store_max_concurrent_gets: Option<usize>,
+    /// Close pg connections that have been idle for this long.
+    idle_timeout: Option<Duration>,
+    /// Path to the SSL server cert to use for pg connections.
+    ssl_server_cert: Option<String>,
+    /// Path to the SSL server key to use for pg connections.
+    ssl_server_key: Option<String>,
     integration_testing: bool,
     disable_rpc_auth: bool,
     enable_simple_query_rpc: bool,
     enable_flight_api: bool,
+    /// Accept pg connections without a password when no authenticator is
+    /// set.
+    allow_passwordless: bool,
     metastore_bucket: Option<String>,
 }
 
 impl ComputeServerBuilder {
     fn new() -> Self {
         ComputeServerBuilder {
-            pg_listener: None,
+            pg_listeners: Vec::new(),
             rpc_listener: None,
+            metrics_listener: None,
             segment_key: None,
             authenticator: None,
             data_dir: None,
@@ -73,29 +130,47 @@ impl ComputeServerBuilder {
             location: None,
             storage_options: HashMap::new(),
             spill_path: None,
+            max_connections: None,
+            object_store_max_concurrent_gets: None,
+            idle_timeout: None,
+            ssl_server_cert: None,
+            ssl_server_key: None,
             integration_testing: false,
             disable_rpc_auth: false,
             enable_simple_query_rpc: false,
             enable_flight_api: false,
+            allow_passwordless: false,
             metastore_bucket: None,
         }
     }
 
     /// Set the authenticator to use for the pg handler.
+    ///
+    /// Required when serving over the pg protocol, unless passwordless
+    /// connections are allowed.
     pub fn with_authenticator<T: LocalAuthenticator + 'static>(mut self, authenticator: T) -> Self {
         self.authenticator = Some(Box::new(authenticator));
         self
     }
 
     /// Add a tcp listener to use for serving over the pg protocol.
+    ///
+    /// May be called multiple times to accept connections on multiple
+    /// addresses.
     pub fn with_pg_listener(mut self, pg_listener: TcpListener) -> Self {
-        self.pg_listener = Some(pg_listener);
+        self.pg_listeners.push(pg_listener);
         self
     }
 
     /// Optionally add a tcp listener to use for serving over the pg protocol.
     pub fn with_pg_listener_opt(mut self, pg_listener: Option<TcpListener>) -> Self {
-        self.pg_listener = pg_listener;
+        self.pg_listeners.extend(pg_listener);
+        self
+    }
+
+    /// Add tcp listeners to use for serving over the pg protocol.
+    pub fn with_pg_listeners(mut self, pg_listeners: Vec<TcpListener>) -> Self {
+        self.pg_listeners.extend(pg_listeners);
         self
     }
 
@@ -111,6 +186,19 @@ impl ComputeServerBuilder {
         self
     }
 
+    /// Add a tcp listener to serve Prometheus metrics on at `/metrics`, and
+    /// the server's health at `/health`.
+    pub fn with_metrics_listener(mut self, metrics_listener: TcpListener) -> Self {
+        self.metrics_listener = Some(metrics_listener);
+        self
+    }
+
+    /// Optionally add a tcp listener to serve Prometheus metrics on.
+    pub fn with_metrics_listener_opt(mut self, metrics_listener: Option<TcpListener>) -> Self {
+        self.metrics_listener = metrics_listener;
+        self
+    }
+
     pub fn with_segment_key(mut self, segment_key: String) -> Self {
         self.segment_key = Some(segment_key);
         self
@@ -166,6 +254,59 @@ impl ComputeServerBuilder {
         self
     }
 
+    /// Set the maximum number of concurrent pg connections.
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = Some(max_connections);
+        self
+    }
+
+    pub fn with_max_connections_opt(mut self, max_connections: Option<usize>) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Set the maximum number of concurrent GET requests made against each
+    /// object store used by external tables.
+    ///
+    /// The limit applies to the whole process.
+    pub fn with_object_store_max_concurrent_gets(mut self, max: usize) -> Self {
+        self.object_store_max_concurrent_gets = Some(max);
+        self
+    }
+
+    pub fn with_object_store_max_concurrent_gets_opt(mut self, max: Option<usize>) -> Self {
+        self.object_store_max_concurrent_gets = max;
+        self
+    }
+
+    /// Close pg connections that haven't sent a message within `timeout`.
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_idle_timeout_opt(mut self, timeout: Option<Duration>) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Enable SSL/TLS for pg connections using the provided cert and key
+    /// files.
+    pub fn with_ssl_server_cert_and_key(mut self, cert: String, key: String) -> Self {
+        self.ssl_server_cert = Some(cert);
+        self.ssl_server_key = Some(key);
+        self
+    }
+
+    pub fn with_ssl_server_cert_opt(mut self, cert: Option<String>) -> Self {
+        self.ssl_server_cert = cert;
+        self
+    }
+
+    pub fn with_ssl_server_key_opt(mut self, key: Option<String>) -> Self {
+        self.ssl_server_key = key;
+        self
+    }
 
     pub fn with_metastore_bucket(mut self, name: String) -> Self {
         self.metastore_bucket = Some(name);
@@ -197,9 +338,17 @@ impl ComputeServerBuilder {
         self
     }
 
+    /// Accept pg connections without prompting for a password when no
+    /// authenticator is set. Only meant for servers running locally.
+    pub fn allow_passwordless(mut self, allow_passwordless: bool) -> Self {
+        self.allow_passwordless = allow_passwordless;
+        self
+    }
+
     pub async fn connect(self) -> Result<ComputeServer> {
         // Invalid state if we have a pg_listener but no authenticator.
-        if self.pg_listener.is_some() && self.authenticator.is_none() {
+        if !self.pg_listeners.is_empty() && self.authenticator.is_none() && !self.allow_passwordless
+        {
             return Err(anyhow!("pg_listener provided but no authenticator"));
         }
 
@@ -209,6 +358,11 @@ impl ComputeServerBuilder {
         debug!(?env_tmp, "ensuring temp dir");
         fs::create_dir_all(&env_tmp)?;
 
+        if let Some(max) = self.object_store_max_concurrent_gets {
+            debug!(%max, "limiting concurrent object store requests");
+            ObjectStoreRegistry::global().set_max_concurrent_gets(Some(max));
+        }
+
         let tracker = match self.segment_key.clone() {
             Some(key) => {
                 debug!("initializing segment telemetry tracker");
@@ -220,21 +374,36 @@ impl ComputeServerBuilder {
             }
         };
 
-        // Create the `Engine` instance
-        let engine = self.create_engine_from_opts(tracker).await?;
+        // Create the `Engine` instance, loading the ssl config concurrently
+        // since it doesn't depend on the engine.
+        let load_ssl_conf = async {
+            let conf = match (&self.ssl_server_cert, &self.ssl_server_key) {
+                (Some(cert), Some(key)) => Some(SslConfig::new(cert, key).await?),
+                (None, None) => None,
+                _ => {
+                    return Err(anyhow!(
+                        "both or neither of the server key and cert must be provided"
+                    ))
+                }
+            };
+            Ok::<_, anyhow::Error>(conf)
+        };
+        let ((engine, metastore_store), ssl_conf) =
+            tokio::try_join!(self.create_engine_from_opts(tracker), load_ssl_conf)?;
 
-        let pg_config = if let Some(listener) = self.pg_listener {
+        let pg_config = if !self.pg_listeners.is_empty() {
             let handler_conf = ProtocolHandlerConfig {
-                authenticator: self.authenticator.unwrap(),
-                // TODO: Allow specifying SSL/TLS on the GlareDB side as well. I
-                // want to hold off on doing that until we have a shared config
-                // between the proxy and GlareDB.
-                ssl_conf: None,
+                // Only passwordless if explicitly allowed, checked above.
+                authenticator: self
+                    .authenticator
+                    .unwrap_or_else(|| Box::new(PasswordlessAuthenticator::default())),
+                ssl_conf,
                 integration_testing: self.integration_testing,
+                idle_timeout: self.idle_timeout,
             };
             let pg_handler = Arc::new(ProtocolHandler::new(engine.clone(), handler_conf));
             Some(PostgresProtocolConfig {
-                listener,
+                listeners: self.pg_listeners,
                 handler: pg_handler,
             })
         } else {
@@ -250,21 +419,30 @@ impl ComputeServerBuilder {
             pg_config,
             engine,
             rpc_listener: self.rpc_listener,
+            metrics_listener: self.metrics_listener,
+            connection_limit: self
+                .max_connections
+                .map(|max| Arc::new(Semaphore::new(max))),
+            metastore_store,
         })
     }
 
+    /// Create the engine, returning the object store used for the metastore
+    /// if one was created separately from the engine's storage.
     async fn create_engine_from_opts(
         &self,
         tracker: Tracker,
-    ) -> Result<Arc<Engine>, anyhow::Error> {
-        let engine = if let Some(location) = self.location.clone() {
+    ) -> Result<(Arc<Engine>, Option<Arc<dyn ObjectStore>>), anyhow::Error> {
+        let (engine, metastore_store) = if let Some(location) = self.location.clone() {
             // TODO: try to consolidate with --data-dir and --metastore-addr options
+            let start = Instant::now();
             let engine = Engine::from_storage_options(
                 &location,
                 &HashMap::from_iter(self.storage_options.clone()),
             )
             .await?;
-            Arc::new(engine.with_tracker(Arc::new(tracker)))
+            info!(elapsed = ?start.elapsed(), "engine created");
+            (Arc::new(engine.with_tracker(Arc::new(tracker))), None)
         } else {
             // TODO: There's going to need to more validation needed to ensure we're
             // using a metastore that makes sense. E.g. using a remote metastore and
@@ -300,10 +478,11 @@ impl ComputeServerBuilder {
                     "cannot specify local metastore datadir  with service_account_path or bucket"
                 ))
                 }
-                (Some(_), None, None) => {
-                    return Err(anyhow!(
-                        "cannot specify bucket without specifying the service_account_path"
-                    ))
+                (Some(bucket), None, None) => {
+                    // Without a service account, assume the bucket lives in
+                    // an S3 compatible store configured through the standard
+                    // `AWS_*` environment variables.
+                    s3_storage_config_from_env(bucket)?
                 }
                 (None, None, Some(_)) => {
                     return Err(anyhow!(
@@ -323,10 +502,22 @@ impl ComputeServerBuilder {
             };
 
 
-            let metastore_client =
-                start_inprocess(metastore_storage_conf.new_object_store()?).await?;
+            let start = Instant::now();
+            let metastore_store = metastore_storage_conf.new_object_store()?;
+            info!(elapsed = ?start.elapsed(), "metastore object store opened");
 
-            Arc::new(
+            // Check that the store is reachable while the metastore starts.
+            // An unreachable store fails the check instead of leaving startup
+            // hanging.
+            let start = Instant::now();
+            let (_, metastore_client) =
+                tokio::try_join!(check_object_store(metastore_store.as_ref()), async {
+                    Ok::<_, anyhow::Error>(start_inprocess(metastore_store.clone()).await?)
+                },)?;
+            info!(elapsed = ?start.elapsed(), "metastore started");
+
+            let start = Instant::now();
+            let engine = Arc::new(
                 Engine::new(
                     metastore_client,
                     storage_conf,
@@ -334,18 +525,143 @@ impl ComputeServerBuilder {
                     self.spill_path.clone(),
                 )
                 .await?,
-            )
+            );
+            info!(elapsed = ?start.elapsed(), "engine created");
+            (engine, Some(metastore_store))
         };
-        Ok(engine)
+        Ok((engine, metastore_store))
     }
 }
 
+/// Check that an object store is reachable, failing if it doesn't respond
+/// within `OBJECT_STORE_CHECK_TIMEOUT`.
+///
+/// Transient errors are retried with exponential backoff, up to
+/// `OBJECT_STORE_CHECK_ATTEMPTS` attempts.
+async fn check_object_store(store: &dyn ObjectStore) -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        let err = match tokio::time::timeout(
+            OBJECT_STORE_CHECK_TIMEOUT,
+            store.list_with_delimiter(None),
+        )
+        .await
+        {
+            Ok(Ok(_)) => return Ok(()),
+            Ok(Err(e)) => e,
+            Err(_) => return Err(anyhow!(
+                "timed out after {OBJECT_STORE_CHECK_TIMEOUT:?} waiting for metastore object store {store}, check the bucket and credentials"
+            )),
+        };
+
+        if attempt >= OBJECT_STORE_CHECK_ATTEMPTS || !is_transient_store_error(&err) {
+            return Err(anyhow!(
+                "failed to reach metastore object store {store}: {err}"
+            ));
+        }
+
+        let backoff = OBJECT_STORE_CHECK_BACKOFF * 2_u32.pow(attempt - 1);
+        warn!(
+            %err,
+            %attempt,
+            ?backoff,
+            "transient error reaching metastore object store, retrying"
+        );
+        tokio::time::sleep(backoff).await;
+        attempt += 1;
+    }
+}
+
+/// Returns if an object store error might succeed when retried.
+///
+/// Request failures surface as generic errors wrapping the error of the
+/// request, possibly within store specific errors. Timeouts, connection
+/// failures and server errors (5xx) are assumed to be transient. Anything
+/// else, such as bad credentials or a missing bucket, won't resolve itself.
+fn is_transient_store_error(err: &object_store::Error) -> bool {
+    let source = match err {
+        object_store::Error::Generic { source, .. } => source,
+        _ => return false,
+    };
+
+    let mut current: Option<&(dyn std::error::Error + 'static)> = Some(source.as_ref());
+    while let Some(err) = current {
+        if let Some(err) = err.downcast_ref::<object_store_reqwest::Error>() {
+            return err.is_timeout()
+                || err.is_connect()
+                || err.status().is_some_and(|status| status.is_server_error());
+        }
+        if let Some(err) = err.downcast_ref::<io::Error>() {
+            return matches!(
+                err.kind(),
+                io::ErrorKind::TimedOut
+                    | io::ErrorKind::ConnectionRefused
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::UnexpectedEof
+            );
+        }
+        current = err.source();
+    }
+    false
+}
+
+/// Build an S3 storage config for the given bucket using the standard `AWS_*`
+/// environment variables.
+///
+/// Setting `AWS_ENDPOINT` allows for using S3 compatible stores such as MinIO.
+fn s3_storage_config_from_env(bucket: String) -> Result<StorageConfig> {
+    let env_var = |key: AmazonS3ConfigKey| env::var(key.as_ref().to_uppercase()).ok();
+
+    let access_key_id = env_var(AmazonS3ConfigKey::AccessKeyId).ok_or_else(|| {
+        anyhow!("'AWS_ACCESS_KEY_ID' must be set when using a metastore bucket without a service account")
+    })?;
+    let secret_access_key = env_var(AmazonS3ConfigKey::SecretAccessKey).ok_or_else(|| {
+        anyhow!("'AWS_SECRET_ACCESS_KEY' must be set when using a metastore bucket without a service account")
+    })?;
+
+    Ok(StorageConfig::S3 {
+        access_key_id,
+        secret_access_key,
+        region: env_var(AmazonS3ConfigKey::Region),
+        endpoint: env_var(AmazonS3ConfigKey::Endpoint),
+        bucket: Some(bucket),
+    })
+}
+
+/// Check that the engine and the metastore's object store can be reached,
+/// see [`ComputeServer::health`].
+async fn check_health(
+    engine: &Engine,
+    metastore_store: Option<&dyn ObjectStore>,
+) -> Result<()> {
+    let check = async {
+        engine.health_check().await?;
+        if let Some(store) = metastore_store {
+            store.list_with_delimiter(None).await?;
+        }
+        Ok(())
+    };
+    tokio::time::timeout(HEALTH_CHECK_TIMEOUT, check)
+        .await
+        .map_err(|_| anyhow!("health check timed out after {HEALTH_CHECK_TIMEOUT:?}"))?
+}
 
 impl ComputeServer {
     pub fn builder() -> ComputeServerBuilder {
         ComputeServerBuilder::new()
     }
 
+    /// Check that the server is able to serve queries.
+    ///
+    /// Errors if the metastore or any of the object stores backing it and the
+    /// native tables can't be reached within `HEALTH_CHECK_TIMEOUT`. Also
+    /// served from `/health` on the metrics listener, for use as a readiness
+    /// probe.
+    pub async fn health(&self) -> Result<()> {
+        check_health(&self.engine, self.metastore_store.as_deref()).await
+    }
+
     fn build_rpc_service(&self) -> Router {
         // Start rpc service.
         let handler = RpcHandler::new(
@@ -371,18 +687,49 @@ impl ComputeServer {
         server
     }
 
-    /// Serve using the provided config.
+    /// Serve using the provided config, shutting down on ctrl-c.
     pub async fn serve(self) -> Result<()> {
+        self.serve_with_shutdown(async {
+            if let Err(err) = signal::ctrl_c().await {
+                error!(%err, "unable to listen for shutdown signal");
+                // Keep serving, we'll just never be able to shut down
+                // gracefully.
+                future::pending::<()>().await;
+            }
+        })
+        .await
+    }
+
+    /// Serve using the provided config until `signal` completes.
+    ///
+    /// Once signalled, no new connections are accepted, and we wait up to
+    /// `SHUTDOWN_GRACE_PERIOD` for existing connections and sessions to
+    /// finish before returning.
+    pub async fn serve_with_shutdown(self, signal: impl Future<Output = ()>) -> Result<()> {
         let rpc_msg = if let Some(listener) = &self.rpc_listener {
             format!("Connect via RPC: grpc://{}", listener.local_addr()?)
         } else {
             "".to_string()
         };
 
-        let pg_msg = if let Some(PostgresProtocolConfig { ref listener, .. }) = &self.pg_config {
+        let pg_msg = if let Some(PostgresProtocolConfig { listeners, .. }) = &self.pg_config {
+            let mut msgs = Vec::with_capacity(listeners.len());
+            for listener in listeners {
+                msgs.push(format!(
+                    "Connect via Postgres protocol: postgresql://{}",
+                    listener.local_addr()?,
+                ));
+            }
+            msgs.join("\n")
+        } else {
+            "".to_string()
+        };
+
+        let metrics_msg = if let Some(listener) = &self.metrics_listener {
+            let addr = listener.local_addr()?;
             format!(
-                "Connect via Postgres protocol: postgresql://{}",
-                listener.local_addr()?,
+                "Metrics available at: http://{addr}/metrics\n\
+                 Health check at: http://{addr}/health"
             )
         } else {
             "".to_string()
@@ -391,22 +738,24 @@ impl ComputeServer {
         info!(
             "Starting GlareDB {}\n{}",
             env!("CARGO_PKG_VERSION"),
-            [rpc_msg, pg_msg].join("\n"),
+            [rpc_msg, pg_msg, metrics_msg].join("\n"),
         );
 
-        // Shutdown handler.
-
-        let engine = self.engine.clone();
-        let mut rx = spawn_shutdown_handler(engine, self.integration_testing);
-
         // Start rpc service.
+        let (rpc_shutdown_tx, rpc_shutdown_rx) = oneshot::channel::<()>();
         if self.rpc_listener.is_some() {
             let server = self.build_rpc_service();
+            let listener = self.rpc_listener.unwrap();
             tokio::spawn(async move {
-                let incoming =
-                    TcpIncoming::from_listener(self.rpc_listener.unwrap(), true, None).unwrap();
-
-                if let Err(e) = server.serve_with_incoming(incoming).await {
+                let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+                let shutdown = async {
+                    let _ = rpc_shutdown_rx.await;
+                };
+
+                if let Err(e) = server
+                    .serve_with_incoming_shutdown(incoming, shutdown)
+                    .await
+                {
                     // TODO: Maybe panic instead? Revisit once we have
                     // everything working.
                     error!(%e, "rpc service died");
@@ -414,86 +763,141 @@ impl ComputeServer {
             });
         }
 
-        if let Some(PostgresProtocolConfig { listener, handler }) = self.pg_config {
+        let server_metrics = self.engine.server_metrics();
+
+        // Start metrics endpoint.
+        let (metrics_shutdown_tx, metrics_shutdown_rx) = oneshot::channel::<()>();
+        if let Some(listener) = self.metrics_listener {
+            let metrics = server_metrics.clone();
+            let engine = self.engine.clone();
+            let metastore_store = self.metastore_store.clone();
+            let health: HealthCheck = Arc::new(move || {
+                let engine = engine.clone();
+                let metastore_store = metastore_store.clone();
+                Box::pin(async move {
+                    check_health(&engine, metastore_store.as_deref()).await
+                })
+            });
+            tokio::spawn(serve_metrics(listener, metrics, health, async move {
+                let _ = metrics_shutdown_rx.await;
+            }));
+        }
+
+        tokio::pin!(signal);
+        let mut conns = JoinSet::new();
+
+        if let Some(PostgresProtocolConfig { listeners, handler }) = self.pg_config {
             // Postgres handler loop.
             loop {
                 tokio::select! {
-                    _ = &mut rx => {
-                        info!("shutting down");
-                        return Ok(())
-                    }
-
-                result = listener.accept() => {
-                    let (conn, client_addr) = result?;
-
-                    let pg_handler = handler.clone();
-                    let conn_id = Uuid::new_v4();
-                    let span = debug_span!("glaredb_connection", %conn_id);
-
-                    tokio::spawn(
-                        async move {
-                            debug!(%client_addr, "client connected (pg)");
-                            match pg_handler.handle_connection(conn_id, conn).await {
-                                Ok(_) => debug!(%client_addr, "client disconnected"),
-                                Err(e) => debug!(%e, %client_addr, "client disconnected with error"),
+                    _ = &mut signal => break,
+
+                    // Reap finished connections so the set doesn't grow
+                    // unbounded.
+                    Some(_) = conns.join_next(), if !conns.is_empty() => (),
+
+                    // Accepting is cancel safe, so the accepts on listeners
+                    // that lost the race can be dropped.
+                    (result, _, _) = select_all(listeners.iter().map(|l| Box::pin(l.accept()))) => {
+                        let (conn, client_addr) = result?;
+
+                        let permit = match &self.connection_limit {
+                            Some(limit) => match limit.clone().try_acquire_owned() {
+                                Ok(permit) => Some(permit),
+                                Err(_) => {
+                                    warn!(%client_addr, "connection limit reached, refusing connection");
+                                    let pg_handler = handler.clone();
+                                    conns.spawn(async move {
+                                        let refuse = pg_handler.refuse_too_many_clients(conn);
+                                        match tokio::time::timeout(REFUSE_CONNECTION_TIMEOUT, refuse).await {
+                                            Ok(Ok(())) => (),
+                                            Ok(Err(e)) => debug!(%e, %client_addr, "failed to refuse connection"),
+                                            Err(_) => debug!(%client_addr, "timed out refusing connection"),
+                                        }
+                                    });
+                                    continue;
+                                }
+                            },
+                            None => None,
+                        };
+
+                        let pg_handler = handler.clone();
+                        let conn_id = Uuid::new_v4();
+                        let span = debug_span!("glaredb_connection", %conn_id);
+                        let metrics = server_metrics.clone();
+
+                        conns.spawn(
+                            async move {
+                                debug!(%client_addr, "client connected (pg)");
+                                metrics.connection_opened();
+                                match pg_handler.handle_connection(conn_id, conn).await {
+                                    Ok(_) => debug!(%client_addr, "client disconnected"),
+                                    Err(e) => debug!(%e, %client_addr, "client disconnected with error"),
+                                }
+                                metrics.connection_closed();
+                                drop(permit);
                             }
-                        }
-                        .instrument(span),
-                    );
-                }
+                            .instrument(span),
+                        );
+                    }
                 }
             }
         } else {
             // No pg listener. Just wait for shutdown.
-            rx.await.map_err(|_| anyhow!("shutdown error"))
+            signal.await;
         }
-    }
-}
 
-fn spawn_shutdown_handler(
-    engine: Arc<Engine>,
-    is_integration_testing: bool,
-) -> oneshot::Receiver<()> {
-    let (tx, rx) = oneshot::channel();
-    tokio::spawn(async move {
-        match signal::ctrl_c().await {
-            Ok(()) => {
-                info!("shutdown triggered");
-
-                // Don't wait for active-sessions if integration testing is
-                // not set. This helps when doing "CTRL-C" during testing.
-                if !is_integration_testing {
-                    loop {
-                        let sess_count = engine.session_count();
-                        if sess_count == 0 {
-                            break;
-                        }
-
-                        info!(%sess_count, "shutdown prevented, active sessions");
-
-                        // Still have sessions. Keep looping with some sleep in
-                        // between.
-                        tokio::time::sleep(tokio::time::Duration::from_millis(5000)).await;
+        info!("shutdown triggered");
+        let _ = rpc_shutdown_tx.send(());
+        let _ = metrics_shutdown_tx.send(());
+
+        // Don't wait for active sessions if integration testing is set. This
+        // helps when doing "CTRL-C" during testing.
+        if !self.integration_testing {
+            let engine = self.engine.clone();
+            let drain = async {
+                while conns.join_next().await.is_some() {}
+
+                // Sessions may also be held by rpc clients.
+                loop {
+                    let sess_count = engine.session_count();
+                    if sess_count == 0 {
+                        break;
                     }
+
+                    info!(%sess_count, "shutdown delayed, active sessions");
+                    tokio::time::sleep(Duration::from_millis(500)).await;
                 }
+            };
 
-                // Shutdown!
-                let _ = tx.send(());
-            }
-            Err(err) => {
-                error!(%err, "unable to listen for shutdown signal");
+            if tokio::time::timeout(SHUTDOWN_GRACE_PERIOD, drain)
+                .await
+                .is_err()
+            {
+                warn!(
+                    conn_count = %conns.len(),
+                    sess_count = %self.engine.session_count(),
+                    "shutdown grace period elapsed, dropping active connections"
+                );
             }
         }
-    });
-    rx
+
+        info!("shutting down");
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fmt;
     use std::time::Duration;
 
     use pgsrv::auth::SingleUserAuthenticator;
-    use tokio_postgres::{Config as ClientConfig, NoTls};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio_postgres::error::SqlState;
+    use tokio_postgres::types::Type as PgType;
+    use tokio_postgres::{Client, Config as ClientConfig, NoTls};
 
     use super::*;
 
@@ -507,12 +911,252 @@ mod tests {
     // the config values provided (and inversely, that we don't start things up
     // if the config indicates we shouldn't).
 
+    /// Start a server listening for pg connections on a local port, returning
+    /// the config for connecting to it.
+    ///
+    /// `configure` can set any additional options on the builder. The returned
+    /// config doesn't include a password.
+    async fn start_test_server(
+        configure: impl FnOnce(ComputeServerBuilder) -> ComputeServerBuilder,
+    ) -> ClientConfig {
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+        let pg_addr = pg_listener.local_addr().unwrap();
+
+        let server = configure(ComputeServer::builder().with_pg_listener(pg_listener))
+            .connect()
+            .await
+            .unwrap();
+        tokio::spawn(server.serve());
+
+        let mut config = ClientConfig::new();
+        config
+            .user("glaredb")
+            .dbname("glaredb")
+            .host("localhost")
+            .port(pg_addr.port());
+        config
+    }
+
+    /// Authenticate with the password "glaredb".
+    fn password_auth(builder: ComputeServerBuilder) -> ComputeServerBuilder {
+        builder.with_authenticator(SingleUserAuthenticator {
+            user: "glaredb".to_string(),
+            password: "glaredb".to_string(),
+        })
+    }
+
+    /// Connect to a server, driving the connection in the background.
+    async fn connect(config: &ClientConfig) -> Client {
+        let (client, conn) = tokio::time::timeout(Duration::from_secs(5), config.connect(NoTls))
+            .await
+            .unwrap() // Timeout error
+            .unwrap(); // Connect error
+        tokio::spawn(conn);
+        client
+    }
+
     #[tokio::test]
     async fn no_hang_on_rpc_service_start() {
-        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
         let rpc_listener = TcpListener::bind("localhost:0").await.unwrap();
 
-        let pg_addr = pg_listener.local_addr().unwrap();
+        let mut config =
+            start_test_server(|builder| password_auth(builder).with_rpc_listener(rpc_listener))
+                .await;
+        let client = connect(config.password("glaredb")).await;
+
+        tokio::time::timeout(Duration::from_secs(5), client.simple_query("select 1"))
+            .await
+            .unwrap() // Timeout error
+            .unwrap(); // Query error
+    }
+
+    #[tokio::test]
+    async fn accept_on_multiple_listeners() {
+        // Accept connections on a second address in addition to the one set up
+        // by the test server.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let mut config =
+            start_test_server(|builder| password_auth(builder).with_pg_listener(listener)).await;
+        config.password("glaredb");
+
+        let mut other = config.clone();
+        other.host("127.0.0.1").port(port);
+
+        for config in [config, other] {
+            let client = connect(&config).await;
+
+            tokio::time::timeout(Duration::from_secs(5), client.simple_query("select 1"))
+                .await
+                .unwrap() // Timeout error
+                .unwrap(); // Query error
+        }
+    }
+
+    #[tokio::test]
+    async fn requires_authenticator() {
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+
+        let result = ComputeServer::builder()
+            .with_pg_listener(pg_listener)
+            .connect()
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn allow_passwordless() {
+        let config = start_test_server(|builder| builder.allow_passwordless(true)).await;
+        let client = connect(&config).await;
+
+        tokio::time::timeout(Duration::from_secs(5), client.simple_query("select 1"))
+            .await
+            .unwrap() // Timeout error
+            .unwrap(); // Query error
+    }
+
+    #[tokio::test]
+    async fn closes_idle_connections() {
+        let config = start_test_server(|builder| {
+            builder
+                .allow_passwordless(true)
+                .with_idle_timeout(Duration::from_millis(200))
+        })
+        .await;
+
+        let (client, conn) = tokio::time::timeout(Duration::from_secs(5), config.connect(NoTls))
+            .await
+            .unwrap() // Timeout error
+            .unwrap(); // Connect error
+        let conn = tokio::spawn(conn);
+
+        client.simple_query("select 1").await.unwrap();
+
+        // The server closes the connection with a fatal error once it's been
+        // idle long enough.
+        tokio::time::timeout(Duration::from_secs(5), conn)
+            .await
+            .unwrap() // Timeout error
+            .unwrap() // Join error
+            .unwrap_err();
+        client.simple_query("select 1").await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn binds_query_parameters() {
+        let config = start_test_server(|builder| builder.allow_passwordless(true)).await;
+        let client = connect(&config).await;
+
+        // Parameter type inferred from the statement, planned once and
+        // executed with different values.
+        let stmt = client
+            .prepare("select name from (values (1, 'a'), (2, 'b')) as t(id, name) where id = $1")
+            .await
+            .unwrap();
+        assert_eq!(&[PgType::INT8], stmt.params());
+        for (id, expected) in [(1_i64, "a"), (2, "b")] {
+            let row = client.query_one(&stmt, &[&id]).await.unwrap();
+            assert_eq!(expected, row.get::<_, &str>(0));
+        }
+
+        // Parameter type provided by the client.
+        let stmt = client
+            .prepare_typed("select $1", &[PgType::INT4])
+            .await
+            .unwrap();
+        assert_eq!(&[PgType::INT4], stmt.params());
+        let row = client.query_one(&stmt, &[&7_i32]).await.unwrap();
+        assert_eq!(7, row.get::<_, i32>(0));
+    }
+
+    #[tokio::test]
+    async fn kills_running_query() {
+        let config = start_test_server(|builder| builder.allow_passwordless(true)).await;
+
+        let runaway = connect(&config).await;
+        let admin = connect(&config).await;
+
+        let query = tokio::spawn(async move {
+            runaway
+                .simple_query("select * from generate_series(1, 1000000000000)")
+                .await
+        });
+
+        // Wait for the runaway query to show up as active.
+        let conn_id = loop {
+            let row = admin
+                .query_opt(
+                    "select connection_id from glare_catalog.sessions
+                     where status = 'active'
+                       and query_text like '%generate_series%'
+                       and query_text not like '%glare_catalog%'",
+                    &[],
+                )
+                .await
+                .unwrap();
+            match row {
+                Some(row) => break row.get::<_, String>(0),
+                None => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        };
+
+        admin
+            .batch_execute(&format!("KILL '{conn_id}'"))
+            .await
+            .unwrap();
+
+        let err = tokio::time::timeout(Duration::from_secs(5), query)
+            .await
+            .unwrap() // Timeout error
+            .unwrap() // Join error
+            .unwrap_err();
+        assert!(err.to_string().contains("canceled"), "{err}");
+
+        // The connection is still open, but no longer running a query.
+        let err = admin
+            .batch_execute(&format!("KILL '{conn_id}'"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no active query"), "{err}");
+
+        let err = admin
+            .batch_execute("KILL '00000000-0000-0000-0000-000000000000'")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn health_check() {
+        let server = ComputeServer::builder().connect().await.unwrap();
+        server.health().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn health_check_on_metrics_listener() {
+        let metrics_listener = TcpListener::bind("localhost:0").await.unwrap();
+        let metrics_addr = metrics_listener.local_addr().unwrap();
+        start_test_server(|builder| builder.with_metrics_listener(metrics_listener)).await;
+
+        let mut stream = TcpStream::connect(metrics_addr).await.unwrap();
+        stream
+            .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        tokio::time::timeout(Duration::from_secs(5), stream.read_to_string(&mut response))
+            .await
+            .unwrap() // Timeout error
+            .unwrap(); // Read error
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.ends_with("ok"), "{response}");
+    }
+
+    #[tokio::test]
+    async fn serve_with_shutdown_stops_on_signal() {
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+        let rpc_listener = TcpListener::bind("localhost:0").await.unwrap();
 
         let server = ComputeServer::builder()
             .with_authenticator(SingleUserAuthenticator {
@@ -525,28 +1169,106 @@ mod tests {
             .await
             .unwrap();
 
-        tokio::spawn(server.serve());
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let handle = tokio::spawn(server.serve_with_shutdown(async {
+            let _ = shutdown_rx.await;
+        }));
 
-        let (client, conn) = tokio::time::timeout(
-            Duration::from_secs(5),
-            ClientConfig::new()
-                .user("glaredb")
-                .password("glaredb")
-                .dbname("glaredb")
-                .host("localhost")
-                .port(pg_addr.port())
-                .connect(NoTls),
-        )
-        .await
-        .unwrap() // Timeout error
-        .unwrap(); // Connect error
+        shutdown_tx.send(()).unwrap();
 
-        let (conn_err_tx, _conn_err_rx) = oneshot::channel();
-        tokio::spawn(async move { conn_err_tx.send(conn.await) });
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .unwrap() // Timeout error
+            .unwrap() // Join error
+            .unwrap(); // Serve error
+    }
 
-        tokio::time::timeout(Duration::from_secs(5), client.simple_query("select 1"))
+    #[tokio::test]
+    async fn refuse_connections_past_limit() {
+        let mut config =
+            start_test_server(|builder| password_auth(builder).with_max_connections(1)).await;
+        config.password("glaredb");
+
+        let _client = connect(&config).await;
+
+        // Second connection should be refused while the first is held open.
+        let err = tokio::time::timeout(Duration::from_secs(5), config.connect(NoTls))
             .await
             .unwrap() // Timeout error
-            .unwrap(); // Query error
+            .unwrap_err();
+        let err = err.as_db_error().unwrap();
+        assert_eq!("FATAL", err.severity());
+        assert_eq!(&SqlState::TOO_MANY_CONNECTIONS, err.code());
+        assert_eq!("sorry, too many clients already", err.message());
+    }
+
+    #[tokio::test]
+    async fn transient_store_errors() {
+        /// A store specific error wrapping the error of a request.
+        #[derive(Debug)]
+        struct RequestError(Box<dyn std::error::Error + Send + Sync>);
+
+        impl fmt::Display for RequestError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "request failed")
+            }
+        }
+
+        impl std::error::Error for RequestError {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(self.0.as_ref())
+            }
+        }
+
+        let generic =
+            |source: Box<dyn std::error::Error + Send + Sync>| object_store::Error::Generic {
+                store: "test",
+                source: Box::new(RequestError(source)),
+            };
+
+        // Nothing is listening on the port once the listener is dropped.
+        let addr = TcpListener::bind("localhost:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let err = object_store_reqwest::get(format!("http://{addr}"))
+            .await
+            .unwrap_err();
+        assert!(is_transient_store_error(&generic(Box::new(err))));
+
+        assert!(is_transient_store_error(&generic(Box::new(
+            io::Error::from(io::ErrorKind::ConnectionReset)
+        ))));
+        assert!(!is_transient_store_error(&generic(Box::new(
+            io::Error::from(io::ErrorKind::PermissionDenied)
+        ))));
+
+        // Errors that aren't from a failed request, even if their message
+        // looks like one.
+        assert!(!is_transient_store_error(&generic(
+            "HTTP status server error (503 Service Unavailable)".into()
+        )));
+        assert!(!is_transient_store_error(&object_store::Error::NotFound {
+            path: "bucket".to_string(),
+            source: "not found".into(),
+        }));
+    }
+
+    #[tokio::test]
+    async fn ssl_requires_cert_and_key() {
+        let pg_listener = TcpListener::bind("localhost:0").await.unwrap();
+
+        let result = ComputeServer::builder()
+            .with_authenticator(SingleUserAuthenticator {
+                user: "glaredb".to_string(),
+                password: "glaredb".to_string(),
+            })
+            .with_pg_listener(pg_listener)
+            .with_ssl_server_cert_opt(Some("server.crt".to_string()))
+            .connect()
+            .await;
+
+        assert!(result.is_err());
     }
 }
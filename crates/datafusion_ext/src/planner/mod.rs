@@ -29,6 +29,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use datafusion::arrow::datatypes::{DataType, Field, IntervalUnit, Schema, TimeUnit};
 use datafusion::common::config::ConfigOptions;
 use datafusion::common::{
@@ -56,6 +57,8 @@ use parser::sqlparser::ast::{
     TableAlias,
     TimezoneInfo,
 };
+pub use relation::infer_func_for_file;
+pub use statement::InsertSource;
 
 use crate::conversion::convert;
 use crate::functions::FuncParamValue;
@@ -85,6 +88,14 @@ impl IdentNormalizer {
         }
     }
 }
+/// A previous version of a table to scan, from `VERSION AS OF <n>` or
+/// `TIMESTAMP AS OF '<ts>'`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableVersion {
+    Version(i64),
+    Timestamp(DateTime<Utc>),
+}
+
 /// The ContextProvider trait allows the query planner to obtain meta-data about tables and
 /// functions referenced in SQL statements
 #[async_trait]
@@ -92,6 +103,13 @@ pub trait AsyncContextProvider: Send + Sync {
     /// Getter for a datasource
     async fn get_table_source(&mut self, name: TableReference<'_>) -> Result<Arc<dyn TableSource>>;
 
+    /// Getter for a previous version of a datasource (time travel).
+    async fn get_table_source_at_version(
+        &mut self,
+        name: TableReference<'_>,
+        version: TableVersion,
+    ) -> Result<Arc<dyn TableSource>>;
+
     /// Get a table returning function.
     ///
     /// Note that this accepts a table reference since these functions are
@@ -19,7 +19,9 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use async_recursion::async_recursion;
-use datafusion::common::{DataFusionError, GetExt, OwnedTableReference, Result};
+use chrono::Utc;
+use datafusion::arrow::compute::kernels::cast_utils::string_to_datetime;
+use datafusion::common::{plan_err, DataFusionError, GetExt, OwnedTableReference, Result};
 use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::logical_expr::{LogicalPlan, LogicalPlanBuilder};
 use datafusion::scalar::ScalarValue;
@@ -27,7 +29,7 @@ use datafusion::sql::planner::PlannerContext;
 use parser::sqlparser::ast;
 
 use crate::functions::FuncParamValue;
-use crate::planner::{AsyncContextProvider, SqlQueryPlanner};
+use crate::planner::{AsyncContextProvider, SqlQueryPlanner, TableVersion};
 
 mod join;
 
@@ -44,6 +46,7 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
                 mut name,
                 alias,
                 args,
+                with_hints,
                 ..
             } => {
                 if name.0.len() == 1 && name.0[0].quote_style == Some('\'') {
@@ -73,10 +76,16 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
 
                     // normalize name and alias
                     let table_ref = self.object_name_to_table_reference(name)?;
+                    let version = table_version_from_hints(&with_hints)?;
                     let mut unnamed_args = Vec::new();
                     let mut named_args = HashMap::new();
 
                     match args {
+                        Some(_) if version.is_some() => {
+                            return plan_err!(
+                                "Time travel is not supported for table function '{table_ref}'"
+                            );
+                        }
                         Some(args) => {
                             // Table factor has arguments, look up table returning
                             // function.
@@ -107,16 +116,29 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
                             let table_name = table_ref.to_string();
 
                             let cte = planner_context.get_cte(&table_name);
-                            let plan = if let Some(cte_plan) = cte {
-                                cte_plan.clone()
-                            } else {
-                                let provider = self
-                                    .context_provider
-                                    .get_table_source(table_ref.clone())
-                                    .await?;
-                                let plan_builder =
-                                    LogicalPlanBuilder::scan(table_ref, provider, None)?;
-                                plan_builder.build()?
+                            let plan = match (cte, version) {
+                                (Some(_), Some(_)) => {
+                                    return plan_err!(
+                                        "Time travel is not supported for CTE '{table_name}'"
+                                    );
+                                }
+                                (Some(cte_plan), None) => cte_plan.clone(),
+                                (None, Some(version)) => {
+                                    let provider = self
+                                        .context_provider
+                                        .get_table_source_at_version(table_ref.clone(), version)
+                                        .await?;
+                                    LogicalPlanBuilder::scan(table_ref, provider, None)?.build()?
+                                }
+                                (None, None) => {
+                                    let provider = self
+                                        .context_provider
+                                        .get_table_source(table_ref.clone())
+                                        .await?;
+                                    let plan_builder =
+                                        LogicalPlanBuilder::scan(table_ref, provider, None)?;
+                                    plan_builder.build()?
+                                }
                             };
                             (plan, alias)
                         }
@@ -234,9 +256,50 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
     }
 }
 
+/// Get the table version to scan from the table hints.
+///
+/// The parser rewrites `VERSION AS OF <n>` and `TIMESTAMP AS OF '<ts>'` into
+/// the hints `("version" = <n>)` and `("timestamp" = '<ts>')`. Other hints are
+/// ignored.
+fn table_version_from_hints(hints: &[ast::Expr]) -> Result<Option<TableVersion>> {
+    let mut version = None;
+    for hint in hints {
+        let (name, value) = match hint {
+            ast::Expr::BinaryOp {
+                left,
+                op: ast::BinaryOperator::Eq,
+                right,
+            } => match left.as_ref() {
+                ast::Expr::Identifier(ident) => (ident.value.to_lowercase(), right.as_ref()),
+                _ => continue,
+            },
+            _ => continue,
+        };
+
+        let hint_version = match (name.as_str(), value) {
+            ("version", ast::Expr::Value(ast::Value::Number(n, _))) => match n.parse::<i64>() {
+                Ok(n) if n >= 0 => TableVersion::Version(n),
+                _ => return plan_err!("Invalid table version: {n}"),
+            },
+            ("timestamp", ast::Expr::Value(ast::Value::SingleQuotedString(s))) => {
+                match string_to_datetime(&Utc, s) {
+                    Ok(ts) => TableVersion::Timestamp(ts),
+                    Err(e) => return plan_err!("Invalid table timestamp '{s}': {e}"),
+                }
+            }
+            _ => continue,
+        };
+
+        if version.replace(hint_version).is_some() {
+            return plan_err!("Only one of VERSION AS OF or TIMESTAMP AS OF may be specified");
+        }
+    }
+    Ok(version)
+}
+
 /// Returns a reference to table func by inferring which function to use from a
 /// given path.
-fn infer_func_for_file(path: &str) -> Result<OwnedTableReference> {
+pub fn infer_func_for_file(path: &str) -> Result<OwnedTableReference> {
     let ext = Path::new(path)
         .extension()
         .ok_or_else(|| DataFusionError::Plan(format!("missing file extension: {path}")))?
@@ -19,7 +19,7 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 use async_recursion::async_recursion;
-use datafusion::common::{not_impl_err, plan_err, DataFusionError, Result};
+use datafusion::common::{not_impl_err, plan_err, DataFusionError, Result, UnnestOptions};
 use datafusion::logical_expr::expr::Alias;
 use datafusion::logical_expr::expr_rewriter::{
     normalize_col,
@@ -44,8 +44,12 @@ use datafusion::prelude::Column;
 use datafusion::sql::planner::PlannerContext;
 use parser::sqlparser::ast::{
     Distinct,
+    ExcludeSelectItem,
     Expr as SQLExpr,
+    FunctionArg,
+    FunctionArgExpr,
     GroupByExpr,
+    Ident,
     NamedWindowDefinition,
     ReplaceSelectItem,
     Select,
@@ -100,6 +104,11 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
             .plan_selection(select.selection, plan, planner_context)
             .await?;
 
+        // expand `unnest` calls in the projection into rows
+        let base_plan = self
+            .plan_unnest(base_plan, &mut select.projection, planner_context)
+            .await?;
+
         // handle named windows before processing the projection expression
         check_conflicting_windows(&select.named_window)?;
         match_window_definitions(&mut select.projection, &select.named_window)?;
@@ -350,10 +359,92 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
         }
     }
 
+    /// Expand an `unnest(<array>)` call in the projection into rows.
+    ///
+    /// The array is unnested into one row per element, repeating the other
+    /// columns of the input row. Empty and null arrays produce no rows. The
+    /// call in the projection is replaced with a reference to the unnested
+    /// column, named `unnest` unless aliased.
+    ///
+    /// Only one `unnest` per projection is supported, and only as a top-level
+    /// select expression.
+    async fn plan_unnest(
+        &mut self,
+        plan: LogicalPlan,
+        projection: &mut [SelectItem],
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        const UNNEST_COLUMN: &str = "__unnest";
+
+        let mut array_expr = None;
+        for item in projection.iter_mut() {
+            let (expr, alias) = match item {
+                SelectItem::UnnamedExpr(expr) => (expr, None),
+                SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.clone())),
+                _ => continue,
+            };
+            let arg = match unnest_arg(expr) {
+                Some(arg) => arg.clone(),
+                None => continue,
+            };
+            if array_expr.is_some() {
+                return not_impl_err!("Multiple UNNEST expressions in a projection");
+            }
+            array_expr = Some(
+                self.sql_expr_to_logical_expr(arg, plan.schema(), planner_context)
+                    .await?,
+            );
+            *item = SelectItem::ExprWithAlias {
+                expr: SQLExpr::Identifier(Ident::new(UNNEST_COLUMN)),
+                alias: alias.unwrap_or_else(|| Ident::new("unnest")),
+            };
+        }
+
+        let array_expr = match array_expr {
+            Some(expr) => expr,
+            None => return Ok(plan),
+        };
+
+        // Keep the unnested column out of unqualified wildcards. Qualified
+        // wildcards already skip it since it has no qualifier.
+        for item in projection.iter_mut() {
+            if let SelectItem::Wildcard(options) = item {
+                let ident = Ident::new(UNNEST_COLUMN);
+                options.opt_exclude = Some(match options.opt_exclude.take() {
+                    None => ExcludeSelectItem::Single(ident),
+                    Some(ExcludeSelectItem::Single(other)) => {
+                        ExcludeSelectItem::Multiple(vec![other, ident])
+                    }
+                    Some(ExcludeSelectItem::Multiple(mut idents)) => {
+                        idents.push(ident);
+                        ExcludeSelectItem::Multiple(idents)
+                    }
+                });
+            }
+        }
+
+        let mut exprs: Vec<_> = plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect();
+        exprs.push(array_expr.alias(UNNEST_COLUMN));
+
+        let plan = LogicalPlanBuilder::from(plan)
+            .project(exprs)?
+            .unnest_column_with_options(
+                Column::from_name(UNNEST_COLUMN),
+                UnnestOptions::new().with_preserve_nulls(false),
+            )?
+            .build()?;
+        Ok(plan)
+    }
+
     /// Returns the `Expr`'s corresponding to a SQL query's SELECT expressions.
     ///
     /// Wildcards are expanded into the concrete list of columns.
-    async fn prepare_select_exprs(
+    pub(crate) async fn prepare_select_exprs(
         &mut self,
         plan: &LogicalPlan,
         projection: Vec<SelectItem>,
@@ -640,6 +731,24 @@ fn check_conflicting_windows(window_defs: &[NamedWindowDefinition]) -> Result<()
     Ok(())
 }
 
+/// Get the argument of an `unnest(<expr>)` call.
+fn unnest_arg(expr: &SQLExpr) -> Option<&SQLExpr> {
+    let func = match expr {
+        SQLExpr::Function(func) => func,
+        _ => return None,
+    };
+    if func.over.is_some() || func.name.0.len() != 1 {
+        return None;
+    }
+    if !func.name.0[0].value.eq_ignore_ascii_case("unnest") {
+        return None;
+    }
+    match func.args.as_slice() {
+        [FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))] => Some(arg),
+        _ => None,
+    }
+}
+
 // If the projection is done over a named window, that window
 // name must be defined. Otherwise, it gives an error.
 fn match_window_definitions(
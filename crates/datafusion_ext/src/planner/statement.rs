@@ -1,11 +1,10 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+use datafusion::arrow::datatypes::{DataType, Field, FieldRef, Schema};
 use datafusion::common::{
     plan_datafusion_err,
     plan_err,
-    unqualified_field_not_found,
-    DFField,
     DFSchema,
     DataFusionError,
     OwnedTableReference,
@@ -16,17 +15,33 @@ use datafusion::logical_expr::builder::project;
 use datafusion::logical_expr::{
     Analyze,
     Explain,
+    Expr,
     ExprSchemable,
     LogicalPlan,
+    LogicalPlanBuilder,
     PlanType,
     ToStringifiedPlan,
 };
 use datafusion::scalar::ScalarValue;
 use datafusion::sql::planner::PlannerContext;
-use parser::sqlparser::ast::{self, Query, SetExpr, Statement, Value};
+use parser::sqlparser::ast::{self, Query, SelectItem, SetExpr, Statement, Value};
+use protogen::metastore::types::options::InternalColumnDefinition;
 
+use crate::functions::FuncParamValue;
 use crate::planner::{AsyncContextProvider, SqlQueryPlanner};
 
+/// The rows to insert into a table.
+pub enum InsertSource {
+    /// The output of a query, e.g. `INSERT INTO t SELECT ...` or `VALUES`.
+    Query(Box<Query>),
+    /// A scan of a table function, e.g. the files read by `COPY ... FROM`.
+    TableFunction {
+        func: OwnedTableReference,
+        args: Vec<FuncParamValue>,
+        opts: HashMap<String, FuncParamValue>,
+    },
+}
+
 impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
     /// Generate a plan for EXPLAIN ... that will print out a plan
     pub async fn explain_statement_to_plan(
@@ -69,8 +84,100 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
         &mut self,
         table_name: &OwnedTableReference,
         columns: &Vec<String>,
-        source: Box<Query>,
+        source: InsertSource,
     ) -> Result<LogicalPlan> {
+        let (plan, _) = self
+            .plan_insert_source(table_name, columns, source, false)
+            .await?;
+        Ok(plan)
+    }
+
+    /// Plan the source of an insert, evolving the table's schema to fit the
+    /// source instead of casting the source to the table's schema.
+    ///
+    /// Table columns are widened to the type of the source column if that
+    /// doesn't lose any existing values (see
+    /// [`InternalColumnDefinition::is_widening`]). Other type differences are
+    /// cast the same as a normal insert. Source columns that don't exist in
+    /// the table are added as nullable columns, using the source column's name
+    /// when no column list is provided.
+    ///
+    /// Returns the source plan along with the evolved table schema.
+    pub async fn insert_to_evolved_source_plan(
+        &mut self,
+        table_name: &OwnedTableReference,
+        columns: &Vec<String>,
+        source: InsertSource,
+    ) -> Result<(LogicalPlan, Schema)> {
+        self.plan_insert_source(table_name, columns, source, true)
+            .await
+    }
+
+    /// Plan the expressions of an insert's RETURNING clause.
+    ///
+    /// Expressions are planned against `source`, the rows being inserted as
+    /// planned by `insert_to_source_plan`, so they may reference any of the
+    /// table's columns.
+    pub async fn insert_returning_to_exprs(
+        &mut self,
+        source: &LogicalPlan,
+        returning: Vec<SelectItem>,
+    ) -> Result<Vec<Expr>> {
+        self.prepare_select_exprs(source, returning, false, &mut PlannerContext::new())
+            .await
+    }
+
+    /// Plan the query providing the rows of an insert into `fields`.
+    ///
+    /// Placeholders in a `VALUES` source take the type of the column they're
+    /// inserted into.
+    async fn plan_insert_query(
+        &mut self,
+        query: Box<Query>,
+        fields: &[Option<FieldRef>],
+    ) -> Result<LogicalPlan> {
+        // infer types for Values clause... other types should be resolvable the regular way
+        let mut prepare_param_data_types = BTreeMap::new();
+        if let SetExpr::Values(ast::Values { rows, .. }) = (*query.body).clone() {
+            for row in rows.iter() {
+                for (idx, val) in row.iter().enumerate() {
+                    if let ast::Expr::Value(Value::Placeholder(name)) = val {
+                        let name =
+                            name.replace('$', "").parse::<usize>().map_err(|_| {
+                                plan_datafusion_err!("Can't parse placeholder: {name}")
+                            })? - 1;
+                        let field = fields.get(idx).ok_or_else(|| {
+                            plan_datafusion_err!(
+                                "Placeholder ${} refers to a non existent column",
+                                idx + 1
+                            )
+                        })?;
+                        // Columns being added have no type until the source
+                        // is planned.
+                        if let Some(field) = field {
+                            let dt = field.data_type().clone();
+                            let _ = prepare_param_data_types.insert(name, dt);
+                        }
+                    }
+                }
+            }
+        }
+        let prepare_param_data_types = prepare_param_data_types.into_values().collect();
+
+        // Projection
+        let mut planner_context =
+            PlannerContext::new().with_prepare_param_data_types(prepare_param_data_types);
+        self.query_to_plan_with_context(*query, &mut planner_context)
+            .await
+    }
+
+    async fn plan_insert_source(
+        &mut self,
+        table_name: &OwnedTableReference,
+        columns: &Vec<String>,
+        source: InsertSource,
+        evolve: bool,
+    ) -> Result<(LogicalPlan, Schema)> {
         // Do a table lookup to verify the table exists
         let table_source = self
             .context_provider
@@ -78,7 +185,7 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
             .await?;
 
         let arrow_schema = (*table_source.schema()).clone();
-        let table_schema = DFSchema::try_from(arrow_schema)?;
+        let table_schema = DFSchema::try_from(arrow_schema.clone())?;
 
         // Get insert fields and target table's value indices
         //
@@ -87,10 +194,19 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
         //
         // if value_indices[i] = None, it means that the value of the i-th target table's column is
         // not provided, and should be filled with a default value later.
-        let (fields, value_indices) = if columns.is_empty() {
+        //
+        // When evolving the schema, columns missing from the table are
+        // collected in `new_columns` along with the index of the source output
+        // providing their values, and have no field.
+        let mut new_columns = Vec::new();
+        let (mut fields, mut value_indices) = if columns.is_empty() {
             // Empty means we're inserting into all columns of the table
             (
-                table_schema.fields().clone(),
+                table_schema
+                    .fields()
+                    .iter()
+                    .map(|f| Some(f.field().clone()))
+                    .collect::<Vec<_>>(),
                 (0..table_schema.fields().len())
                     .map(Some)
                     .collect::<Vec<_>>(),
@@ -101,9 +217,19 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
                 .iter()
                 .enumerate()
                 .map(|(i, c)| {
-                    let column_index = table_schema
-                        .index_of_column_by_name(None, c)?
-                        .ok_or_else(|| unqualified_field_not_found(c, &table_schema))?;
+                    let column_index = match table_schema.index_of_column_by_name(None, c)? {
+                        Some(idx) => idx,
+                        None if evolve => {
+                            new_columns.push((c.clone(), i));
+                            return Ok(None);
+                        }
+                        None => {
+                            return plan_err!(
+                                "column \"{c}\" of relation \"{}\" does not exist",
+                                table_name.table()
+                            )
+                        }
+                    };
                     if value_indices[column_index].is_some() {
                         return Err(DataFusionError::SchemaError(
                             datafusion::common::SchemaError::DuplicateUnqualifiedField {
@@ -114,51 +240,70 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
                     } else {
                         value_indices[column_index] = Some(i);
                     }
-                    Ok(table_schema.field(column_index).clone())
+                    Ok(Some(table_schema.field(column_index).field().clone()))
                 })
-                .collect::<Result<Vec<DFField>>>()?;
+                .collect::<Result<Vec<Option<FieldRef>>>>()?;
             (fields, value_indices)
         };
 
-        // infer types for Values clause... other types should be resolvable the regular way
-        let mut prepare_param_data_types = BTreeMap::new();
-        if let SetExpr::Values(ast::Values { rows, .. }) = (*source.body).clone() {
-            for row in rows.iter() {
-                for (idx, val) in row.iter().enumerate() {
-                    if let ast::Expr::Value(Value::Placeholder(name)) = val {
-                        let name =
-                            name.replace('$', "").parse::<usize>().map_err(|_| {
-                                plan_datafusion_err!("Can't parse placeholder: {name}")
-                            })? - 1;
-                        let field = fields.get(idx).ok_or_else(|| {
-                            plan_datafusion_err!(
-                                "Placeholder ${} refers to a non existent column",
-                                idx + 1
-                            )
-                        })?;
-                        let dt = field.field().data_type().clone();
-                        let _ = prepare_param_data_types.insert(name, dt);
-                    }
-                }
+        let source = match source {
+            InsertSource::Query(query) => self.plan_insert_query(query, &fields).await?,
+            InsertSource::TableFunction { func, args, opts } => {
+                let provider = self
+                    .context_provider
+                    .get_table_function_source(func.clone(), args, opts)
+                    .await?;
+                LogicalPlanBuilder::scan(func, provider, None)?.build()?
+            }
+        };
+        let source_fields = source.schema().fields();
+        if evolve && columns.is_empty() {
+            // Any extra source columns are added to the table.
+            for (i, field) in source_fields.iter().enumerate().skip(fields.len()) {
+                new_columns.push((field.name().clone(), i));
+                fields.push(None);
             }
         }
-        let prepare_param_data_types = prepare_param_data_types.into_values().collect();
+        // Source outputs are matched to target columns by position.
+        if fields.len() > source_fields.len() {
+            plan_err!("INSERT has more target columns than expressions")?;
+        }
+        if fields.len() < source_fields.len() {
+            plan_err!("INSERT has more expressions than target columns")?;
+        }
 
-        // Projection
-        let mut planner_context =
-            PlannerContext::new().with_prepare_param_data_types(prepare_param_data_types);
-        let source = self
-            .query_to_plan_with_context(*source, &mut planner_context)
-            .await?;
-        if fields.len() != source.schema().fields().len() {
-            plan_err!("Column count doesn't match insert query!")?;
+        let mut table_fields: Vec<FieldRef> = arrow_schema.fields().iter().cloned().collect();
+        if evolve {
+            for (i, value_index) in value_indices.iter().enumerate() {
+                let Some(v) = value_index else {
+                    continue;
+                };
+                let source_type = source_fields[*v].data_type();
+                // Integer literals are planned as Int64, but e.g. inserting
+                // `1` shouldn't widen an Int32 column.
+                if InternalColumnDefinition::is_widening(table_fields[i].data_type(), source_type)
+                    && !literals_fit(&source, *v, table_fields[i].data_type())
+                {
+                    table_fields[i] = Arc::new(
+                        table_fields[i]
+                            .as_ref()
+                            .clone()
+                            .with_data_type(source_type.clone()),
+                    );
+                }
+            }
+            for (name, v) in new_columns {
+                let data_type = source_fields[v].data_type().clone();
+                table_fields.push(Arc::new(Field::new(name, data_type, true)));
+                value_indices.push(Some(v));
+            }
         }
 
         let exprs = value_indices
             .into_iter()
             .enumerate()
             .map(|(i, value_index)| {
-                let target_field = table_schema.field(i);
+                let target_field = &table_fields[i];
                 let expr = match value_index {
                     Some(v) => {
                         let source_field = source.schema().field(v);
@@ -179,6 +324,35 @@ impl<'a, S: AsyncContextProvider> SqlQueryPlanner<'a, S> {
             })
             .collect::<Result<Vec<datafusion::logical_expr::Expr>>>()?;
         let source = project(source, exprs)?;
-        Ok(source)
+        Ok((source, Schema::new(table_fields)))
+    }
+}
+
+/// Check if the `idx`th output of `source` only ever comes from literals that
+/// can be cast to `data_type` without losing information.
+fn literals_fit(source: &LogicalPlan, idx: usize, data_type: &DataType) -> bool {
+    let exprs: Vec<&Expr> = match source {
+        LogicalPlan::Values(values) => values
+            .values
+            .iter()
+            .filter_map(|row| row.get(idx))
+            .collect(),
+        LogicalPlan::Projection(projection) => projection.expr.get(idx).into_iter().collect(),
+        _ => return false,
+    };
+    exprs.into_iter().all(|expr| literal_fits(expr, data_type))
+}
+
+fn literal_fits(expr: &Expr, data_type: &DataType) -> bool {
+    match expr {
+        Expr::Alias(alias) => literal_fits(&alias.expr, data_type),
+        // Casting fails on overflow, and casting back catches anything else
+        // lost in the cast (e.g. a fractional part).
+        Expr::Literal(value) => value
+            .cast_to(data_type)
+            .and_then(|cast| cast.cast_to(&value.data_type()))
+            .map(|roundtrip| &roundtrip == value)
+            .unwrap_or(false),
+        _ => false,
     }
 }
@@ -1,4 +1,5 @@
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
@@ -9,13 +10,67 @@ use datafusion::physical_plan::{ExecutionPlan, RecordBatchStream, SendableRecord
 use futures::stream::{Stream, StreamExt};
 use serde_json::json;
 use telemetry::Tracker;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use crate::metrics::AggregatedMetrics;
+use crate::session_registry::SessionHandle;
 
 /// Result type used when we don't know the result of a query yet.
 const UNKNOWN_RESULT_TYPE: &str = "unknown";
 
+/// Counters aggregated across every session of a server.
+///
+/// Exposed to operators through the server's metrics endpoint.
+#[derive(Debug, Default)]
+pub struct ServerMetrics {
+    active_connections: AtomicU64,
+    queries_total: AtomicU64,
+    query_errors_total: AtomicU64,
+    bytes_read_total: AtomicU64,
+}
+
+impl ServerMetrics {
+    pub fn connection_opened(&self) {
+        self.active_connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn connection_closed(&self) {
+        self.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Number of currently open Postgres client connections.
+    pub fn active_connections(&self) -> u64 {
+        self.active_connections.load(Ordering::Relaxed)
+    }
+
+    /// Total number of queries executed, including failed queries.
+    pub fn queries_total(&self) -> u64 {
+        self.queries_total.load(Ordering::Relaxed)
+    }
+
+    /// Total number of queries that failed.
+    pub fn query_errors_total(&self) -> u64 {
+        self.query_errors_total.load(Ordering::Relaxed)
+    }
+
+    /// Total number of bytes read by data sources across all queries.
+    pub fn bytes_read_total(&self) -> u64 {
+        self.bytes_read_total.load(Ordering::Relaxed)
+    }
+
+    fn record_query(&self, metric: &QueryMetrics) {
+        self.queries_total.fetch_add(1, Ordering::Relaxed);
+        if matches!(metric.execution_status, ExecutionStatus::Fail) {
+            self.query_errors_total.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(bytes_read) = metric.bytes_read {
+            self.bytes_read_total
+                .fetch_add(bytes_read, Ordering::Relaxed);
+        }
+    }
+}
+
 /// Pushes metrics to the telemetry tracker for the open session.
 #[derive(Debug, Clone)]
 pub struct SessionMetricsHandler {
@@ -23,6 +78,10 @@ pub struct SessionMetricsHandler {
     database_id: Uuid,
     connection_id: Uuid,
     tracker: Arc<Tracker>,
+    server_metrics: Arc<ServerMetrics>,
+    /// Handle for reporting the status of the session to the server's session
+    /// registry.
+    session: Option<Arc<SessionHandle>>,
 }
 
 impl SessionMetricsHandler {
@@ -31,19 +90,51 @@ impl SessionMetricsHandler {
         database_id: Uuid,
         connection_id: Uuid,
         tracker: Arc<Tracker>,
+        server_metrics: Arc<ServerMetrics>,
     ) -> SessionMetricsHandler {
         SessionMetricsHandler {
             user_id,
             database_id,
             connection_id,
             tracker,
+            server_metrics,
+            session: None,
+        }
+    }
+
+    /// Report query starts and completions to the session registry through
+    /// `session`.
+    pub fn with_session_handle(mut self, session: SessionHandle) -> SessionMetricsHandler {
+        self.session = Some(Arc::new(session));
+        self
+    }
+
+    /// Mark the session as executing a query that can be canceled with
+    /// `cancel`.
+    pub fn query_started(&self, query_text: &str, cancel: CancellationToken) {
+        if let Some(session) = &self.session {
+            session.query_started(query_text, cancel);
+        }
+    }
+
+    /// Mark the session as idle.
+    ///
+    /// Only needed for queries that don't push a metric, pushing a metric
+    /// marks the session as idle.
+    pub fn query_finished(&self) {
+        if let Some(session) = &self.session {
+            session.query_finished();
         }
     }
 
     /// Push a metrics directly into the metrics vector.
     ///
-    /// This will also push the metric out to Segment.
+    /// This will also push the metric out to Segment, and update the server
+    /// wide counters.
     pub fn push_metric(&self, metric: QueryMetrics) {
+        self.server_metrics.record_query(&metric);
+        self.query_finished();
+
         self.tracker.track(
             "Execution metric",
             self.user_id,
@@ -0,0 +1,287 @@
+//! Registry of the sessions open on a server, exposing what each session is
+//! currently doing.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::vars::SessionVars;
+
+/// What a session is currently doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatus {
+    /// Waiting for a query from the client.
+    Idle,
+    /// Executing a query.
+    Active,
+}
+
+impl SessionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionStatus::Idle => "idle",
+            SessionStatus::Active => "active",
+        }
+    }
+}
+
+/// A snapshot of a registered session.
+#[derive(Debug, Clone)]
+pub struct SessionInfo {
+    pub connection_id: Uuid,
+    pub user_name: String,
+    pub database_name: String,
+    pub connected_at: DateTime<Utc>,
+    pub status: SessionStatus,
+    /// Text of the query currently executing, or of the last query executed
+    /// if the session is idle.
+    pub query_text: Option<String>,
+    /// When the current (or last) query started.
+    pub query_started_at: Option<DateTime<Utc>>,
+}
+
+/// The database and user a session is connected as.
+///
+/// Sessions can only see and cancel the sessions of the same user on the
+/// same database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionOwner {
+    pub database_id: Uuid,
+    pub user_id: Uuid,
+}
+
+impl SessionOwner {
+    pub fn from_vars(vars: &SessionVars) -> Self {
+        SessionOwner {
+            database_id: vars.database_id(),
+            user_id: vars.user_id(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CancelQueryError {
+    #[error("Connection not found: {0}")]
+    ConnectionNotFound(Uuid),
+    #[error("Connection has no active query: {0}")]
+    NoActiveQuery(Uuid),
+}
+
+#[derive(Debug)]
+struct SessionEntry {
+    info: SessionInfo,
+    owner: SessionOwner,
+    /// Cancels the currently executing query.
+    cancel: Option<CancellationToken>,
+}
+
+/// Sessions currently open on a server, keyed by connection id.
+#[derive(Debug, Default)]
+pub struct SessionRegistry {
+    sessions: Mutex<HashMap<Uuid, SessionEntry>>,
+}
+
+impl SessionRegistry {
+    /// Register a new idle session.
+    ///
+    /// The session is removed from the registry once the returned handle is
+    /// dropped.
+    pub fn register(
+        self: &Arc<Self>,
+        connection_id: Uuid,
+        owner: SessionOwner,
+        user_name: impl Into<String>,
+        database_name: impl Into<String>,
+    ) -> SessionHandle {
+        let info = SessionInfo {
+            connection_id,
+            user_name: user_name.into(),
+            database_name: database_name.into(),
+            connected_at: Utc::now(),
+            status: SessionStatus::Idle,
+            query_text: None,
+            query_started_at: None,
+        };
+        let entry = SessionEntry {
+            info,
+            owner,
+            cancel: None,
+        };
+        self.sessions.lock().insert(connection_id, entry);
+
+        SessionHandle {
+            registry: self.clone(),
+            connection_id,
+        }
+    }
+
+    /// Get a snapshot of the registered sessions belonging to `owner`,
+    /// ordered by connection time.
+    pub fn sessions(&self, owner: SessionOwner) -> Vec<SessionInfo> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .lock()
+            .values()
+            .filter(|entry| entry.owner == owner)
+            .map(|entry| entry.info.clone())
+            .collect();
+        sessions.sort_by_key(|info| info.connected_at);
+        sessions
+    }
+
+    /// Cancel the query currently executing on a connection belonging to
+    /// `owner`.
+    ///
+    /// Connections of other owners are reported as not found, so that their
+    /// existence isn't revealed.
+    pub fn cancel_query(
+        &self,
+        connection_id: Uuid,
+        owner: SessionOwner,
+    ) -> Result<(), CancelQueryError> {
+        let sessions = self.sessions.lock();
+        let entry = sessions
+            .get(&connection_id)
+            .filter(|entry| entry.owner == owner)
+            .ok_or(CancelQueryError::ConnectionNotFound(connection_id))?;
+        match &entry.cancel {
+            Some(cancel) if entry.info.status == SessionStatus::Active => {
+                cancel.cancel();
+                Ok(())
+            }
+            _ => Err(CancelQueryError::NoActiveQuery(connection_id)),
+        }
+    }
+
+    fn update(&self, connection_id: Uuid, f: impl FnOnce(&mut SessionEntry)) {
+        if let Some(entry) = self.sessions.lock().get_mut(&connection_id) {
+            f(entry)
+        }
+    }
+}
+
+/// Handle for updating the status of a registered session.
+#[derive(Debug)]
+pub struct SessionHandle {
+    registry: Arc<SessionRegistry>,
+    connection_id: Uuid,
+}
+
+impl SessionHandle {
+    /// Mark the session as executing `query_text`. Canceling the query
+    /// through the registry cancels `cancel`.
+    pub fn query_started(&self, query_text: &str, cancel: CancellationToken) {
+        self.registry.update(self.connection_id, |entry| {
+            entry.info.status = SessionStatus::Active;
+            entry.info.query_text = Some(query_text.to_string());
+            entry.info.query_started_at = Some(Utc::now());
+            entry.cancel = Some(cancel);
+        })
+    }
+
+    /// Mark the session as idle.
+    pub fn query_finished(&self) {
+        self.registry.update(self.connection_id, |entry| {
+            entry.info.status = SessionStatus::Idle;
+            entry.cancel = None;
+        })
+    }
+}
+
+impl Drop for SessionHandle {
+    fn drop(&mut self) {
+        self.registry.sessions.lock().remove(&self.connection_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_owner() -> SessionOwner {
+        SessionOwner {
+            database_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn register_and_update() {
+        let registry = Arc::new(SessionRegistry::default());
+        let owner = new_owner();
+        let handle = registry.register(Uuid::new_v4(), owner, "user", "db");
+
+        let sessions = registry.sessions(owner);
+        assert_eq!(1, sessions.len());
+        assert_eq!(SessionStatus::Idle, sessions[0].status);
+
+        handle.query_started("select 1", CancellationToken::new());
+        let sessions = registry.sessions(owner);
+        assert_eq!(SessionStatus::Active, sessions[0].status);
+        assert_eq!(Some("select 1"), sessions[0].query_text.as_deref());
+
+        handle.query_finished();
+        let sessions = registry.sessions(owner);
+        assert_eq!(SessionStatus::Idle, sessions[0].status);
+        assert_eq!(Some("select 1"), sessions[0].query_text.as_deref());
+
+        drop(handle);
+        assert!(registry.sessions(owner).is_empty());
+    }
+
+    #[test]
+    fn sessions_of_owner() {
+        let registry = Arc::new(SessionRegistry::default());
+        let owner = new_owner();
+        let other_user = SessionOwner {
+            user_id: Uuid::new_v4(),
+            ..owner
+        };
+        let _h1 = registry.register(Uuid::new_v4(), owner, "user", "db");
+        let _h2 = registry.register(Uuid::new_v4(), other_user, "other", "db");
+        let _h3 = registry.register(Uuid::new_v4(), new_owner(), "user", "other");
+
+        let sessions = registry.sessions(owner);
+        assert_eq!(1, sessions.len());
+        assert_eq!("user", sessions[0].user_name);
+        assert_eq!("db", sessions[0].database_name);
+    }
+
+    #[test]
+    fn cancel_query() {
+        let registry = Arc::new(SessionRegistry::default());
+        let owner = new_owner();
+        let conn_id = Uuid::new_v4();
+        let handle = registry.register(conn_id, owner, "user", "db");
+
+        let err = registry.cancel_query(Uuid::new_v4(), owner).unwrap_err();
+        assert!(matches!(err, CancelQueryError::ConnectionNotFound(_)));
+        let err = registry.cancel_query(conn_id, owner).unwrap_err();
+        assert!(matches!(err, CancelQueryError::NoActiveQuery(_)));
+
+        let cancel = CancellationToken::new();
+        handle.query_started("select 1", cancel.clone());
+
+        // Other users can't cancel the query, even on the same database.
+        let other_user = SessionOwner {
+            user_id: Uuid::new_v4(),
+            ..owner
+        };
+        let err = registry.cancel_query(conn_id, other_user).unwrap_err();
+        assert!(matches!(err, CancelQueryError::ConnectionNotFound(_)));
+        let err = registry.cancel_query(conn_id, new_owner()).unwrap_err();
+        assert!(matches!(err, CancelQueryError::ConnectionNotFound(_)));
+        assert!(!cancel.is_cancelled());
+
+        registry.cancel_query(conn_id, owner).unwrap();
+        assert!(cancel.is_cancelled());
+
+        handle.query_finished();
+        let err = registry.cancel_query(conn_id, owner).unwrap_err();
+        assert!(matches!(err, CancelQueryError::NoActiveQuery(_)));
+    }
+}
@@ -77,6 +77,7 @@ impl SessionVars {
      client_encoding: String,
      extra_floating_digits: i32,
      statement_timeout: i32,
+     batch_size: usize,
      timezone: String,
      datestyle: String,
      transaction_isolation: String,
@@ -93,11 +94,16 @@ impl SessionVars {
      database_name: String,
      max_datasource_count: Option<usize>,
      memory_limit_bytes: Option<usize>,
+     query_memory_limit_bytes: usize,
      max_tunnel_count: Option<usize>,
      max_credentials_count: Option<usize>,
      is_cloud_instance: bool,
      dialect: Dialect,
      enable_experimental_scheduler: bool,
+     enable_schema_evolution: bool,
+     preserve_insert_order: bool,
+     vacuum_enforce_retention: bool,
+     read_only: bool,
     }
 }
 
@@ -210,6 +216,9 @@ impl SessionVars {
     pub fn with_is_cloud_instance(self, value: bool, setter: VarType) -> Self {
         with_property!(self, is_cloud_instance, setter, value)
     }
+    pub fn with_read_only(self, value: bool, setter: VarType) -> Self {
+        with_property!(self, read_only, setter, value)
+    }
 }
 
 impl ConfigExtension for SessionVars {
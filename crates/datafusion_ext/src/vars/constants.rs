@@ -43,6 +43,18 @@ pub(super) const STATEMENT_TIMEOUT: ServerVar<i32> = ServerVar {
     description: "Statement timeout in milliseconds",
 };
 
+pub(super) const BATCH_SIZE: ServerVar<usize> = ServerVar {
+    name: "batch_size",
+    value: &8192,
+    group: "glaredb",
+    user_configurable: true,
+    description: "Target number of rows in record batches produced during execution",
+};
+
+/// Bounds for `batch_size`.
+pub(super) const MIN_BATCH_SIZE: usize = 1;
+pub(super) const MAX_BATCH_SIZE: usize = 1024 * 1024;
+
 pub(super) const TIMEZONE: ServerVar<str> = ServerVar {
     name: "TimeZone",
     value: "UTC",
@@ -182,6 +194,14 @@ pub(super) const MEMORY_LIMIT_BYTES: ServerVar<Option<usize>> = ServerVar {
     description: "Memory limit in bytes",
 };
 
+pub(super) const QUERY_MEMORY_LIMIT_BYTES: ServerVar<usize> = ServerVar {
+    name: "query_memory_limit_bytes",
+    value: &0,
+    group: "glaredb",
+    user_configurable: true,
+    description: "Memory limit in bytes for a single query, sorts and aggregates spill to disk once reached. 0 means no limit",
+};
+
 pub(super) const MAX_TUNNEL_COUNT: ServerVar<Option<usize>> = ServerVar {
     name: "max_tunnel_count",
     value: &None,
@@ -222,6 +242,39 @@ pub(super) const ENABLE_EXPERIMENTAL_SCHEDULER: ServerVar<bool> = ServerVar {
     description: "If the experimental query scheduler should be enabled",
 };
 
+pub(super) const ENABLE_SCHEMA_EVOLUTION: ServerVar<bool> = ServerVar {
+    name: "enable_schema_evolution",
+    value: &false,
+    group: "glaredb",
+    user_configurable: true,
+    description: "If inserts into native tables may widen column types and add columns",
+};
+
+pub(super) const PRESERVE_INSERT_ORDER: ServerVar<bool> = ServerVar {
+    name: "preserve_insert_order",
+    value: &false,
+    group: "glaredb",
+    user_configurable: true,
+    description: "If inserts into native tables should write rows in the order of the input",
+};
+
+pub(super) const VACUUM_ENFORCE_RETENTION: ServerVar<bool> = ServerVar {
+    name: "vacuum_enforce_retention",
+    value: &true,
+    group: "glaredb",
+    user_configurable: true,
+    description:
+        "If VACUUM should reject retention periods shorter than the table's configured retention",
+};
+
+pub(super) const READ_ONLY: ServerVar<bool> = ServerVar {
+    name: "read_only",
+    value: &false,
+    group: "glaredb",
+    user_configurable: true,
+    description: "If the session rejects statements that modify data or the catalog",
+};
+
 /// Note that these are not normally shown in the search path.
 pub(super) const IMPLICIT_SCHEMAS: [&str; 2] = [
     POSTGRES_SCHEMA,
@@ -13,6 +13,7 @@ use uuid::Uuid;
 
 use super::constants::{
     APPLICATION_NAME,
+    BATCH_SIZE,
     CLIENT_ENCODING,
     CLIENT_MIN_MESSAGES,
     CONNECTION_ID,
@@ -22,14 +23,20 @@ use super::constants::{
     DIALECT,
     ENABLE_DEBUG_DATASOURCES,
     ENABLE_EXPERIMENTAL_SCHEDULER,
+    ENABLE_SCHEMA_EVOLUTION,
     EXTRA_FLOAT_DIGITS,
     FORCE_CATALOG_REFRESH,
     GLAREDB_VERSION,
     IS_CLOUD_INSTANCE,
+    MAX_BATCH_SIZE,
     MAX_CREDENTIALS_COUNT,
     MAX_DATASOURCE_COUNT,
     MAX_TUNNEL_COUNT,
     MEMORY_LIMIT_BYTES,
+    MIN_BATCH_SIZE,
+    PRESERVE_INSERT_ORDER,
+    QUERY_MEMORY_LIMIT_BYTES,
+    READ_ONLY,
     REMOTE_SESSION_ID,
     SEARCH_PATH,
     SERVER_VERSION,
@@ -39,6 +46,7 @@ use super::constants::{
     TRANSACTION_ISOLATION,
     USER_ID,
     USER_NAME,
+    VACUUM_ENFORCE_RETENTION,
 };
 use super::error::VarError;
 use super::value::Value;
@@ -58,6 +66,7 @@ pub struct SessionVarsInner {
     pub client_encoding: SessionVar<str>,
     pub extra_floating_digits: SessionVar<i32>,
     pub statement_timeout: SessionVar<i32>,
+    pub batch_size: SessionVar<usize>,
     pub timezone: SessionVar<str>,
     pub datestyle: SessionVar<str>,
     pub transaction_isolation: SessionVar<str>,
@@ -75,11 +84,16 @@ pub struct SessionVarsInner {
     pub database_name: SessionVar<str>,
     pub max_datasource_count: SessionVar<Option<usize>>,
     pub memory_limit_bytes: SessionVar<Option<usize>>,
+    pub query_memory_limit_bytes: SessionVar<usize>,
     pub max_tunnel_count: SessionVar<Option<usize>>,
     pub max_credentials_count: SessionVar<Option<usize>>,
     pub is_cloud_instance: SessionVar<bool>,
     pub dialect: SessionVar<Dialect>,
     pub enable_experimental_scheduler: SessionVar<bool>,
+    pub enable_schema_evolution: SessionVar<bool>,
+    pub preserve_insert_order: SessionVar<bool>,
+    pub vacuum_enforce_retention: SessionVar<bool>,
+    pub read_only: SessionVar<bool>,
 }
 
 impl SessionVarsInner {
@@ -106,6 +120,8 @@ impl SessionVarsInner {
             Ok(&self.extra_floating_digits)
         } else if name.eq_ignore_ascii_case(STATEMENT_TIMEOUT.name) {
             Ok(&self.statement_timeout)
+        } else if name.eq_ignore_ascii_case(BATCH_SIZE.name) {
+            Ok(&self.batch_size)
         } else if name.eq_ignore_ascii_case(TIMEZONE.name) {
             Ok(&self.timezone)
         } else if name.eq_ignore_ascii_case(DATESTYLE.name) {
@@ -140,6 +156,8 @@ impl SessionVarsInner {
             Ok(&self.max_datasource_count)
         } else if name.eq_ignore_ascii_case(MEMORY_LIMIT_BYTES.name) {
             Ok(&self.memory_limit_bytes)
+        } else if name.eq_ignore_ascii_case(QUERY_MEMORY_LIMIT_BYTES.name) {
+            Ok(&self.query_memory_limit_bytes)
         } else if name.eq_ignore_ascii_case(MAX_TUNNEL_COUNT.name) {
             Ok(&self.max_tunnel_count)
         } else if name.eq_ignore_ascii_case(MAX_CREDENTIALS_COUNT.name) {
@@ -150,6 +168,14 @@ impl SessionVarsInner {
             Ok(&self.dialect)
         } else if name.eq_ignore_ascii_case(ENABLE_EXPERIMENTAL_SCHEDULER.name) {
             Ok(&self.enable_experimental_scheduler)
+        } else if name.eq_ignore_ascii_case(ENABLE_SCHEMA_EVOLUTION.name) {
+            Ok(&self.enable_schema_evolution)
+        } else if name.eq_ignore_ascii_case(PRESERVE_INSERT_ORDER.name) {
+            Ok(&self.preserve_insert_order)
+        } else if name.eq_ignore_ascii_case(VACUUM_ENFORCE_RETENTION.name) {
+            Ok(&self.vacuum_enforce_retention)
+        } else if name.eq_ignore_ascii_case(READ_ONLY.name) {
+            Ok(&self.read_only)
         } else {
             Err(VarError::UnknownVariable(name.to_string()).into())
         }
@@ -167,6 +193,17 @@ impl SessionVarsInner {
             self.extra_floating_digits.set_from_str(val, setter)
         } else if name.eq_ignore_ascii_case(STATEMENT_TIMEOUT.name) {
             self.statement_timeout.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(BATCH_SIZE.name) {
+            match val.parse::<usize>() {
+                Ok(size) if (MIN_BATCH_SIZE..=MAX_BATCH_SIZE).contains(&size) => {
+                    self.batch_size.set_raw(size, setter)
+                }
+                _ => Err(VarError::InvalidSessionVarValue {
+                    name: BATCH_SIZE.name.to_string(),
+                    val: val.to_string(),
+                }
+                .into()),
+            }
         } else if name.eq_ignore_ascii_case(TIMEZONE.name) {
             self.timezone.set_from_str(val, setter)
         } else if name.eq_ignore_ascii_case(DATESTYLE.name) {
@@ -201,6 +238,8 @@ impl SessionVarsInner {
             self.max_datasource_count.set_from_str(val, setter)
         } else if name.eq_ignore_ascii_case(MEMORY_LIMIT_BYTES.name) {
             self.memory_limit_bytes.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(QUERY_MEMORY_LIMIT_BYTES.name) {
+            self.query_memory_limit_bytes.set_from_str(val, setter)
         } else if name.eq_ignore_ascii_case(MAX_TUNNEL_COUNT.name) {
             self.max_tunnel_count.set_from_str(val, setter)
         } else if name.eq_ignore_ascii_case(MAX_CREDENTIALS_COUNT.name) {
@@ -209,6 +248,18 @@ impl SessionVarsInner {
             self.dialect.set_from_str(val, setter)
         } else if name.eq_ignore_ascii_case(ENABLE_EXPERIMENTAL_SCHEDULER.name) {
             self.enable_experimental_scheduler.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(ENABLE_SCHEMA_EVOLUTION.name) {
+            self.enable_schema_evolution.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(PRESERVE_INSERT_ORDER.name) {
+            self.preserve_insert_order.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(VACUUM_ENFORCE_RETENTION.name) {
+            self.vacuum_enforce_retention.set_from_str(val, setter)
+        } else if name.eq_ignore_ascii_case(READ_ONLY.name) {
+            // Once read only, users can't make the session writable again.
+            if *self.read_only.value() && matches!(setter, VarType::UserDefined) {
+                return Err(VarError::VariableReadonly(READ_ONLY.name.to_string()).into());
+            }
+            self.read_only.set_from_str(val, setter)
         } else {
             Err(VarError::UnknownVariable(name.to_string()).into())
         }
@@ -220,6 +271,7 @@ impl SessionVarsInner {
             self.client_encoding.config_entry(),
             self.extra_floating_digits.config_entry(),
             self.statement_timeout.config_entry(),
+            self.batch_size.config_entry(),
             self.timezone.config_entry(),
             self.datestyle.config_entry(),
             self.transaction_isolation.config_entry(),
@@ -235,10 +287,15 @@ impl SessionVarsInner {
             self.database_name.config_entry(),
             self.max_datasource_count.config_entry(),
             self.memory_limit_bytes.config_entry(),
+            self.query_memory_limit_bytes.config_entry(),
             self.max_tunnel_count.config_entry(),
             self.max_credentials_count.config_entry(),
             self.is_cloud_instance.config_entry(),
             self.dialect.config_entry(),
+            self.enable_schema_evolution.config_entry(),
+            self.preserve_insert_order.config_entry(),
+            self.vacuum_enforce_retention.config_entry(),
+            self.read_only.config_entry(),
         ]
     }
 }
@@ -250,6 +307,7 @@ impl Default for SessionVarsInner {
             client_encoding: SessionVar::new(&CLIENT_ENCODING),
             extra_floating_digits: SessionVar::new(&EXTRA_FLOAT_DIGITS),
             statement_timeout: SessionVar::new(&STATEMENT_TIMEOUT),
+            batch_size: SessionVar::new(&BATCH_SIZE),
             timezone: SessionVar::new(&TIMEZONE),
             datestyle: SessionVar::new(&DATESTYLE),
             transaction_isolation: SessionVar::new(&TRANSACTION_ISOLATION),
@@ -267,11 +325,16 @@ impl Default for SessionVarsInner {
             database_name: SessionVar::new(&DATABASE_NAME),
             max_datasource_count: SessionVar::new(&MAX_DATASOURCE_COUNT),
             memory_limit_bytes: SessionVar::new(&MEMORY_LIMIT_BYTES),
+            query_memory_limit_bytes: SessionVar::new(&QUERY_MEMORY_LIMIT_BYTES),
             max_tunnel_count: SessionVar::new(&MAX_TUNNEL_COUNT),
             max_credentials_count: SessionVar::new(&MAX_CREDENTIALS_COUNT),
             is_cloud_instance: SessionVar::new(&IS_CLOUD_INSTANCE),
             dialect: SessionVar::new(&DIALECT),
             enable_experimental_scheduler: SessionVar::new(&ENABLE_EXPERIMENTAL_SCHEDULER),
+            enable_schema_evolution: SessionVar::new(&ENABLE_SCHEMA_EVOLUTION),
+            preserve_insert_order: SessionVar::new(&PRESERVE_INSERT_ORDER),
+            vacuum_enforce_retention: SessionVar::new(&VACUUM_ENFORCE_RETENTION),
+            read_only: SessionVar::new(&READ_ONLY),
         }
     }
 }
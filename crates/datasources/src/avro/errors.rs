@@ -0,0 +1,37 @@
+use datafusion::error::DataFusionError;
+use datafusion_ext::errors::ExtensionError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AvroError {
+    #[error("Unsupported Avro type in '{location}': {typ}")]
+    UnsupportedType { location: String, typ: String },
+
+    #[error("no objects found at path: {0}")]
+    NotFound(String),
+
+    #[error(transparent)]
+    Avro(#[from] apache_avro::Error),
+
+    #[error(transparent)]
+    ObjectStoreSource(#[from] crate::object_store::errors::ObjectStoreSourceError),
+
+    #[error(transparent)]
+    ObjectStore(#[from] object_store::Error),
+
+    #[error(transparent)]
+    Datafusion(#[from] datafusion::error::DataFusionError),
+}
+
+impl From<AvroError> for ExtensionError {
+    fn from(e: AvroError) -> Self {
+        ExtensionError::String(e.to_string())
+    }
+}
+
+impl From<AvroError> for DataFusionError {
+    fn from(e: AvroError) -> Self {
+        DataFusionError::External(Box::new(e))
+    }
+}
+
+pub type Result<T, E = AvroError> = std::result::Result<T, E>;
@@ -0,0 +1,147 @@
+//! Avro files read from object storage.
+
+pub mod errors;
+
+use std::sync::Arc;
+
+use apache_avro::{Reader, Schema as AvroSchema};
+use datafusion::datasource::file_format::avro::AvroFormat;
+use datafusion::datasource::TableProvider;
+use datafusion::execution::context::SessionState;
+use object_store::{ObjectMeta, ObjectStore};
+
+use crate::avro::errors::{AvroError, Result};
+use crate::common::url::DatasourceUrl;
+use crate::object_store::{ObjStoreAccess, ObjStoreAccessor};
+
+/// Number of bytes fetched when reading the header of an object. The whole
+/// object is fetched if the header doesn't fit.
+const HEADER_FETCH_SIZE: usize = 64 * 1024;
+
+/// Create a table provider for one or more Avro objects.
+///
+/// The arrow schema is derived from the Avro schema embedded in the objects.
+/// Every object is checked for types that can't be converted to arrow before
+/// the table is created.
+pub async fn avro_table(
+    store_access: Arc<dyn ObjStoreAccess>,
+    source_url: DatasourceUrl,
+    state: &SessionState,
+) -> Result<Arc<dyn TableProvider>> {
+    let path = source_url.path().into_owned();
+
+    let accessor = ObjStoreAccessor::new(store_access)?;
+
+    let mut list = accessor.list_globbed(&path).await?;
+    if list.is_empty() {
+        return Err(AvroError::NotFound(path));
+    }
+    list.sort_by(|a, b| a.location.cmp(&b.location));
+
+    let store = accessor.clone().into_object_store();
+    for obj in &list {
+        let schema = read_writer_schema(store.as_ref(), obj).await?;
+        check_supported(&schema).map_err(|typ| AvroError::UnsupportedType {
+            location: obj.location.to_string(),
+            typ,
+        })?;
+    }
+
+    Ok(accessor
+        .into_table_provider(state, Arc::new(AvroFormat), list)
+        .await?)
+}
+
+/// Read the schema an Avro object was written with from its header.
+async fn read_writer_schema(store: &dyn ObjectStore, obj: &ObjectMeta) -> Result<AvroSchema> {
+    let len = obj.size.min(HEADER_FETCH_SIZE);
+    let bytes = store.get_range(&obj.location, 0..len).await?;
+    match Reader::new(bytes.as_ref()) {
+        Ok(reader) => Ok(reader.writer_schema().clone()),
+        Err(_) if len < obj.size => {
+            let bytes = store.get(&obj.location).await?.bytes().await?;
+            Ok(Reader::new(bytes.as_ref())?.writer_schema().clone())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Check that every type in the schema can be read into arrow, returning a
+/// description of the first type that can't.
+fn check_supported(schema: &AvroSchema) -> Result<(), String> {
+    match schema {
+        AvroSchema::Array(inner) | AvroSchema::Map(inner) => check_supported(inner),
+        AvroSchema::Record(record) => record
+            .fields
+            .iter()
+            .try_for_each(|field| check_supported(&field.schema)),
+        AvroSchema::Union(union) => {
+            // Only nullable types are supported, e.g. `["null", "long"]`.
+            let non_null: Vec<_> = union
+                .variants()
+                .iter()
+                .filter(|variant| !matches!(variant, AvroSchema::Null))
+                .collect();
+            match non_null.as_slice() {
+                [inner] => check_supported(inner),
+                _ => Err(format!("union with {} non-null variants", non_null.len())),
+            }
+        }
+        AvroSchema::Ref { name } => Err(format!("reference to named type '{}'", name.name)),
+        AvroSchema::Duration => Err("duration".to_string()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supported_types() {
+        let schema = AvroSchema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "test",
+                "fields": [
+                    {"name": "id", "type": "long"},
+                    {"name": "name", "type": ["null", "string"]},
+                    {"name": "tags", "type": {"type": "array", "items": "string"}},
+                    {"name": "ts", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+                ]
+            }"#,
+        )
+        .unwrap();
+        check_supported(&schema).unwrap();
+    }
+
+    #[test]
+    fn unsupported_types() {
+        let schema = AvroSchema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "test",
+                "fields": [
+                    {"name": "value", "type": ["null", "string", "long"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let err = check_supported(&schema).unwrap_err();
+        assert_eq!("union with 2 non-null variants", err);
+
+        let schema = AvroSchema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "node",
+                "fields": [
+                    {"name": "value", "type": "long"},
+                    {"name": "next", "type": ["null", "node"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let err = check_supported(&schema).unwrap_err();
+        assert_eq!("reference to named type 'node'", err);
+    }
+}
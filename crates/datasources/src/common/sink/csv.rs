@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use datafusion::arrow::csv::{Writer as CsvWriter, WriterBuilder as CsvWriterBuilder};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::common::Result as DfResult;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::error::DataFusionError;
 use datafusion::execution::TaskContext;
 use datafusion::physical_plan::insert::DataSink;
@@ -21,12 +22,24 @@ use crate::common::errors::Result;
 
 const BUFFER_SIZE: usize = 2 * 1024 * 1024;
 
+/// Options for writing CSV files.
+///
+/// Other than the header, defaults match Postgres' `COPY ... WITH (FORMAT
+/// csv)`.
 #[derive(Debug, Clone)]
 pub struct CsvSinkOpts {
     /// Delimiter between values.
     pub delim: u8,
     /// Include header.
     pub header: bool,
+    /// Character used to quote values containing special characters.
+    pub quote: u8,
+    /// String written for null values.
+    pub null: String,
+    /// Write records terminated by "\r\n" instead of "\n".
+    pub crlf: bool,
+    /// Compression applied to the written file.
+    pub compression: FileCompressionType,
 }
 
 impl Default for CsvSinkOpts {
@@ -34,6 +47,10 @@ impl Default for CsvSinkOpts {
         CsvSinkOpts {
             delim: b',',
             header: true,
+            quote: b'"',
+            null: String::new(),
+            crlf: false,
+            compression: FileCompressionType::UNCOMPRESSED,
         }
     }
 }
@@ -75,6 +92,7 @@ impl CsvSink {
 
     async fn stream_into_inner(&self, mut stream: SendableRecordBatchStream) -> Result<usize> {
         let (_id, obj_handle) = self.store.put_multipart(&self.loc).await?;
+        let obj_handle = self.opts.compression.convert_async_writer(obj_handle)?;
         let mut writer = AsyncCsvWriter::new(obj_handle, BUFFER_SIZE, &self.opts);
 
         while let Some(batch) = stream.next().await {
@@ -117,6 +135,11 @@ struct AsyncCsvWriter<W> {
     sync_writer: CsvWriter<SharedBuffer>,
     buffer: SharedBuffer,
     row_count: usize,
+    /// Quote character, needed to find record terminators when writing CRLF.
+    quote: u8,
+    crlf: bool,
+    /// If the buffer ended inside a quoted value on the last flush.
+    in_quotes: bool,
 }
 
 impl<W: AsyncWrite + Unpin + Send> AsyncCsvWriter<W> {
@@ -125,6 +148,8 @@ impl<W: AsyncWrite + Unpin + Send> AsyncCsvWriter<W> {
         let sync_writer = CsvWriterBuilder::new()
             .with_delimiter(sink_opts.delim)
             .with_header(sink_opts.header)
+            .with_quote(sink_opts.quote)
+            .with_null(sink_opts.null.clone())
             .build(buf.clone());
 
         AsyncCsvWriter {
@@ -132,6 +157,9 @@ impl<W: AsyncWrite + Unpin + Send> AsyncCsvWriter<W> {
             sync_writer,
             buffer: buf,
             row_count: 0,
+            quote: sink_opts.quote,
+            crlf: sink_opts.crlf,
+            in_quotes: false,
         }
     }
 
@@ -154,7 +182,12 @@ impl<W: AsyncWrite + Unpin + Send> AsyncCsvWriter<W> {
             return Ok(());
         }
 
-        self.async_writer.write_all(&buf).await?;
+        if self.crlf {
+            let buf = lf_to_crlf(&buf, self.quote, &mut self.in_quotes);
+            self.async_writer.write_all(&buf).await?;
+        } else {
+            self.async_writer.write_all(&buf).await?;
+        }
         self.async_writer.flush().await?;
 
         buf.clear();
@@ -162,3 +195,119 @@ impl<W: AsyncWrite + Unpin + Send> AsyncCsvWriter<W> {
         Ok(())
     }
 }
+
+/// Replace the line feeds terminating records with "\r\n".
+///
+/// Line feeds inside quoted values are left as is. Arrow's writer always
+/// terminates records with "\n", and escapes quotes by doubling them, so
+/// tracking whether we're in a quoted value only requires toggling on each
+/// quote character.
+fn lf_to_crlf(buf: &[u8], quote: u8, in_quotes: &mut bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(buf.len() + buf.len() / 16);
+    for &b in buf {
+        if b == quote {
+            *in_quotes = !*in_quotes;
+        } else if b == b'\n' && !*in_quotes {
+            out.push(b'\r');
+        }
+        out.push(b);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::array::{Int32Array, StringArray};
+    use datafusion::arrow::csv::ReaderBuilder;
+    use datafusion::arrow::datatypes::{DataType, Field, Schema};
+    use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+    use object_store::memory::InMemory;
+
+    use super::*;
+
+    fn test_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), Some(2), None])),
+                Arc::new(StringArray::from(vec![
+                    Some("mario"),
+                    Some("luigi|peach"),
+                    Some("yoshi\ntoad"),
+                ])),
+            ],
+        )
+        .unwrap()
+    }
+
+    async fn write_csv(batch: RecordBatch, opts: CsvSinkOpts) -> Vec<u8> {
+        let store = Arc::new(InMemory::new());
+        let sink = CsvSink::from_obj_store(store.clone(), "test.csv", opts);
+
+        let schema = batch.schema();
+        let stream = futures::stream::once(async move { Ok(batch) });
+        sink.stream_into_inner(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+            .await
+            .unwrap();
+
+        store
+            .get(&ObjectPath::from("test.csv"))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap()
+            .to_vec()
+    }
+
+    #[tokio::test]
+    async fn write_defaults() {
+        let out = write_csv(test_batch(), CsvSinkOpts::default()).await;
+        assert_eq!(
+            "a,b\n1,mario\n2,luigi|peach\n,\"yoshi\ntoad\"\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn write_options() {
+        let opts = CsvSinkOpts {
+            delim: b'|',
+            header: false,
+            quote: b'\'',
+            null: "NULL".to_string(),
+            crlf: true,
+            ..Default::default()
+        };
+        let out = write_csv(test_batch(), opts).await;
+        assert_eq!(
+            "1|mario\r\n2|'luigi|peach'\r\nNULL|'yoshi\ntoad'\r\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let opts = CsvSinkOpts {
+            delim: b'|',
+            quote: b'\'',
+            crlf: true,
+            ..Default::default()
+        };
+        let batch = test_batch().slice(0, 2);
+        let out = write_csv(batch.clone(), opts).await;
+
+        let mut reader = ReaderBuilder::new(batch.schema())
+            .with_header(true)
+            .with_delimiter(b'|')
+            .with_quote(b'\'')
+            .build(out.as_slice())
+            .unwrap();
+        let read = reader.next().unwrap().unwrap();
+        assert_eq!(batch, read);
+    }
+}
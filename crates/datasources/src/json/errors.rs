@@ -30,6 +30,9 @@ pub enum JsonError {
     #[error(transparent)]
     Datafusion(#[from] datafusion::error::DataFusionError),
 
+    #[error(transparent)]
+    Lines(#[from] tokio_util::codec::LinesCodecError),
+
     #[error(transparent)]
     ChannelSend(#[from] futures::channel::mpsc::SendError),
 
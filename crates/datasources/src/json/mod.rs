@@ -1,3 +1,4 @@
 pub mod errors;
+pub mod ndjson;
 mod stream;
 pub mod table;
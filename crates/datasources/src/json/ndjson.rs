@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::Schema;
+use datafusion::datasource::streaming::StreamingTable;
+use datafusion::datasource::TableProvider;
+use datafusion::physical_plan::streaming::PartitionStream;
+use futures::StreamExt;
+
+use crate::common::url::DatasourceUrl;
+use crate::json::errors::JsonError;
+use crate::json::stream::{ndjson_object_stream, NdjsonPartition};
+use crate::json::table::infer_schema;
+use crate::object_store::{ObjStoreAccess, ObjStoreAccessor};
+
+/// Create a table provider for one or more newline-delimited json (JSON
+/// Lines) objects.
+///
+/// If `schema` isn't provided, it's inferred from the first
+/// `schema_sample_size` documents (100 by default). When `ignore_errors` is
+/// set, lines that aren't valid json objects are skipped instead of failing
+/// the query.
+pub async fn ndjson_streaming_table(
+    store_access: Arc<dyn ObjStoreAccess>,
+    source_url: DatasourceUrl,
+    schema: Option<Schema>,
+    schema_sample_size: Option<i64>,
+    ignore_errors: bool,
+) -> Result<Arc<dyn TableProvider>, JsonError> {
+    let path = source_url.path().into_owned();
+
+    let accessor = ObjStoreAccessor::new(store_access)?;
+
+    let mut list = accessor.list_globbed(&path).await?;
+    if list.is_empty() {
+        return Err(JsonError::NotFound(path));
+    }
+
+    // for consistent results, particularly for the sample, always
+    // sort by location
+    list.sort_by(|a, b| a.location.cmp(&b.location));
+
+    let store = accessor.into_object_store();
+
+    let schema = match schema {
+        Some(schema) => Arc::new(schema),
+        None => {
+            let sample_size = schema_sample_size.unwrap_or(100).max(1) as usize;
+
+            let mut sample = Vec::with_capacity(sample_size);
+            'objects: for obj in &list {
+                let mut docs = ndjson_object_stream(store.clone(), obj, ignore_errors);
+                while let Some(doc) = docs.next().await {
+                    sample.push(doc?);
+                    if sample.len() >= sample_size {
+                        break 'objects;
+                    }
+                }
+            }
+
+            Arc::new(infer_schema(&sample))
+        }
+    };
+
+    // Every object gets its own partition. Sampled documents are read again
+    // when the partition executes so partitions stay re-executable.
+    let streams = list
+        .into_iter()
+        .map(|obj| {
+            Arc::new(NdjsonPartition::new(
+                schema.clone(),
+                store.clone(),
+                obj,
+                ignore_errors,
+            )) as Arc<dyn PartitionStream>
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Arc::new(StreamingTable::try_new(schema, streams)?))
+}
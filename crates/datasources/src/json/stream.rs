@@ -13,6 +13,7 @@ use futures::{Stream, StreamExt, TryStreamExt};
 use json_stream::JsonStream;
 use object_store::{ObjectMeta, ObjectStore};
 use serde_json::{Map, Value};
+use tokio_util::codec::{FramedRead, LinesCodec};
 
 use crate::json::errors::{JsonError, Result};
 
@@ -73,9 +74,85 @@ impl PartitionStream for ObjectStorePartition {
     }
 }
 
+/// NdjsonPartition reads a single newline-delimited json object from
+/// object storage, decoding one document per line. The object is only
+/// read when the partition is executed.
+pub(crate) struct NdjsonPartition {
+    schema: Arc<Schema>,
+    store: Arc<dyn ObjectStore>,
+    obj: ObjectMeta,
+    ignore_errors: bool,
+}
+
+impl NdjsonPartition {
+    pub fn new(
+        schema: Arc<Schema>,
+        store: Arc<dyn ObjectStore>,
+        obj: ObjectMeta,
+        ignore_errors: bool,
+    ) -> Self {
+        Self {
+            schema,
+            store,
+            obj,
+            ignore_errors,
+        }
+    }
+}
+
+impl PartitionStream for NdjsonPartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        Box::pin(JsonHandler::new(
+            self.schema.clone(),
+            ndjson_object_stream(self.store.clone(), &self.obj, self.ignore_errors),
+        ))
+    }
+}
+
+/// Stream the documents of a newline-delimited json object.
+///
+/// Blank lines are skipped. Lines that aren't json objects produce an
+/// error, unless `ignore_errors` is set, in which case they're skipped.
+pub(crate) fn ndjson_object_stream(
+    store: Arc<dyn ObjectStore>,
+    obj: &ObjectMeta,
+    ignore_errors: bool,
+) -> JsonObjectStream {
+    FramedRead::new(
+        object_store::buffered::BufReader::with_capacity(store, obj, 8 * 1024 * 1024),
+        LinesCodec::new(),
+    )
+    .filter_map(move |line| {
+        futures::future::ready(match line {
+            Ok(line) if line.trim().is_empty() => None,
+            Ok(line) => match parse_ndjson_line(&line) {
+                Ok(doc) => Some(Ok(doc)),
+                Err(_) if ignore_errors => None,
+                Err(e) => Some(Err(e)),
+            },
+            Err(e) => Some(Err(e.into())),
+        })
+    })
+    .boxed()
+}
+
+fn parse_ndjson_line(line: &str) -> Result<Map<String, Value>> {
+    match serde_json::from_str(line)? {
+        Value::Object(doc) => Ok(doc),
+        Value::Null => Ok(Map::new()),
+        _ => Err(JsonError::UnspportedType(
+            "only objects are supported in newline-delimited json",
+        )),
+    }
+}
+
 /// JsonObjectStream represents a sequence of "json documents" in an
 /// intermediate format produced by serde_json.
-type JsonObjectStream = Pin<Box<dyn Stream<Item = Result<Map<String, Value>>> + Send>>;
+pub(crate) type JsonObjectStream = Pin<Box<dyn Stream<Item = Result<Map<String, Value>>> + Send>>;
 
 /// JsonHandler is the basis of all stream handling, converting
 /// streams of serde_json objects to RecordBatches, including from
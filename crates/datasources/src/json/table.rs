@@ -73,26 +73,7 @@ async fn json_streaming_table_inner(
                 )?;
             }
 
-            let mut field_set = indexmap::IndexMap::<String, DataType>::new();
-            for obj in &data {
-                for (key, value) in obj.into_iter() {
-                    let typ = type_for_value(value);
-                    match field_set.get(key) {
-                        Some(v) => match widen_type(v, typ) {
-                            Some(wider) => field_set.insert(key.to_string(), wider),
-                            None => None,
-                        },
-                        None => field_set.insert(key.to_string(), typ),
-                    };
-                }
-            }
-
-            let schema = Arc::new(Schema::new(
-                field_set
-                    .into_iter()
-                    .map(|(k, v)| Field::new(k, v, true))
-                    .collect::<Vec<_>>(),
-            ));
+            let schema = Arc::new(infer_schema(&data));
 
             streams.push(Arc::new(VectorPartition::new(schema.clone(), data)));
             schema
@@ -143,6 +124,31 @@ fn push_unwind_json_values(
     Ok(())
 }
 
+/// Infer a schema from every unique field in the given documents. All fields
+/// are nullable.
+pub(crate) fn infer_schema(docs: &[Map<String, Value>]) -> Schema {
+    let mut field_set = indexmap::IndexMap::<String, DataType>::new();
+    for obj in docs {
+        for (key, value) in obj.into_iter() {
+            let typ = type_for_value(value);
+            match field_set.get(key) {
+                Some(v) => match widen_type(v, typ) {
+                    Some(wider) => field_set.insert(key.to_string(), wider),
+                    None => None,
+                },
+                None => field_set.insert(key.to_string(), typ),
+            };
+        }
+    }
+
+    Schema::new(
+        field_set
+            .into_iter()
+            .map(|(k, v)| Field::new(k, v, true))
+            .collect::<Vec<_>>(),
+    )
+}
+
 fn widen_type(left: &DataType, right: DataType) -> Option<DataType> {
     match (left, right) {
         (&DataType::Null, right) => Some(right),
@@ -35,6 +35,7 @@ use super::spec::{
     Snapshot,
     TableMetadata,
 };
+use crate::common::exprs_to_phys_exprs;
 use crate::common::url::DatasourceUrl;
 use crate::lake::iceberg::errors::{IcebergError, Result};
 
@@ -329,7 +330,7 @@ impl TableProvider for IcebergTableReader {
         &self,
         ctx: &SessionState,
         projection: Option<&Vec<usize>>,
-        _filters: &[Expr],
+        filters: &[Expr],
         limit: Option<usize>,
     ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
         // Create the datafusion specific url, and register the object store.
@@ -349,7 +350,9 @@ impl TableProvider for IcebergTableReader {
         // delete information are ignored.
 
         // TODO: Use provided filters to prune out partitions and/or data files
-        // (since the metadata will have some info about file content).
+        // (since the metadata will have some info about file content). For now
+        // the filters are only handed to the parquet exec for row group
+        // pruning.
 
         // TODO: Collect statistics and pass to exec.
 
@@ -402,6 +405,7 @@ impl TableProvider for IcebergTableReader {
             .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
         let file_schema = self.schema();
+        let predicate = exprs_to_phys_exprs(filters, ctx, &file_schema)?;
         let statistics = Statistics::new_unknown(file_schema.as_ref());
 
         let conf = FileScanConfig {
@@ -416,7 +420,7 @@ impl TableProvider for IcebergTableReader {
         };
 
         let plan = ParquetFormat::new()
-            .create_physical_plan(ctx, conf, None)
+            .create_physical_plan(ctx, conf, predicate.as_ref())
             .await?;
 
         Ok(Arc::new(IcebergTableScan { parquet_scan: plan }))
@@ -112,5 +112,5 @@ pub fn storage_options_into_object_store(
     opts: &StorageOptions,
 ) -> Result<Arc<dyn ObjectStore>, LakeStorageOptionsError> {
     let access = storage_options_into_store_access(url, opts)?;
-    Ok(access.create_store()?)
+    Ok(access.cached_store()?)
 }
@@ -1,5 +1,6 @@
 //! Data source implementations.
 
+pub mod avro;
 pub mod bigquery;
 pub mod bson;
 pub mod cassandra;
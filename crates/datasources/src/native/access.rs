@@ -1,5 +1,5 @@
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -7,24 +7,39 @@ use datafusion::arrow::datatypes::{DataType, Field, Schema as ArrowSchema, TimeU
 use datafusion::common::ToDFSchema;
 use datafusion::datasource::TableProvider;
 use datafusion::error::Result as DataFusionResult;
-use datafusion::execution::context::SessionState;
+use datafusion::execution::context::{SessionConfig, SessionContext, SessionState};
 use datafusion::logical_expr::{ident, Cast, LogicalPlan, TableProviderFilterPushDown, TableType};
 use datafusion::physical_expr::create_physical_expr;
 use datafusion::physical_expr::execution_props::ExecutionProps;
 use datafusion::physical_plan::empty::EmptyExec;
 use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::union::UnionExec;
 use datafusion::physical_plan::{ExecutionPlan, Statistics};
 use datafusion::prelude::Expr;
 use datafusion_ext::metrics::ReadOnlyDataSourceMetricsExecAdapter;
+use datafusion_ext::planner::TableVersion;
+use datafusion_ext::vars::SessionVars;
 use deltalake::delta_datafusion::DataFusionMixins;
-use deltalake::kernel::{ArrayType, DataType as DeltaDataType};
-use deltalake::logstore::{default_logstore, logstores, LogStore, LogStoreFactory};
+use deltalake::kernel::{
+    Action,
+    ArrayType,
+    DataType as DeltaDataType,
+    Remove,
+    StructField,
+    StructType,
+};
+use deltalake::logstore::{default_logstore, get_actions, logstores, LogStore, LogStoreFactory};
 use deltalake::operations::create::CreateBuilder;
 use deltalake::operations::delete::DeleteBuilder;
+use deltalake::operations::restore::RestoreBuilder;
+use deltalake::operations::transaction::commit;
 use deltalake::operations::update::UpdateBuilder;
+use deltalake::operations::write::WriteBuilder;
+use deltalake::protocol::DeltaOperation;
 pub use deltalake::protocol::SaveMode;
 use deltalake::storage::{factories, ObjectStoreFactory, ObjectStoreRef, StorageOptions};
-use deltalake::{DeltaResult, DeltaTable, DeltaTableConfig};
+use deltalake::table::state::DeltaTableState;
+use deltalake::{DeltaResult, DeltaTable, DeltaTableConfig, DeltaTableError};
 use futures::StreamExt;
 use object_store::path::Path as ObjectStorePath;
 use object_store::prefix::PrefixStore;
@@ -37,7 +52,14 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::native::errors::{NativeError, Result};
-use crate::native::insert::NativeTableInsertExec;
+use crate::native::insert::{
+    MergeMatchedAction,
+    MergeNotMatchedAction,
+    NativeTableInsertExec,
+    NativeTableMergeExec,
+};
+use crate::native::optimize::OptimizeExec;
+use crate::native::vacuum::VacuumExec;
 
 #[derive(Debug, Clone)]
 pub struct NativeTableStorage {
@@ -225,8 +247,11 @@ impl NativeTableStorage {
                 );
             }
 
+            if !opts.partition_by.is_empty() {
+                builder = builder.with_partition_columns(opts.partition_by.clone());
+            }
+
             let delta_table = builder.await?;
-            // TODO: Partitioning
             NativeTable::new(delta_table)
         };
 
@@ -247,6 +272,188 @@ impl NativeTableStorage {
         Ok(NativeTable::new(table))
     }
 
+    /// Get the latest version of a native table, which the table can later
+    /// be restored to.
+    ///
+    /// Unlike [`NativeTableStorage::load_table`], this only lists the table's
+    /// log instead of reading it.
+    pub async fn table_version(&self, table: &TableEntry) -> Result<NativeTableVersion> {
+        let _ = Self::opts_from_ent(table)?; // Check that this is the correct table type.
+
+        let store = self.create_delta_store_for_table(table);
+        let version = store.get_latest_version(0).await?;
+        Ok(NativeTableVersion { store, version })
+    }
+
+    /// Load a previous version of a native table.
+    ///
+    /// A timestamp loads the latest version committed at or before it.
+    pub async fn load_table_at_version(
+        &self,
+        table: &TableEntry,
+        version: TableVersion,
+    ) -> Result<NativeTable> {
+        let _ = Self::opts_from_ent(table)?; // Check that this is the correct table type.
+
+        let delta_store = self.create_delta_store_for_table(table);
+        let mut table = DeltaTable::new(delta_store, DeltaTableConfig::default());
+
+        match version {
+            TableVersion::Version(version) => table.load_version(version).await?,
+            TableVersion::Timestamp(ts) => table.load_with_datetime(ts).await?,
+        }
+
+        Ok(NativeTable::new(table))
+    }
+
+    /// Rewrite a table using the output of `exprs` projected over its current
+    /// contents, replacing the table's schema.
+    ///
+    /// Delta can't represent all schema changes through metadata alone (e.g.
+    /// renaming a column without column mapping), so we rewrite the data.
+    ///
+    /// Returns the version of the table before the rewrite, see
+    /// [`NativeTableStorage::restore_table`].
+    pub async fn rewrite_table(&self, table: &TableEntry, exprs: Vec<Expr>) -> Result<i64> {
+        let table = self.load_table(table).await?;
+        let version = table.delta.version();
+        let store = table.delta.log_store();
+        let snapshot = table.delta.state.clone();
+
+        // Read using the delta table directly to get the types as they're
+        // stored.
+        let ctx = SessionContext::new();
+        let input = ctx
+            .read_table(Arc::new(table.delta))?
+            .select(exprs)?
+            .create_physical_plan()
+            .await?;
+
+        WriteBuilder::new(store, snapshot)
+            .with_input_session_state(ctx.state())
+            .with_input_execution_plan(input)
+            .with_save_mode(SaveMode::Overwrite)
+            .with_overwrite_schema(true)
+            .await?;
+
+        Ok(version)
+    }
+
+    /// Insert `input` into a table while altering its columns, rewriting the
+    /// existing rows using `exprs` projected over the table's current
+    /// contents (see [`NativeTableStorage::rewrite_table`]).
+    ///
+    /// The rewritten and inserted rows are written in a single commit, so the
+    /// table's columns only change if the insert succeeds. The delta-rs
+    /// version we use only allows changing a table's schema when overwriting
+    /// it, hence rewriting the existing rows. `input` must have the altered
+    /// schema.
+    ///
+    /// Returns the version of the table before the insert along with the
+    /// number of rows inserted.
+    pub async fn insert_with_new_schema(
+        &self,
+        table: &TableEntry,
+        exprs: Vec<Expr>,
+        input: Arc<dyn ExecutionPlan>,
+        state: SessionState,
+    ) -> Result<(i64, u64)> {
+        let table = self.load_table(table).await?;
+        let version = table.delta.version();
+        let existing_rows = count_rows(table.delta.snapshot()?);
+        let partition_columns = table.partition_columns();
+        let store = table.delta.log_store();
+        let snapshot = table.delta.state.clone();
+
+        // Read using the delta table directly to get the types as they're
+        // stored.
+        let ctx = SessionContext::new();
+        let existing = ctx
+            .read_table(Arc::new(table.delta))?
+            .select(exprs)?
+            .create_physical_plan()
+            .await?;
+        let input = Arc::new(UnionExec::new(vec![existing, input]));
+
+        let table = WriteBuilder::new(store, snapshot)
+            .with_input_session_state(state)
+            .with_input_execution_plan(input)
+            .with_save_mode(SaveMode::Overwrite)
+            .with_overwrite_schema(true)
+            .with_partition_columns(partition_columns)
+            .await?;
+
+        // All files are rewritten, so the inserted rows are the rows in the
+        // new version that weren't in the old one.
+        let inserted_rows = match (existing_rows, count_rows(table.snapshot()?)) {
+            (Some(existing), Some(total)) => total.saturating_sub(existing),
+            _ => 0,
+        };
+
+        Ok((version, inserted_rows))
+    }
+
+    /// Add nullable columns to a table by changing only the schema in the
+    /// table's metadata. Files written before the change are read with nulls
+    /// for the added columns.
+    ///
+    /// Returns the version of the table before the columns were added, see
+    /// [`NativeTableStorage::restore_table`].
+    pub async fn add_columns(
+        &self,
+        table: &TableEntry,
+        columns: Vec<(String, DataType)>,
+    ) -> Result<i64> {
+        let table = self.load_table(table).await?;
+        let snapshot = table.delta.snapshot()?;
+        let version = snapshot.version();
+
+        let mut fields: Vec<StructField> = snapshot.schema().fields().cloned().collect();
+        for (name, arrow_type) in columns {
+            let delta_field = arrow_to_delta_safe(&arrow_type)?;
+            if delta_field.metadata.is_some() {
+                // The type couldn't be read back without field metadata.
+                return Err(NativeError::DeltaTable(DeltaTableError::Generic(format!(
+                    "Adding a column of type {arrow_type} is not supported"
+                ))));
+            }
+            fields.push(StructField::new(name, delta_field.data_type, true));
+        }
+
+        let mut metadata = snapshot.metadata().clone();
+        metadata.schema_string = serde_json::to_string(&StructType::new(fields))
+            .map_err(|e| DeltaTableError::Generic(e.to_string()))?;
+
+        // A write without any files, only replacing the metadata.
+        let operation = DeltaOperation::Write {
+            mode: SaveMode::Append,
+            partition_by: None,
+            predicate: None,
+        };
+        commit(
+            table.delta.log_store().as_ref(),
+            &vec![Action::Metadata(metadata)],
+            operation,
+            Some(snapshot),
+            None,
+        )
+        .await?;
+
+        Ok(version)
+    }
+
+    /// Restore a table to a previous version by committing a new version with
+    /// the same files as the previous one.
+    ///
+    /// Used to undo changes to a table when a related catalog change fails.
+    pub async fn restore_table(&self, table: &TableEntry, version: i64) -> Result<()> {
+        let table = self.load_table(table).await?;
+        RestoreBuilder::new(table.delta.log_store(), table.delta.state.unwrap())
+            .with_version_to_restore(version)
+            .await?;
+        Ok(())
+    }
+
     pub async fn delete_table(&self, table: &TableEntry) -> Result<()> {
         let prefix = self.table_prefix(table.meta.id);
         let mut x = self.store.list(Some(&prefix.into()));
@@ -281,16 +488,21 @@ impl NativeTableStorage {
         default_logstore(Arc::new(prefixed), &root_url, &StorageOptions::default())
     }
 
+    /// Delete the rows matching `where_expr`, or all rows if there's no
+    /// predicate. The commit is attributed to `connection_id`, see
+    /// [`commit_metadata`].
     pub async fn delete_rows_where(
         &self,
         table_entry: &TableEntry,
         where_expr: Option<Expr>,
+        connection_id: Uuid,
     ) -> Result<usize> {
         let table = self.load_table(table_entry).await?;
         if let Some(where_expr) = where_expr {
             let deleted_rows =
                 DeleteBuilder::new(table.delta.log_store(), table.delta.state.unwrap())
                     .with_predicate(where_expr)
+                    .with_metadata(commit_metadata(connection_id))
                     .await?
                     .1
                     .num_deleted_rows;
@@ -304,19 +516,45 @@ impl NativeTableStorage {
                     records = *num_rows;
                 }
             }
-            DeleteBuilder::new(table.delta.log_store(), table.delta.state.unwrap()).await?;
+            DeleteBuilder::new(table.delta.log_store(), table.delta.state.unwrap())
+                .with_metadata(commit_metadata(connection_id))
+                .await?;
             Ok(records)
         }
     }
 
+    /// Remove all rows from the given tables.
+    ///
+    /// This only commits a removal of the tables' files to their logs, no data
+    /// is rewritten. Every table is loaded before any of them are changed so
+    /// that a missing table fails the whole operation. Delta commits are per
+    /// table, so a failure while committing may leave earlier tables
+    /// truncated.
+    pub async fn truncate_tables(&self, tables: &[TableEntry], connection_id: Uuid) -> Result<()> {
+        let mut loaded = Vec::with_capacity(tables.len());
+        for table in tables {
+            loaded.push(self.load_table(table).await?);
+        }
+
+        for table in loaded {
+            DeleteBuilder::new(table.delta.log_store(), table.delta.state.unwrap())
+                .with_metadata(commit_metadata(connection_id))
+                .await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn update_rows_where(
         &self,
         table: &TableEntry,
         updates: Vec<(String, Expr)>,
         where_expr: Option<Expr>,
+        connection_id: Uuid,
     ) -> Result<usize> {
         let table = self.load_table(table).await?;
-        let mut builder = UpdateBuilder::new(table.delta.log_store(), table.delta.state.unwrap());
+        let mut builder = UpdateBuilder::new(table.delta.log_store(), table.delta.state.unwrap())
+            .with_metadata(commit_metadata(connection_id));
         for update in updates.into_iter() {
             builder = builder.with_update(update.0, update.1);
         }
@@ -328,6 +566,158 @@ impl NativeTableStorage {
     }
 }
 
+/// Count the rows in a table snapshot using the stats recorded for each file.
+///
+/// Returns `None` if any file is missing stats.
+fn count_rows(snapshot: &DeltaTableState) -> Option<u64> {
+    snapshot
+        .file_actions()
+        .ok()?
+        .iter()
+        .map(|add| Some(add.get_stats().ok()??.num_records as u64))
+        .sum()
+}
+
+/// Key in the commit info of native table writes holding the id of the
+/// connection that made the write.
+const COMMIT_CONNECTION_ID_KEY: &str = "glaredbConnectionId";
+
+/// Metadata to add to the commit info of writes made by a connection, so
+/// that its transactions can tell their commits apart from the commits of
+/// other connections (see [`NativeTableVersion::undo`]).
+pub fn commit_metadata(connection_id: Uuid) -> HashMap<String, Value> {
+    HashMap::from([(
+        COMMIT_CONNECTION_ID_KEY.to_string(),
+        json!(connection_id.to_string()),
+    )])
+}
+
+/// Get the id of the connection executing a plan, which is nil if the
+/// session config has no session variables.
+pub fn session_connection_id(config: &SessionConfig) -> Uuid {
+    config
+        .options()
+        .extensions
+        .get::<SessionVars>()
+        .map(|vars| vars.connection_id())
+        .unwrap_or_default()
+}
+
+/// A version of a native table, used to undo later changes to the table.
+#[derive(Debug, Clone)]
+pub struct NativeTableVersion {
+    store: Arc<dyn LogStore>,
+    version: i64,
+}
+
+impl NativeTableVersion {
+    /// Location of the table, which is the same across versions.
+    pub fn location(&self) -> String {
+        self.store.root_uri()
+    }
+
+    /// Check that every commit to the table after this version was made by
+    /// the connection `connection_id`, returning the table as currently
+    /// committed.
+    ///
+    /// Errors with [`NativeError::CommittedByOtherConnection`] otherwise,
+    /// since undoing the commits would also undo the other connection's
+    /// changes.
+    pub async fn check_undo(&self, connection_id: Uuid) -> Result<DeltaTable> {
+        let mut table = DeltaTable::new(self.store.clone(), DeltaTableConfig::default());
+        table.load().await?;
+
+        let expected = json!(connection_id.to_string());
+        for version in (self.version + 1)..=table.version() {
+            let commit = self
+                .store
+                .read_commit_entry(version)
+                .await?
+                .ok_or(DeltaTableError::InvalidVersion(version))?;
+            let committed_by_connection =
+                get_actions(version, commit)
+                    .await?
+                    .into_iter()
+                    .any(|action| match action {
+                        Action::CommitInfo(info) => {
+                            info.info.get(COMMIT_CONNECTION_ID_KEY) == Some(&expected)
+                        }
+                        _ => false,
+                    });
+            if !committed_by_connection {
+                return Err(NativeError::CommittedByOtherConnection {
+                    location: self.location(),
+                    version,
+                });
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Undo the commits made to the table after this version by committing a
+    /// new version that removes the files they added and adds back the files
+    /// they removed. Does nothing if the table hasn't changed since.
+    ///
+    /// The commits must all have been made by `connection_id`, see
+    /// [`NativeTableVersion::check_undo`].
+    pub async fn undo(&self, connection_id: Uuid) -> Result<()> {
+        let table = self.check_undo(connection_id).await?;
+        if table.version() == self.version {
+            return Ok(());
+        }
+
+        let mut previous = DeltaTable::new(self.store.clone(), DeltaTableConfig::default());
+        previous.load_version(self.version).await?;
+
+        let current_files = table.snapshot()?.file_actions()?;
+        let previous_files = previous.snapshot()?.file_actions()?;
+        let current_paths: HashSet<_> = current_files.iter().map(|add| &add.path).collect();
+        let previous_paths: HashSet<_> = previous_files.iter().map(|add| &add.path).collect();
+
+        let deletion_timestamp = chrono::Utc::now().timestamp_millis();
+        let mut actions = Vec::new();
+        for add in current_files.iter() {
+            if !previous_paths.contains(&add.path) {
+                actions.push(Action::Remove(Remove {
+                    path: add.path.clone(),
+                    deletion_timestamp: Some(deletion_timestamp),
+                    data_change: true,
+                    extended_file_metadata: Some(true),
+                    partition_values: Some(add.partition_values.clone()),
+                    size: Some(add.size),
+                    tags: add.tags.clone(),
+                    deletion_vector: add.deletion_vector.clone(),
+                    base_row_id: add.base_row_id,
+                    default_row_commit_version: add.default_row_commit_version,
+                }));
+            }
+        }
+        for add in previous_files.iter() {
+            if !current_paths.contains(&add.path) {
+                let mut add = add.clone();
+                add.data_change = true;
+                actions.push(Action::Add(add));
+            }
+        }
+
+        let operation = DeltaOperation::Restore {
+            version: Some(self.version),
+            datetime: None,
+        };
+        commit(
+            table.log_store().as_ref(),
+            &actions,
+            operation,
+            Some(table.snapshot()?),
+            Some(commit_metadata(connection_id)),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct NativeTable {
     delta: DeltaTable,
@@ -342,16 +732,56 @@ impl NativeTable {
         self.delta.table_uri()
     }
 
+    /// Get the version of the table as loaded, which the table can later be
+    /// restored to.
+    pub fn current_version(&self) -> NativeTableVersion {
+        NativeTableVersion {
+            store: self.delta.log_store(),
+            version: self.delta.version(),
+        }
+    }
+
     pub fn into_table_provider(self) -> Arc<dyn TableProvider> {
         Arc::new(self)
     }
 
+    /// Columns the table's data is partitioned by.
+    pub fn partition_columns(&self) -> Vec<String> {
+        self.delta
+            .metadata()
+            .map(|meta| meta.partition_columns.clone())
+            .unwrap_or_default()
+    }
+
     /// Create a new execution plan for inserting `input` into the table.
     pub fn insert_exec(
         &self,
         input: Arc<dyn ExecutionPlan>,
         overwrite: bool,
     ) -> Arc<dyn ExecutionPlan> {
+        Arc::new(self.new_insert_exec(input, overwrite))
+    }
+
+    /// Create a new execution plan for overwriting the table with `input`.
+    ///
+    /// If `replace_where` is provided, only rows matching the predicate are
+    /// replaced. Otherwise the entire table is overwritten.
+    pub fn overwrite_exec(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        replace_where: Option<Expr>,
+    ) -> Arc<dyn ExecutionPlan> {
+        Arc::new(
+            self.new_insert_exec(input, true)
+                .with_replace_where(replace_where),
+        )
+    }
+
+    fn new_insert_exec(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        overwrite: bool,
+    ) -> NativeTableInsertExec {
         let save_mode = if overwrite {
             SaveMode::Overwrite
         } else {
@@ -360,13 +790,66 @@ impl NativeTable {
 
         let store = self.delta.log_store();
         let snapshot = self.delta.state.clone();
-        Arc::new(NativeTableInsertExec::new(
+        NativeTableInsertExec::new(
             input,
             store,
             snapshot.unwrap(),
             save_mode,
+            self.partition_columns(),
+        )
+    }
+
+    /// Create a new execution plan for merging `input` into the table, matching
+    /// rows on the `keys` columns.
+    pub fn merge_exec(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        keys: Vec<String>,
+        matched: MergeMatchedAction,
+        not_matched: MergeNotMatchedAction,
+    ) -> Arc<dyn ExecutionPlan> {
+        let store = self.delta.log_store();
+        let snapshot = self.delta.state.clone();
+        Arc::new(NativeTableMergeExec::new(
+            input,
+            store,
+            snapshot.unwrap(),
+            keys,
+            matched,
+            not_matched,
+        ))
+    }
+
+    /// Create a new execution plan for deleting files no longer referenced by
+    /// the table, keeping files removed within the last `retain_hours`.
+    ///
+    /// Unless `enforce_retention` is false, `retain_hours` may not be shorter
+    /// than the table's configured retention period.
+    pub fn vacuum_exec(
+        &self,
+        retain_hours: Option<u64>,
+        enforce_retention: bool,
+        dry_run: bool,
+    ) -> Arc<dyn ExecutionPlan> {
+        let store = self.delta.log_store();
+        let snapshot = self.delta.state.clone();
+        let retention = retain_hours.map(|hours| chrono::Duration::hours(hours as i64));
+        Arc::new(VacuumExec::new(
+            store,
+            snapshot.unwrap(),
+            retention,
+            enforce_retention,
+            dry_run,
         ))
     }
+
+    /// Create a new execution plan for compacting the table's small files,
+    /// optionally Z-ordering the data by the given columns.
+    pub fn optimize_exec(&self, zorder_by: Vec<String>) -> Arc<dyn ExecutionPlan> {
+        let store = self.delta.log_store();
+        let snapshot = self.delta.state.clone();
+        Arc::new(OptimizeExec::new(store, snapshot.unwrap(), zorder_by))
+    }
 }
 
 #[async_trait]
@@ -485,18 +968,39 @@ impl TableProvider for NativeTable {
 
     async fn insert_into(
         &self,
-        _state: &SessionState,
+        state: &SessionState,
         input: Arc<dyn ExecutionPlan>,
         overwrite: bool,
     ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
-        Ok(self.insert_exec(input, overwrite))
+        let preserve_order = state
+            .config()
+            .options()
+            .extensions
+            .get::<SessionVars>()
+            .is_some_and(|vars| vars.preserve_insert_order());
+        Ok(Arc::new(
+            self.new_insert_exec(input, overwrite)
+                .with_preserve_order(preserve_order),
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use datafusion::arrow::array::{Int32Array, StringArray, UInt64Array};
     use datafusion::arrow::datatypes::DataType;
+    use datafusion::arrow::record_batch::RecordBatch;
+    use datafusion::common::Column;
+    use datafusion::datasource::TableProvider;
+    use datafusion::execution::context::SessionContext;
+    use datafusion::physical_plan::memory::MemoryExec;
+    use datafusion::physical_plan::{collect, displayable, ExecutionPlan};
+    use datafusion::prelude::{col, lit, Expr};
     use deltalake::protocol::SaveMode;
+    use futures::StreamExt;
     use object_store_util::conf::StorageConfig;
     use protogen::metastore::types::catalog::{EntryMeta, EntryType, SourceAccessMode, TableEntry};
     use protogen::metastore::types::options::{InternalColumnDefinition, TableOptionsInternal};
@@ -504,45 +1008,58 @@ mod tests {
     use url::Url;
     use uuid::Uuid;
 
-    use crate::native::access::NativeTableStorage;
-
-    #[tokio::test]
-    async fn test_delete_table() {
-        let db_id = Uuid::new_v4();
-        let dir = tempdir().unwrap();
-        let conf = StorageConfig::Local {
-            path: dir.path().to_path_buf(),
-        };
-
-        let storage = NativeTableStorage::new(
-            db_id,
-            Url::from_file_path(dir.path()).unwrap(),
-            conf.new_object_store().unwrap(),
-        );
+    use crate::native::access::{NativeTable, NativeTableStorage};
+    use crate::native::insert::{MergeMatchedAction, MergeNotMatchedAction, MERGE_SOURCE_ALIAS};
 
-        let entry = TableEntry {
+    /// Create the catalog entry of a native table.
+    fn test_table_entry(
+        name: &str,
+        columns: Vec<InternalColumnDefinition>,
+        partition_by: Vec<String>,
+    ) -> TableEntry {
+        TableEntry {
             meta: EntryMeta {
                 entry_type: EntryType::Table,
                 id: 12345,
                 parent: 54321,
-                name: "table_1".to_string(),
+                name: name.to_string(),
                 builtin: false,
                 external: false,
                 is_temp: false,
             },
             options: TableOptionsInternal {
-                columns: vec![InternalColumnDefinition {
-                    name: "id".to_string(),
-                    nullable: true,
-                    arrow_type: DataType::Int32,
-                }],
+                columns,
+                partition_by,
             }
             .into(),
             tunnel_id: None,
-            access_mode: SourceAccessMode::ReadOnly,
+            access_mode: SourceAccessMode::ReadWrite,
             columns: None,
+            comment: None,
+            column_comments: BTreeMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_table() {
+        let db_id = Uuid::new_v4();
+        let dir = tempdir().unwrap();
+        let conf = StorageConfig::Local {
+            path: dir.path().to_path_buf(),
         };
 
+        let storage = NativeTableStorage::new(
+            db_id,
+            Url::from_file_path(dir.path()).unwrap(),
+            conf.new_object_store().unwrap(),
+        );
+
+        let entry = test_table_entry(
+            "table_1",
+            InternalColumnDefinition::from_tuples([("id", DataType::Int32, true)]),
+            Vec::new(),
+        );
+
         // Create a table, load it, delete it and load it again!
         storage
             .create_table(&entry, SaveMode::ErrorIfExists)
@@ -558,4 +1075,309 @@ mod tests {
             .unwrap_err();
         assert_eq!(err, "Error loading table");
     }
+
+    #[tokio::test]
+    async fn test_partitioned_insert() {
+        let db_id = Uuid::new_v4();
+        let dir = tempdir().unwrap();
+        let conf = StorageConfig::Local {
+            path: dir.path().to_path_buf(),
+        };
+
+        let storage = NativeTableStorage::new(
+            db_id,
+            Url::from_file_path(dir.path()).unwrap(),
+            conf.new_object_store().unwrap(),
+        );
+
+        let entry = test_table_entry(
+            "table_1",
+            InternalColumnDefinition::from_tuples([
+                ("val", DataType::Utf8, true),
+                ("part", DataType::Int32, true),
+            ]),
+            vec!["part".to_string()],
+        );
+
+        let table = storage
+            .create_table(&entry, SaveMode::ErrorIfExists)
+            .await
+            .unwrap();
+        assert_eq!(vec!["part".to_string()], table.partition_columns());
+
+        let schema = TableProvider::schema(&table);
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "b", "c"])),
+                Arc::new(Int32Array::from(vec![1, 2, 1])),
+            ],
+        )
+        .unwrap();
+        let input = Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap());
+
+        let ctx = SessionContext::new();
+        let mut stream = table
+            .insert_exec(input, false)
+            .execute(0, ctx.task_ctx())
+            .unwrap();
+        while let Some(res) = stream.next().await {
+            res.unwrap();
+        }
+
+        // Each partition value is written to its own directory.
+        let table = storage.load_table(&entry).await.unwrap();
+        let files: Vec<_> = table
+            .delta
+            .get_files_iter()
+            .unwrap()
+            .map(|path| path.to_string())
+            .collect();
+        assert_eq!(2, files.len());
+        assert!(files.iter().any(|path| path.starts_with("part=1/")));
+        assert!(files.iter().any(|path| path.starts_with("part=2/")));
+
+        // A filter on the partition column only reads the matching directory.
+        let plan = table
+            .scan(&ctx.state(), None, &[col("part").eq(lit(1))], None)
+            .await
+            .unwrap();
+        let display = displayable(plan.as_ref()).indent(true).to_string();
+        assert!(display.contains("part=1/"));
+        assert!(!display.contains("part=2/"));
+
+        let batches = collect(plan, ctx.task_ctx()).await.unwrap();
+        let num_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(2, num_rows);
+    }
+
+    /// Create a table with an `id` and `val` column in `dir` containing the
+    /// rows `(1, 'a'), (2, 'b')`.
+    async fn create_merge_table(dir: &std::path::Path) -> (NativeTableStorage, TableEntry) {
+        let conf = StorageConfig::Local {
+            path: dir.to_path_buf(),
+        };
+        let storage = NativeTableStorage::new(
+            Uuid::new_v4(),
+            Url::from_file_path(dir).unwrap(),
+            conf.new_object_store().unwrap(),
+        );
+
+        let entry = test_table_entry(
+            "table_1",
+            InternalColumnDefinition::from_tuples([
+                ("id", DataType::Int32, true),
+                ("val", DataType::Utf8, true),
+            ]),
+            Vec::new(),
+        );
+
+        let table = storage
+            .create_table(&entry, SaveMode::ErrorIfExists)
+            .await
+            .unwrap();
+        let input = merge_source(&table, &[(1, "a"), (2, "b")]);
+        execute_count(Arc::new(table.insert_exec(input, false))).await;
+
+        (storage, entry)
+    }
+
+    fn merge_source(table: &NativeTable, rows: &[(i32, &str)]) -> Arc<dyn ExecutionPlan> {
+        let schema = TableProvider::schema(table);
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_iter_values(rows.iter().map(|row| row.0))),
+                Arc::new(StringArray::from_iter_values(rows.iter().map(|row| row.1))),
+            ],
+        )
+        .unwrap();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    async fn execute_count(plan: Arc<dyn ExecutionPlan>) -> u64 {
+        let batches = collect(plan, SessionContext::new().task_ctx())
+            .await
+            .unwrap();
+        batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap()
+            .value(0)
+    }
+
+    async fn table_rows(table: &NativeTable) -> Vec<(i32, String)> {
+        let ctx = SessionContext::new();
+        let plan = table.scan(&ctx.state(), None, &[], None).await.unwrap();
+        let batches = collect(plan, ctx.task_ctx()).await.unwrap();
+
+        let mut rows = Vec::new();
+        for batch in batches {
+            let ids = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            let vals = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap();
+            for idx in 0..batch.num_rows() {
+                rows.push((ids.value(idx), vals.value(idx).to_string()));
+            }
+        }
+        rows.sort();
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_insert_count() {
+        let dir = tempdir().unwrap();
+        let (storage, entry) = create_merge_table(dir.path()).await;
+
+        // The memory exec doesn't expose metrics, so the count must come from
+        // the files written by the insert. Files that were already in the
+        // table aren't counted.
+        let table = storage.load_table(&entry).await.unwrap();
+        let input = merge_source(&table, &[(3, "c"), (4, "d"), (5, "e")]);
+        assert_eq!(
+            3,
+            execute_count(Arc::new(table.insert_exec(input, false))).await
+        );
+
+        let table = storage.load_table(&entry).await.unwrap();
+        let input = merge_source(&table, &[(6, "f")]);
+        assert_eq!(
+            1,
+            execute_count(Arc::new(table.insert_exec(input, true))).await
+        );
+
+        let table = storage.load_table(&entry).await.unwrap();
+        assert_eq!(expected_rows(&[(6, "f")]), table_rows(&table).await);
+    }
+
+    /// Merge `source` into the table, returning the reported count and the
+    /// rows of the table afterwards.
+    async fn merge(
+        matched: MergeMatchedAction,
+        not_matched: MergeNotMatchedAction,
+        source: &[(i32, &str)],
+    ) -> (u64, Vec<(i32, String)>) {
+        let dir = tempdir().unwrap();
+        let (storage, entry) = create_merge_table(dir.path()).await;
+
+        let table = storage.load_table(&entry).await.unwrap();
+        let input = merge_source(&table, source);
+        let count =
+            execute_count(table.merge_exec(input, vec!["id".to_string()], matched, not_matched))
+                .await;
+
+        let table = storage.load_table(&entry).await.unwrap();
+        (count, table_rows(&table).await)
+    }
+
+    fn expected_rows(rows: &[(i32, &str)]) -> Vec<(i32, String)> {
+        rows.iter()
+            .map(|(id, val)| (*id, val.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_merge_update() {
+        let (count, rows) = merge(
+            MergeMatchedAction::Update,
+            MergeNotMatchedAction::Insert,
+            &[(2, "x"), (3, "y")],
+        )
+        .await;
+        assert_eq!(2, count);
+        assert_eq!(expected_rows(&[(1, "a"), (2, "x"), (3, "y")]), rows);
+    }
+
+    #[tokio::test]
+    async fn test_merge_update_set() {
+        let source_val = Expr::Column(Column::new(Some(MERGE_SOURCE_ALIAS), "val"));
+
+        // Only matched rows satisfying the predicate are updated.
+        let (count, rows) = merge(
+            MergeMatchedAction::UpdateSet {
+                assignments: vec![("val".to_string(), source_val.clone())],
+                predicate: Some(source_val.eq(lit("x"))),
+            },
+            MergeNotMatchedAction::DoNothing,
+            &[(1, "z"), (2, "x"), (3, "y")],
+        )
+        .await;
+        assert_eq!(1, count);
+        assert_eq!(expected_rows(&[(1, "a"), (2, "x")]), rows);
+    }
+
+    #[tokio::test]
+    async fn test_merge_delete() {
+        let (count, rows) = merge(
+            MergeMatchedAction::Delete,
+            MergeNotMatchedAction::DoNothing,
+            &[(2, "x"), (3, "y")],
+        )
+        .await;
+        assert_eq!(1, count);
+        assert_eq!(expected_rows(&[(1, "a")]), rows);
+    }
+
+    #[tokio::test]
+    async fn test_merge_do_nothing() {
+        let (count, rows) = merge(
+            MergeMatchedAction::DoNothing,
+            MergeNotMatchedAction::Insert,
+            &[(2, "x"), (3, "y")],
+        )
+        .await;
+        assert_eq!(1, count);
+        assert_eq!(expected_rows(&[(1, "a"), (2, "b"), (3, "y")]), rows);
+    }
+
+    #[tokio::test]
+    async fn test_merge_duplicate_source_keys() {
+        // Only the first row for a key is inserted when matched rows are left
+        // as is.
+        let (count, rows) = merge(
+            MergeMatchedAction::DoNothing,
+            MergeNotMatchedAction::Insert,
+            &[(3, "x"), (3, "y"), (2, "z")],
+        )
+        .await;
+        assert_eq!(1, count);
+        assert_eq!(expected_rows(&[(1, "a"), (2, "b"), (3, "x")]), rows);
+
+        // Otherwise the rows are rejected, whether or not they match an
+        // existing row.
+        for source in [[(2, "x"), (2, "y")], [(3, "x"), (3, "y")]] {
+            let dir = tempdir().unwrap();
+            let (storage, entry) = create_merge_table(dir.path()).await;
+            let table = storage.load_table(&entry).await.unwrap();
+
+            let plan = table.merge_exec(
+                merge_source(&table, &source),
+                vec!["id".to_string()],
+                MergeMatchedAction::Update,
+                MergeNotMatchedAction::Insert,
+            );
+            let err = collect(plan, SessionContext::new().task_ctx())
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("cannot affect row a second time"),
+                "{err}"
+            );
+
+            // Nothing was written.
+            let table = storage.load_table(&entry).await.unwrap();
+            assert_eq!(
+                expected_rows(&[(1, "a"), (2, "b")]),
+                table_rows(&table).await
+            );
+        }
+    }
 }
@@ -21,6 +21,9 @@ pub enum NativeError {
     #[error("Table entry not a native table: {0}")]
     NotNative(protogen::metastore::types::catalog::TableEntry),
 
+    #[error("Table {location} was changed by another connection in version {version}")]
+    CommittedByOtherConnection { location: String, version: i64 },
+
     #[error("{0}")]
     Static(&'static str),
 }
@@ -1,16 +1,24 @@
 use std::any::Any;
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use datafusion::arrow::array::BooleanArray;
+use datafusion::arrow::compute::filter_record_batch;
 use datafusion::arrow::datatypes::{DataType, SchemaRef};
-use datafusion::common::ToDFSchema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::row::{RowConverter, SortField};
+use datafusion::common::{Column, ToDFSchema};
+use datafusion::datasource::MemTable;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
-use datafusion::execution::context::SessionState;
+use datafusion::execution::context::{SessionContext, SessionState};
 use datafusion::execution::TaskContext;
 use datafusion::logical_expr::{ident, Cast, Expr};
-use datafusion::physical_expr::{create_physical_expr, PhysicalSortExpr};
+use datafusion::physical_expr::{create_physical_expr, PhysicalSortExpr, PhysicalSortRequirement};
 use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
 use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
 use datafusion::physical_plan::{
+    collect,
     DisplayAs,
     DisplayFormatType,
     Distribution,
@@ -19,14 +27,17 @@ use datafusion::physical_plan::{
     SendableRecordBatchStream,
     Statistics,
 };
-use deltalake::kernel::StructField;
-use deltalake::logstore::LogStore;
+use deltalake::kernel::{Action, StructField};
+use deltalake::logstore::{get_actions, LogStore};
+use deltalake::operations::merge::MergeBuilder;
 use deltalake::operations::write::WriteBuilder;
 use deltalake::protocol::SaveMode;
 use deltalake::table::state::DeltaTableState;
+use deltalake::DeltaTable;
 use futures::StreamExt;
 
 use crate::common::util::{create_count_record_batch, COUNT_SCHEMA};
+use crate::native::access::{commit_metadata, session_connection_id};
 
 /// An execution plan for inserting data into a delta table.
 #[derive(Debug)]
@@ -35,20 +46,58 @@ pub struct NativeTableInsertExec {
     store: Arc<dyn LogStore>,
     snapshot: DeltaTableState,
     save_mode: SaveMode,
+    partition_by: Vec<String>,
+    replace_where: Option<Expr>,
+    preserve_order: bool,
 }
 
 impl NativeTableInsertExec {
+    /// Create a new insert exec.
+    ///
+    /// `partition_by` must match the partition columns the table was created
+    /// with. Rows are written to a directory per distinct partition value.
     pub fn new(
         input: Arc<dyn ExecutionPlan>,
         store: Arc<dyn LogStore>,
         snapshot: DeltaTableState,
         save_mode: SaveMode,
+        partition_by: Vec<String>,
     ) -> Self {
         NativeTableInsertExec {
             input,
             store,
             snapshot,
             save_mode,
+            partition_by,
+            replace_where: None,
+            preserve_order: false,
+        }
+    }
+
+    /// Only replace rows matching `predicate` when overwriting the table.
+    ///
+    /// All inserted rows must match the predicate. Without a predicate,
+    /// `SaveMode::Overwrite` replaces the entire table.
+    pub fn with_replace_where(mut self, predicate: Option<Expr>) -> Self {
+        self.replace_where = predicate;
+        self
+    }
+
+    /// Write rows in the order of the input.
+    ///
+    /// The input is merged into a single partition, keeping its ordering, so
+    /// that the written files retain the sort order of the input.
+    pub fn with_preserve_order(mut self, preserve_order: bool) -> Self {
+        self.preserve_order = preserve_order;
+        self
+    }
+
+    /// The ordering of the input that should be kept when writing.
+    fn input_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        if self.preserve_order {
+            self.input.output_ordering()
+        } else {
+            None
         }
     }
 }
@@ -71,11 +120,21 @@ impl ExecutionPlan for NativeTableInsertExec {
     }
 
     fn required_input_distribution(&self) -> Vec<Distribution> {
-        vec![Distribution::UnspecifiedDistribution]
+        if self.input_ordering().is_some() {
+            vec![Distribution::SinglePartition]
+        } else {
+            vec![Distribution::UnspecifiedDistribution]
+        }
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<Vec<PhysicalSortRequirement>>> {
+        vec![self
+            .input_ordering()
+            .map(PhysicalSortRequirement::from_sort_exprs)]
     }
 
     fn maintains_input_order(&self) -> Vec<bool> {
-        vec![false]
+        vec![self.preserve_order]
     }
 
     fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
@@ -91,6 +150,9 @@ impl ExecutionPlan for NativeTableInsertExec {
             store: self.store.clone(),
             snapshot: self.snapshot.clone(),
             save_mode: self.save_mode,
+            partition_by: self.partition_by.clone(),
+            replace_where: self.replace_where.clone(),
+            preserve_order: self.preserve_order,
         }))
     }
 
@@ -112,70 +174,49 @@ impl ExecutionPlan for NativeTableInsertExec {
             context.runtime_env(),
         );
 
-        let schema = self.input.schema();
-        let fields = schema.fields().clone();
-        let input_dfschema = schema.to_dfschema()?;
-        // delta-rs does not support all data types, so we need to check if the input schema
-        // contains any unsupported data types.
-        // If it does, we need to cast them to supported data types.
-        let mut contains_unsupported_fields = false;
-        let projections = fields
-            .iter()
-            .map(|field| {
-                let e = if StructField::try_from(field.as_ref()).is_ok() {
-                    ident(field.name())
-                } else {
-                    contains_unsupported_fields = true;
-
-                    match field.data_type() {
-                        DataType::Timestamp(_, _) => Expr::Cast(Cast {
-                            expr: Box::new(ident(field.name())),
-                            data_type: DataType::Timestamp(
-                                datafusion::arrow::datatypes::TimeUnit::Microsecond,
-                                None,
-                            ),
-                        }),
-
-                        dtype => {
-                            return Err(DataFusionError::Execution(format!(
-                                "Unsupported data type {:?} for field {}",
-                                dtype,
-                                field.name()
-                            )))
-                        }
-                    }
-                };
-                let e = create_physical_expr(&e, &input_dfschema, state.execution_props()).unwrap();
-                Ok((e, field.name().clone()))
-            })
-            .collect::<DataFusionResult<Vec<_>>>()?;
-
-        let input = if contains_unsupported_fields {
-            Arc::new(ProjectionExec::try_new(projections, self.input.clone())?)
-        } else {
-            self.input.clone()
+        let input = cast_unsupported_fields(self.input.clone(), &state)?;
+
+        // Partitions are written concurrently, so merge them into one to keep
+        // the input ordering across the written files.
+        let input: Arc<dyn ExecutionPlan> = match self.input_ordering() {
+            Some(ordering) if input.output_partitioning().partition_count() > 1 => {
+                Arc::new(SortPreservingMergeExec::new(ordering.to_vec(), input))
+            }
+            _ => input,
         };
 
         // Allows writing multiple output partitions from the input execution
         // plan.
         //
         // TODO: Possibly try avoiding cloning the snapshot.
-        let builder = WriteBuilder::new(self.store.clone(), Some(self.snapshot.clone()))
+        let connection_id = session_connection_id(context.session_config());
+        let mut builder = WriteBuilder::new(self.store.clone(), Some(self.snapshot.clone()))
             .with_input_session_state(state)
             .with_save_mode(self.save_mode)
-            .with_input_execution_plan(input.clone());
+            .with_input_execution_plan(input.clone())
+            .with_metadata(commit_metadata(connection_id));
+        if !self.partition_by.is_empty() {
+            builder = builder.with_partition_columns(self.partition_by.clone());
+        }
+        if let Some(predicate) = &self.replace_where {
+            builder = builder.with_replace_where(predicate.clone());
+        }
 
         let output = futures::stream::once(async move {
-            let _ = builder
+            let table = builder
                 .await
                 .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
-            let count = input
-                .metrics()
-                .map(|metrics| metrics.output_rows().unwrap_or_default())
-                .unwrap_or_default();
+            // Input metrics aren't always available, so prefer the row counts
+            // recorded on the files we just wrote.
+            let count = count_committed_rows(&table).await.unwrap_or_else(|| {
+                input
+                    .metrics()
+                    .map(|metrics| metrics.output_rows().unwrap_or_default())
+                    .unwrap_or_default() as u64
+            });
 
-            Ok(create_count_record_batch(count as u64))
+            Ok(create_count_record_batch(count))
         })
         .boxed();
 
@@ -197,8 +238,384 @@ impl DisplayAs for NativeTableInsertExec {
                 write!(f, "NativeTableInsertExec")
             }
             DisplayFormatType::Verbose => {
-                write!(f, "NativeTableInsertExec")
+                write!(
+                    f,
+                    "NativeTableInsertExec: partition_by={:?}",
+                    self.partition_by
+                )?;
+                if let Some(predicate) = &self.replace_where {
+                    write!(f, ", replace_where={predicate}")?;
+                }
+                if self.preserve_order {
+                    write!(f, ", preserve_order=true")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Action to take for target rows matched by the merge predicate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeMatchedAction {
+    /// Overwrite the target row with the source row.
+    Update,
+    /// Set columns of the target row to the given expressions.
+    ///
+    /// Expressions reference columns of the source and target rows qualified
+    /// with [`MERGE_SOURCE_ALIAS`] and [`MERGE_TARGET_ALIAS`]. If a predicate
+    /// is provided, only matched rows satisfying it are updated.
+    UpdateSet {
+        assignments: Vec<(String, Expr)>,
+        predicate: Option<Expr>,
+    },
+    /// Delete the target row.
+    Delete,
+    /// Leave the target row as is.
+    DoNothing,
+}
+
+/// Action to take for source rows not matched by the merge predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeNotMatchedAction {
+    /// Insert the source row.
+    Insert,
+    /// Skip the source row.
+    DoNothing,
+}
+
+/// An execution plan for merging (upserting) data into a delta table.
+///
+/// Rows from `input` are matched against the table on equality of all `keys`
+/// columns. A target row may only be affected once, so `input` may not
+/// contain multiple rows with the same key. If matched rows are left as is,
+/// only the first row for each key is merged instead.
+#[derive(Debug)]
+pub struct NativeTableMergeExec {
+    input: Arc<dyn ExecutionPlan>,
+    store: Arc<dyn LogStore>,
+    snapshot: DeltaTableState,
+    keys: Vec<String>,
+    matched: MergeMatchedAction,
+    not_matched: MergeNotMatchedAction,
+}
+
+impl NativeTableMergeExec {
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        store: Arc<dyn LogStore>,
+        snapshot: DeltaTableState,
+        keys: Vec<String>,
+        matched: MergeMatchedAction,
+        not_matched: MergeNotMatchedAction,
+    ) -> Self {
+        NativeTableMergeExec {
+            input,
+            store,
+            snapshot,
+            keys,
+            matched,
+            not_matched,
+        }
+    }
+}
+
+impl ExecutionPlan for NativeTableMergeExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        COUNT_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::UnspecifiedDistribution]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![false]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(Self {
+            input: children[0].clone(),
+            store: self.store.clone(),
+            snapshot: self.snapshot.clone(),
+            keys: self.keys.clone(),
+            matched: self.matched.clone(),
+            not_matched: self.not_matched,
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(
+                format!("Invalid requested partition {partition}. NativeTableMergeExec requires a single input partition.")));
+        }
+
+        let state = SessionState::new_with_config_rt(
+            context.session_config().clone(),
+            context.runtime_env(),
+        );
+
+        let input = cast_unsupported_fields(self.input.clone(), &state)?;
+        let connection_id = session_connection_id(context.session_config());
+        let store = self.store.clone();
+        let snapshot = self.snapshot.clone();
+        let keys = self.keys.clone();
+        let matched = self.matched.clone();
+        let not_matched = self.not_matched;
+
+        let output = futures::stream::once(async move {
+            // Merge requires the source as a data frame, so buffer the input
+            // into a memory table first.
+            let schema = input.schema();
+            let batches = collect(input, context).await?;
+            let batches = dedup_source_keys(
+                &schema,
+                batches,
+                &keys,
+                matched == MergeMatchedAction::DoNothing,
+            )?;
+            let source = SessionContext::new_with_state(state.clone())
+                .read_table(Arc::new(MemTable::try_new(schema.clone(), vec![batches])?))?;
+
+            let predicate = keys
+                .iter()
+                .map(|key| {
+                    Expr::Column(Column::new(Some(MERGE_TARGET_ALIAS), key))
+                        .eq(Expr::Column(Column::new(Some(MERGE_SOURCE_ALIAS), key)))
+                })
+                .reduce(Expr::and)
+                .ok_or_else(|| {
+                    DataFusionError::Internal("merge requires at least one key".to_string())
+                })?;
+
+            let source_col = |name: &str| Expr::Column(Column::new(Some(MERGE_SOURCE_ALIAS), name));
+
+            let mut builder = MergeBuilder::new(store, snapshot, predicate, source)
+                .with_session_state(state)
+                .with_source_alias(MERGE_SOURCE_ALIAS)
+                .with_target_alias(MERGE_TARGET_ALIAS)
+                .with_metadata(commit_metadata(connection_id));
+
+            builder = match matched {
+                MergeMatchedAction::Update => builder.when_matched_update(|update| {
+                    schema.fields().iter().fold(update, |update, field| {
+                        update.update(field.name().as_str(), source_col(field.name()))
+                    })
+                }),
+                MergeMatchedAction::UpdateSet {
+                    assignments,
+                    predicate,
+                } => builder.when_matched_update(|update| {
+                    let update = match predicate {
+                        Some(predicate) => update.predicate(predicate),
+                        None => update,
+                    };
+                    assignments
+                        .into_iter()
+                        .fold(update, |update, (column, expr)| update.update(column, expr))
+                }),
+                MergeMatchedAction::Delete => builder.when_matched_delete(|delete| delete),
+                MergeMatchedAction::DoNothing => Ok(builder),
+            }
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+            builder = match not_matched {
+                MergeNotMatchedAction::Insert => builder.when_not_matched_insert(|insert| {
+                    schema.fields().iter().fold(insert, |insert, field| {
+                        insert.set(field.name().as_str(), source_col(field.name()))
+                    })
+                }),
+                MergeNotMatchedAction::DoNothing => Ok(builder),
+            }
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+            let (_, metrics) = builder
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+            let count = metrics.num_target_rows_inserted
+                + metrics.num_target_rows_updated
+                + metrics.num_target_rows_deleted;
+
+            Ok(create_count_record_batch(count as u64))
+        })
+        .boxed();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            output,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for NativeTableMergeExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(f, "NativeTableMergeExec: keys=[{}]", self.keys.join(", "))
+            }
+            DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "NativeTableMergeExec: keys=[{}], matched={:?}, not_matched={:?}",
+                    self.keys.join(", "),
+                    self.matched,
+                    self.not_matched
+                )
             }
         }
     }
 }
+
+/// Check that no two rows in `batches` have the same (non-null) key.
+///
+/// If `drop_duplicates` is set, every row after the first one for a key is
+/// dropped instead of erroring.
+fn dedup_source_keys(
+    schema: &SchemaRef,
+    batches: Vec<RecordBatch>,
+    keys: &[String],
+    drop_duplicates: bool,
+) -> DataFusionResult<Vec<RecordBatch>> {
+    let indices = keys
+        .iter()
+        .map(|key| schema.index_of(key))
+        .collect::<Result<Vec<_>, _>>()?;
+    let converter = RowConverter::new(
+        indices
+            .iter()
+            .map(|idx| SortField::new(schema.field(*idx).data_type().clone()))
+            .collect(),
+    )?;
+
+    let mut seen = HashSet::new();
+    batches
+        .into_iter()
+        .map(|batch| {
+            let columns: Vec<_> = indices.iter().map(|idx| batch.column(*idx).clone()).collect();
+            let rows = converter.convert_columns(&columns)?;
+
+            let mut keep = Vec::with_capacity(rows.num_rows());
+            for (row_idx, row) in rows.iter().enumerate() {
+                // Null keys never match anything, including each other.
+                if columns.iter().any(|col| col.is_null(row_idx)) {
+                    keep.push(true);
+                    continue;
+                }
+                let first = seen.insert(row.owned());
+                if !first && !drop_duplicates {
+                    return Err(DataFusionError::Execution(format!(
+                        "Merge command cannot affect row a second time: multiple source rows have the same key ({})",
+                        keys.join(", ")
+                    )));
+                }
+                keep.push(first);
+            }
+
+            Ok(filter_record_batch(&batch, &BooleanArray::from(keep))?)
+        })
+        .collect()
+}
+
+/// Sum the row counts recorded in the stats of files added by the latest
+/// commit to `table`.
+///
+/// Returns `None` if the commit can't be read or any added file is missing
+/// stats.
+async fn count_committed_rows(table: &DeltaTable) -> Option<u64> {
+    let version = table.version();
+    let commit = table.log_store().read_commit_entry(version).await.ok()??;
+    let actions = get_actions(version, commit).await.ok()?;
+
+    let mut count = 0;
+    for action in actions {
+        if let Action::Add(add) = action {
+            let stats = add.get_stats().ok()??;
+            count += stats.num_records as u64;
+        }
+    }
+
+    Some(count)
+}
+
+/// Alias for the input rows in merge expressions.
+pub const MERGE_SOURCE_ALIAS: &str = "source";
+/// Alias for the table's rows in merge expressions.
+pub const MERGE_TARGET_ALIAS: &str = "target";
+
+/// delta-rs does not support all data types, so we need to check if the input
+/// schema contains any unsupported data types. If it does, we need to cast them
+/// to supported data types.
+fn cast_unsupported_fields(
+    input: Arc<dyn ExecutionPlan>,
+    state: &SessionState,
+) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+    let schema = input.schema();
+    let fields = schema.fields().clone();
+    let input_dfschema = schema.to_dfschema()?;
+
+    let mut contains_unsupported_fields = false;
+    let projections = fields
+        .iter()
+        .map(|field| {
+            let e = if StructField::try_from(field.as_ref()).is_ok() {
+                ident(field.name())
+            } else {
+                contains_unsupported_fields = true;
+
+                match field.data_type() {
+                    DataType::Timestamp(_, _) => Expr::Cast(Cast {
+                        expr: Box::new(ident(field.name())),
+                        data_type: DataType::Timestamp(
+                            datafusion::arrow::datatypes::TimeUnit::Microsecond,
+                            None,
+                        ),
+                    }),
+
+                    dtype => {
+                        return Err(DataFusionError::Execution(format!(
+                            "Unsupported data type {:?} for field {}",
+                            dtype,
+                            field.name()
+                        )))
+                    }
+                }
+            };
+            let e = create_physical_expr(&e, &input_dfschema, state.execution_props()).unwrap();
+            Ok((e, field.name().clone()))
+        })
+        .collect::<DataFusionResult<Vec<_>>>()?;
+
+    if contains_unsupported_fields {
+        Ok(Arc::new(ProjectionExec::try_new(projections, input)?))
+    } else {
+        Ok(input)
+    }
+}
@@ -4,3 +4,5 @@
 pub mod access;
 pub mod errors;
 pub mod insert;
+pub mod optimize;
+pub mod vacuum;
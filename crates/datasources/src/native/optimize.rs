@@ -0,0 +1,150 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use datafusion::arrow::array::UInt64Array;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use deltalake::logstore::LogStore;
+use deltalake::operations::optimize::{OptimizeBuilder, OptimizeType};
+use deltalake::table::state::DeltaTableState;
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+
+use crate::native::access::{commit_metadata, session_connection_id};
+
+/// Output schema for optimize, a single row with the number of files removed
+/// and added by the compaction.
+pub static OPTIMIZE_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("files_removed", DataType::UInt64, false),
+        Field::new("files_added", DataType::UInt64, false),
+    ]))
+});
+
+/// An execution plan for compacting the small files of a delta table into
+/// larger ones.
+#[derive(Debug)]
+pub struct OptimizeExec {
+    store: Arc<dyn LogStore>,
+    snapshot: DeltaTableState,
+    zorder_by: Vec<String>,
+}
+
+impl OptimizeExec {
+    /// Create a new optimize exec.
+    ///
+    /// If `zorder_by` is empty, files are only bin-packed. Otherwise the data
+    /// is rewritten using a Z-order curve over the given columns.
+    pub fn new(
+        store: Arc<dyn LogStore>,
+        snapshot: DeltaTableState,
+        zorder_by: Vec<String>,
+    ) -> Self {
+        OptimizeExec {
+            store,
+            snapshot,
+            zorder_by,
+        }
+    }
+}
+
+impl ExecutionPlan for OptimizeExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        OPTIMIZE_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            Err(DataFusionError::Plan(
+                "Cannot change children for OptimizeExec".to_string(),
+            ))
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "Invalid requested partition {partition}. OptimizeExec requires a single partition."
+            )));
+        }
+
+        let connection_id = session_connection_id(context.session_config());
+        let mut builder = OptimizeBuilder::new(self.store.clone(), self.snapshot.clone())
+            .with_metadata(commit_metadata(connection_id));
+        if !self.zorder_by.is_empty() {
+            builder = builder.with_type(OptimizeType::ZOrder(self.zorder_by.clone()));
+        }
+
+        let output = futures::stream::once(async move {
+            let (_, metrics) = builder
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+            Ok(RecordBatch::try_new(
+                OPTIMIZE_SCHEMA.clone(),
+                vec![
+                    Arc::new(UInt64Array::from(vec![metrics.num_files_removed])),
+                    Arc::new(UInt64Array::from(vec![metrics.num_files_added])),
+                ],
+            )?)
+        })
+        .boxed();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            output,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for OptimizeExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => write!(f, "OptimizeExec"),
+            DisplayFormatType::Verbose => {
+                write!(f, "OptimizeExec: zorder_by={:?}", self.zorder_by)
+            }
+        }
+    }
+}
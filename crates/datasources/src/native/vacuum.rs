@@ -0,0 +1,147 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use datafusion::arrow::array::StringArray;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use deltalake::logstore::LogStore;
+use deltalake::operations::vacuum::VacuumBuilder;
+use deltalake::table::state::DeltaTableState;
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+
+/// Output schema for vacuum, one row per deleted file.
+pub static VACUUM_SCHEMA: Lazy<SchemaRef> =
+    Lazy::new(|| Arc::new(Schema::new(vec![Field::new("path", DataType::Utf8, false)])));
+
+/// An execution plan for removing files no longer referenced by a delta table.
+#[derive(Debug)]
+pub struct VacuumExec {
+    store: Arc<dyn LogStore>,
+    snapshot: DeltaTableState,
+    retention: Option<chrono::Duration>,
+    enforce_retention: bool,
+    dry_run: bool,
+}
+
+impl VacuumExec {
+    /// Create a new vacuum exec.
+    ///
+    /// If `retention` is `None`, the table's configured retention period is
+    /// used. An explicit retention period may only be shorter than the
+    /// configured one if `enforce_retention` is false.
+    pub fn new(
+        store: Arc<dyn LogStore>,
+        snapshot: DeltaTableState,
+        retention: Option<chrono::Duration>,
+        enforce_retention: bool,
+        dry_run: bool,
+    ) -> Self {
+        VacuumExec {
+            store,
+            snapshot,
+            retention,
+            enforce_retention,
+            dry_run,
+        }
+    }
+}
+
+impl ExecutionPlan for VacuumExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        VACUUM_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            Err(DataFusionError::Plan(
+                "Cannot change children for VacuumExec".to_string(),
+            ))
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "Invalid requested partition {partition}. VacuumExec requires a single partition."
+            )));
+        }
+
+        let mut builder = VacuumBuilder::new(self.store.clone(), self.snapshot.clone())
+            .with_dry_run(self.dry_run)
+            .with_enforce_retention_duration(self.enforce_retention);
+        if let Some(retention) = self.retention {
+            builder = builder.with_retention_period(retention);
+        }
+
+        let output = futures::stream::once(async move {
+            let (_, metrics) = builder
+                .await
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+            let paths = StringArray::from(metrics.files_deleted);
+            Ok(RecordBatch::try_new(
+                VACUUM_SCHEMA.clone(),
+                vec![Arc::new(paths)],
+            )?)
+        })
+        .boxed();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            output,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for VacuumExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => write!(f, "VacuumExec"),
+            DisplayFormatType::Verbose => {
+                write!(f, "VacuumExec: dry_run={}", self.dry_run)
+            }
+        }
+    }
+}
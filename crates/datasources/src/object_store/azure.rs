@@ -10,7 +10,7 @@ use object_store::{CredentialProvider, ObjectStore};
 use protogen::metastore::types::options::StorageOptions;
 
 use super::errors::{ObjectStoreSourceError, Result};
-use super::ObjStoreAccess;
+use super::{opts_cache_key, ObjStoreAccess};
 use crate::common::url::{DatasourceUrl, DatasourceUrlType};
 
 #[derive(Debug, Clone)]
@@ -107,6 +107,16 @@ impl ObjStoreAccess for AzureStoreAccess {
         Ok(Arc::new(build))
     }
 
+    fn cache_key(&self) -> Option<String> {
+        Some(format!(
+            "azure://{}?account_name={:?}&access_key={:?}&opts={}",
+            self.container,
+            self.account_name,
+            self.access_key,
+            opts_cache_key(&self.opts),
+        ))
+    }
+
     fn path(&self, location: &str) -> Result<ObjectStorePath> {
         Ok(ObjectStorePath::from_url_path(location)?)
     }
@@ -9,7 +9,7 @@ use object_store::path::Path as ObjectStorePath;
 use object_store::{CredentialProvider, ObjectStore};
 
 use super::errors::Result;
-use super::ObjStoreAccess;
+use super::{opts_cache_key, ObjStoreAccess};
 
 #[derive(Debug, Clone)]
 pub struct GcsStoreAccess {
@@ -63,6 +63,15 @@ impl ObjStoreAccess for GcsStoreAccess {
         Ok(Arc::new(build))
     }
 
+    fn cache_key(&self) -> Option<String> {
+        Some(format!(
+            "gs://{}?service_account_key={:?}&opts={}",
+            self.bucket,
+            self.service_account_key,
+            opts_cache_key(&self.opts),
+        ))
+    }
+
     fn path(&self, location: &str) -> Result<ObjectStorePath> {
         Ok(ObjectStorePath::from_url_path(location)?)
     }
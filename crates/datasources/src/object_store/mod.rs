@@ -4,7 +4,7 @@ use std::fmt::{Debug, Display};
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
 use datafusion::common::FileType;
 use datafusion::datasource::file_format::FileFormat;
 use datafusion::datasource::listing::PartitionedFile;
@@ -25,10 +25,12 @@ use futures::StreamExt;
 use glob::{MatchOptions, Pattern};
 use object_store::path::Path as ObjectStorePath;
 use object_store::{ObjectMeta, ObjectStore};
+use object_store_util::registry::ObjectStoreRegistry;
 use protogen::metastore::types::options::{TableOptionsObjectStore, TableOptionsV0};
 
 use self::azure::AzureStoreAccess;
 use self::glob_util::{get_resolved_patterns, ResolvedPattern};
+use self::partition::HivePartitions;
 use crate::common::exprs_to_phys_exprs;
 use crate::common::url::DatasourceUrl;
 use crate::lake::storage_options_into_store_access;
@@ -42,6 +44,7 @@ pub mod gcs;
 pub mod glob_util;
 pub mod http;
 pub mod local;
+pub mod partition;
 pub mod s3;
 
 pub struct MultiSourceTableProvider {
@@ -135,6 +138,27 @@ pub trait ObjStoreAccess: Debug + Display + Send + Sync {
     /// Creates an object store.
     fn create_store(&self) -> Result<Arc<dyn ObjectStore>>;
 
+    /// Returns a key identifying the store created by `create_store`,
+    /// including any credentials.
+    ///
+    /// Stores for accesses returning `None` are never reused.
+    fn cache_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Gets an object store, reusing a previously created store with the same
+    /// cache key if there is one.
+    ///
+    /// The store is limited to the process-wide maximum number of concurrent
+    /// GET requests, if one is configured.
+    fn cached_store(&self) -> Result<Arc<dyn ObjectStore>> {
+        let registry = ObjectStoreRegistry::global();
+        match self.cache_key() {
+            Some(key) => registry.get_or_try_insert(key, || self.create_store()),
+            None => Ok(registry.limit(self.create_store()?)),
+        }
+    }
+
     /// Gets the object store path.
     fn path(&self, location: &str) -> Result<ObjectStorePath>;
 
@@ -221,7 +245,7 @@ impl ObjStoreAccessor {
     /// Creates a new object store accessor for the given access.
     pub fn new(access: Arc<dyn ObjStoreAccess>) -> Result<Self> {
         Ok(Self {
-            store: access.create_store()?,
+            store: access.cached_store()?,
             access,
         })
     }
@@ -237,6 +261,9 @@ impl ObjStoreAccessor {
     }
 
     /// Takes all the objects and creates the table provider from the accesor.
+    ///
+    /// Hive-style `key=value` directories in the object paths are exposed as
+    /// partition columns.
     pub async fn into_table_provider(
         self,
         state: &SessionState,
@@ -249,13 +276,15 @@ impl ObjStoreAccessor {
             .infer_schema(&store, state, file_format.as_ref(), &objects)
             .await?;
         let base_url = self.access.base_url()?;
-        Ok(Arc::new(ObjStoreTableProvider {
+        let partitions = HivePartitions::detect(&objects);
+        Ok(Arc::new(ObjStoreTableProvider::new_with_partitions(
             store,
             arrow_schema,
             base_url,
             objects,
             file_format,
-        }))
+            partitions,
+        )))
     }
 
     /// Take the accessor and return the underlying object store.
@@ -267,10 +296,14 @@ impl ObjStoreAccessor {
 #[derive(Debug)]
 pub struct ObjStoreTableProvider {
     store: Arc<dyn ObjectStore>,
+    /// Schema of the files, excluding partition columns.
     arrow_schema: SchemaRef,
+    /// Schema of the table, the file schema followed by partition columns.
+    table_schema: SchemaRef,
     base_url: ObjectStoreUrl,
     objects: Vec<ObjectMeta>,
     file_format: Arc<dyn FileFormat>,
+    partitions: HivePartitions,
 }
 
 impl ObjStoreTableProvider {
@@ -281,12 +314,40 @@ impl ObjStoreTableProvider {
         objects: Vec<ObjectMeta>,
         file_format: Arc<dyn FileFormat>,
     ) -> ObjStoreTableProvider {
+        Self::new_with_partitions(
+            store,
+            arrow_schema,
+            base_url,
+            objects,
+            file_format,
+            HivePartitions::default(),
+        )
+    }
+
+    fn new_with_partitions(
+        store: Arc<dyn ObjectStore>,
+        arrow_schema: SchemaRef,
+        base_url: ObjectStoreUrl,
+        objects: Vec<ObjectMeta>,
+        file_format: Arc<dyn FileFormat>,
+        partitions: HivePartitions,
+    ) -> ObjStoreTableProvider {
+        let table_schema = if partitions.fields().is_empty() {
+            arrow_schema.clone()
+        } else {
+            let mut fields: Vec<_> = arrow_schema.fields().iter().cloned().collect();
+            fields.extend(partitions.fields().iter().cloned().map(Arc::new));
+            Arc::new(Schema::new(fields))
+        };
+
         ObjStoreTableProvider {
             store,
             arrow_schema,
+            table_schema,
             base_url,
             objects,
             file_format,
+            partitions,
         }
     }
 }
@@ -298,7 +359,7 @@ impl TableProvider for ObjStoreTableProvider {
     }
 
     fn schema(&self) -> SchemaRef {
-        self.arrow_schema.clone()
+        self.table_schema.clone()
     }
 
     fn table_type(&self) -> TableType {
@@ -312,20 +373,34 @@ impl TableProvider for ObjStoreTableProvider {
         filters: &[Expr],
         limit: Option<usize>,
     ) -> DatafusionResult<Arc<dyn ExecutionPlan>> {
+        // Filters only referencing partition columns are evaluated here by
+        // skipping objects, the rest are handed to the file format.
+        let (partition_filters, filters): (Vec<_>, Vec<_>) = filters
+            .iter()
+            .cloned()
+            .partition(|filter| self.partitions.is_partition_filter(filter));
+        let keep = self.partitions.prune(ctx, &partition_filters)?;
+
         // See datafusion's `ListingTable::list_files_for_scan`.
-        let files = futures::stream::iter(&self.objects)
-            .map(|object| async {
-                let file: PartitionedFile = object.clone().into();
+        let objects = self
+            .objects
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| keep.get(*idx).copied().unwrap_or(true));
+        let files = futures::stream::iter(objects)
+            .map(|(idx, object)| async move {
+                let mut file: PartitionedFile = object.clone().into();
+                file.partition_values = self.partitions.values(idx);
                 let stats = self
                     .file_format
-                    .infer_stats(ctx, &self.store, self.schema(), object)
+                    .infer_stats(ctx, &self.store, self.arrow_schema.clone(), object)
                     .await?;
                 Ok((file, stats))
             })
             .boxed()
             .buffered(ctx.config_options().execution.meta_fetch_concurrency);
         let (files, statistics) =
-            get_statistics_with_limit(files, self.schema(), limit, true).await?;
+            get_statistics_with_limit(files, self.arrow_schema.clone(), limit, true).await?;
 
         // If there are no files, return an empty exec plan.
         if files.is_empty() {
@@ -341,10 +416,10 @@ impl TableProvider for ObjStoreTableProvider {
             statistics,
             projection: projection.cloned(),
             limit,
-            table_partition_cols: Vec::new(),
+            table_partition_cols: self.partitions.fields().to_vec(),
             output_ordering: Vec::new(),
         };
-        let filters = exprs_to_phys_exprs(filters, ctx, &self.arrow_schema)?;
+        let filters = exprs_to_phys_exprs(&filters, ctx, &self.arrow_schema)?;
 
         // We register the store at scan time so that it can be used by the
         // exec plan.
@@ -364,14 +439,31 @@ impl TableProvider for ObjStoreTableProvider {
         filters: &[&Expr],
     ) -> std::result::Result<Vec<TableProviderFilterPushDown>, datafusion::error::DataFusionError>
     {
-        // todo: support exact pushdonws based on hive style partitioning
+        // Partition filters are exact since they're applied by skipping whole
+        // objects.
         filters
             .iter()
-            .map(|_| Ok(TableProviderFilterPushDown::Inexact))
+            .map(|filter| {
+                if self.partitions.is_partition_filter(filter) {
+                    Ok(TableProviderFilterPushDown::Exact)
+                } else {
+                    Ok(TableProviderFilterPushDown::Inexact)
+                }
+            })
             .collect()
     }
 }
 
+/// Formats store options for use in a cache key.
+///
+/// Options are sorted so that the key doesn't depend on the map's iteration
+/// order.
+pub(crate) fn opts_cache_key<K: AsRef<str>>(opts: &HashMap<K, String>) -> String {
+    let mut opts: Vec<_> = opts.iter().map(|(k, v)| (k.as_ref(), v.as_str())).collect();
+    opts.sort_unstable();
+    format!("{opts:?}")
+}
+
 pub fn file_type_from_path(path: &ObjectStorePath) -> Result<FileType> {
     path.extension()
         .ok_or(ObjectStoreSourceError::NoFileExtension)?
@@ -427,6 +519,16 @@ pub fn init_session_registry<'a>(
                 location,
                 storage_options,
                 ..
+            })
+            | TableOptionsV0::Ndjson(TableOptionsObjectStore {
+                location,
+                storage_options,
+                ..
+            })
+            | TableOptionsV0::Avro(TableOptionsObjectStore {
+                location,
+                storage_options,
+                ..
             }) => {
                 let url = DatasourceUrl::try_new(location)?;
                 storage_options_into_store_access(&url, storage_options)
@@ -450,7 +552,7 @@ pub fn init_session_registry<'a>(
         };
 
         let base_url = access.base_url()?;
-        let store = access.create_store()?;
+        let store = access.cached_store()?;
         runtime.register_object_store(base_url.as_ref(), store);
     }
 
@@ -0,0 +1,221 @@
+//! Hive-style partitioning, where objects are laid out in directories like
+//! `year=2024/month=01/data.parquet`.
+
+use datafusion::arrow::array::{Array, BooleanArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::cast::as_boolean_array;
+use datafusion::error::Result as DatafusionResult;
+use datafusion::execution::context::SessionState;
+use datafusion::prelude::Expr;
+use datafusion::scalar::ScalarValue;
+use object_store::ObjectMeta;
+
+use crate::common::exprs_to_phys_exprs;
+
+/// Directory name Hive uses for null partition values.
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Partition columns recovered from the object paths of a table.
+#[derive(Debug, Clone, Default)]
+pub struct HivePartitions {
+    fields: Vec<Field>,
+    /// Partition values for each object, in the same order as the objects
+    /// the partitions were detected from.
+    values: Vec<Vec<ScalarValue>>,
+}
+
+impl HivePartitions {
+    /// Detect partition columns from `key=value` directory segments.
+    ///
+    /// Every object must have the same keys in the same order, otherwise no
+    /// partitioning is detected. Columns whose values all parse as integers
+    /// are typed as `Int64`, everything else is `Utf8`.
+    pub fn detect(objects: &[ObjectMeta]) -> HivePartitions {
+        let mut keys: Option<Vec<String>> = None;
+        let mut raw_values = Vec::with_capacity(objects.len());
+
+        for object in objects {
+            let (obj_keys, obj_values): (Vec<_>, Vec<_>) = partition_segments(object).unzip();
+            match &keys {
+                Some(keys) if keys != &obj_keys => return HivePartitions::default(),
+                Some(_) => (),
+                None => keys = Some(obj_keys),
+            }
+            raw_values.push(obj_values);
+        }
+
+        let keys = match keys {
+            Some(keys) if !keys.is_empty() => keys,
+            _ => return HivePartitions::default(),
+        };
+
+        let fields: Vec<_> = keys
+            .into_iter()
+            .enumerate()
+            .map(|(idx, key)| {
+                let is_int = raw_values.iter().all(|values| match &values[idx] {
+                    Some(v) => v.parse::<i64>().is_ok(),
+                    None => true,
+                });
+                let typ = if is_int {
+                    DataType::Int64
+                } else {
+                    DataType::Utf8
+                };
+                Field::new(key, typ, true)
+            })
+            .collect();
+
+        let values = raw_values
+            .into_iter()
+            .map(|values| {
+                values
+                    .into_iter()
+                    .zip(&fields)
+                    .map(|(value, field)| match field.data_type() {
+                        DataType::Int64 => ScalarValue::Int64(value.map(|v| v.parse().unwrap())),
+                        _ => ScalarValue::Utf8(value),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        HivePartitions { fields, values }
+    }
+
+    /// The partition columns, appended to the file schema to get the table
+    /// schema.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Partition values for the object at `idx`.
+    pub fn values(&self, idx: usize) -> Vec<ScalarValue> {
+        self.values.get(idx).cloned().unwrap_or_default()
+    }
+
+    /// Returns true if the filter only references partition columns, meaning
+    /// it can be fully evaluated by pruning objects.
+    pub fn is_partition_filter(&self, filter: &Expr) -> bool {
+        if self.fields.is_empty() {
+            return false;
+        }
+        match filter.to_columns() {
+            Ok(cols) => {
+                !cols.is_empty()
+                    && cols
+                        .iter()
+                        .all(|col| self.fields.iter().any(|f| f.name() == &col.name))
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Evaluate partition filters against the partition values of every
+    /// object, returning whether each object should be scanned.
+    pub fn prune(&self, state: &SessionState, filters: &[Expr]) -> DatafusionResult<Vec<bool>> {
+        let num_objects = self.values.len();
+        let schema = Schema::new(self.fields.clone());
+        let predicate = match exprs_to_phys_exprs(filters, state, &schema)? {
+            Some(predicate) => predicate,
+            None => return Ok(vec![true; num_objects]),
+        };
+
+        let columns = (0..self.fields.len())
+            .map(|col| ScalarValue::iter_to_array(self.values.iter().map(|v| v[col].clone())))
+            .collect::<DatafusionResult<Vec<_>>>()?;
+        let batch = RecordBatch::try_new(schema.into(), columns)?;
+
+        let result = predicate.evaluate(&batch)?.into_array(num_objects)?;
+        let result: &BooleanArray = as_boolean_array(&result)?;
+        Ok((0..num_objects)
+            .map(|idx| result.is_valid(idx) && result.value(idx))
+            .collect())
+    }
+}
+
+/// Iterate over the `key=value` directory segments of an object's location.
+/// The file name itself is never a partition segment.
+fn partition_segments(object: &ObjectMeta) -> impl Iterator<Item = (String, Option<String>)> + '_ {
+    let parts: Vec<_> = object.location.parts().collect();
+    let num_dirs = parts.len().saturating_sub(1);
+    parts.into_iter().take(num_dirs).filter_map(|part| {
+        let (key, value) = part.as_ref().split_once('=')?;
+        if key.is_empty() {
+            return None;
+        }
+        let value = match value {
+            "" | HIVE_DEFAULT_PARTITION => None,
+            v => Some(v.to_string()),
+        };
+        Some((key.to_string(), value))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use object_store::path::Path as ObjectStorePath;
+
+    use super::*;
+
+    fn object(path: &str) -> ObjectMeta {
+        ObjectMeta {
+            location: ObjectStorePath::from(path),
+            last_modified: Utc::now(),
+            size: 0,
+            e_tag: None,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn detect_partitions() {
+        let partitions = HivePartitions::detect(&[
+            object("data/year=2023/month=12/part-0.parquet"),
+            object("data/year=2024/month=01/part-0.parquet"),
+            object("data/year=2024/month=__HIVE_DEFAULT_PARTITION__/part-0.parquet"),
+        ]);
+
+        assert_eq!(
+            vec![
+                Field::new("year", DataType::Int64, true),
+                Field::new("month", DataType::Int64, true)
+            ],
+            partitions.fields()
+        );
+        assert_eq!(
+            vec![ScalarValue::Int64(Some(2024)), ScalarValue::Int64(Some(1))],
+            partitions.values(1)
+        );
+        assert_eq!(
+            vec![ScalarValue::Int64(Some(2024)), ScalarValue::Int64(None)],
+            partitions.values(2)
+        );
+    }
+
+    #[test]
+    fn detect_string_partitions() {
+        let partitions = HivePartitions::detect(&[
+            object("data/region=us/part-0.csv"),
+            object("data/region=eu/part-0.csv"),
+        ]);
+        assert_eq!(
+            vec![Field::new("region", DataType::Utf8, true)],
+            partitions.fields()
+        );
+    }
+
+    #[test]
+    fn inconsistent_partitions() {
+        let partitions = HivePartitions::detect(&[
+            object("data/year=2024/part-0.parquet"),
+            object("data/month=01/part-0.parquet"),
+        ]);
+        assert!(partitions.fields().is_empty());
+
+        let partitions = HivePartitions::detect(&[object("data/key=value.parquet")]);
+        assert!(partitions.fields().is_empty());
+    }
+}
@@ -9,7 +9,7 @@ use object_store::path::Path as ObjectStorePath;
 use object_store::{CredentialProvider, ObjectStore};
 
 use super::errors::Result;
-use super::ObjStoreAccess;
+use super::{opts_cache_key, ObjStoreAccess};
 
 #[derive(Debug, Clone)]
 pub struct S3StoreAccess {
@@ -77,6 +77,17 @@ impl ObjStoreAccess for S3StoreAccess {
         Ok(Arc::new(build))
     }
 
+    fn cache_key(&self) -> Option<String> {
+        Some(format!(
+            "s3://{}?region={:?}&access_key_id={:?}&secret_access_key={:?}&opts={}",
+            self.bucket,
+            self.region,
+            self.access_key_id,
+            self.secret_access_key,
+            opts_cache_key(&self.opts),
+        ))
+    }
+
     fn path(&self, location: &str) -> Result<ObjectStorePath> {
         Ok(ObjectStorePath::from_url_path(location)?)
     }
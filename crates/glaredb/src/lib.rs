@@ -676,6 +676,13 @@ impl Operation {
             ExecutionResult::UpdateSuccess { updated_rows } => {
                 Self::numeric_result("count", updated_rows as u64)
             }
+            ExecutionResult::CopySuccess { copied_rows } => {
+                Self::numeric_result("count", copied_rows as u64)
+            }
+            ExecutionResult::OverwriteSuccess { rows_written } => {
+                Self::numeric_result("count", rows_written as u64)
+            }
+            ExecutionResult::Explain { plan, .. } => Self::operation_result("QUERY PLAN", plan),
             _ => Self::operation_result("result", res.to_string()),
         }
     }
@@ -1,5 +1,5 @@
 //! Module for handling the catalog for a single database.
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
@@ -24,7 +24,9 @@ use protogen::metastore::types::catalog::{
 use protogen::metastore::types::options::{
     DatabaseOptions,
     DatabaseOptionsInternal,
+    InternalColumnDefinition,
     TableOptionsInternal,
+    TableOptionsV0,
     TunnelOptions,
 };
 use protogen::metastore::types::service::{AlterDatabaseOperation, AlterTableOperation, Mutation};
@@ -865,6 +867,8 @@ impl State {
                     tunnel_id: None,
                     access_mode: SourceAccessMode::ReadWrite,
                     columns: None,
+                    comment: None,
+                    column_comments: BTreeMap::new(),
                 };
 
                 let policy =
@@ -906,6 +910,8 @@ impl State {
                     tunnel_id,
                     access_mode: SourceAccessMode::ReadOnly,
                     columns: create_ext.columns,
+                    comment: None,
+                    column_comments: BTreeMap::new(),
                 };
 
                 let policy = CreatePolicy::new(create_ext.if_not_exists, create_ext.or_replace)?;
@@ -975,6 +981,213 @@ impl State {
                             other => unreachable!("unexpected entry type: {:?}", other),
                         };
                     }
+                    AlterTableOperation::RenameColumn { old_name, new_name } => {
+                        validate_object_name(&new_name)?;
+
+                        let oid = match objs.tables.get(&alter_table.name) {
+                            None => {
+                                return Err(MetastoreError::MissingNamedObject {
+                                    schema: alter_table.schema,
+                                    name: alter_table.name,
+                                })
+                            }
+                            Some(id) => id,
+                        };
+
+                        let ent = self.entries.get_mut(oid)?.unwrap();
+                        if is_partition_column(ent, &old_name) {
+                            return Err(MetastoreError::AlterPartitionColumn(old_name));
+                        }
+                        let columns = native_table_columns_mut(ent)?;
+                        if columns.iter().any(|col| col.name == new_name) {
+                            return Err(MetastoreError::DuplicateName(new_name));
+                        }
+
+                        match columns.iter_mut().find(|col| col.name == old_name) {
+                            Some(col) => col.name = new_name.clone(),
+                            None => {
+                                return Err(MetastoreError::MissingColumn {
+                                    table: alter_table.name,
+                                    column: old_name,
+                                })
+                            }
+                        }
+
+                        // The comment follows the column.
+                        if let CatalogEntry::Table(ent) = ent {
+                            if let Some(comment) = ent.column_comments.remove(&old_name) {
+                                ent.column_comments.insert(new_name, comment);
+                            }
+                        }
+                    }
+                    AlterTableOperation::AddColumn { name, arrow_type } => {
+                        validate_object_name(&name)?;
+
+                        let oid = match objs.tables.get(&alter_table.name) {
+                            None => {
+                                return Err(MetastoreError::MissingNamedObject {
+                                    schema: alter_table.schema,
+                                    name: alter_table.name,
+                                })
+                            }
+                            Some(id) => id,
+                        };
+
+                        let columns =
+                            native_table_columns_mut(self.entries.get_mut(oid)?.unwrap())?;
+                        if columns.iter().any(|col| col.name == name) {
+                            return Err(MetastoreError::DuplicateName(name));
+                        }
+
+                        // New columns are always nullable so existing rows
+                        // remain valid.
+                        columns.push(InternalColumnDefinition {
+                            name,
+                            nullable: true,
+                            arrow_type,
+                        });
+                    }
+                    AlterTableOperation::DropColumn { name, if_exists } => {
+                        let oid = match objs.tables.get(&alter_table.name) {
+                            None => {
+                                return Err(MetastoreError::MissingNamedObject {
+                                    schema: alter_table.schema,
+                                    name: alter_table.name,
+                                })
+                            }
+                            Some(id) => id,
+                        };
+
+                        let ent = self.entries.get_mut(oid)?.unwrap();
+                        if is_partition_column(ent, &name) {
+                            return Err(MetastoreError::AlterPartitionColumn(name));
+                        }
+                        let columns = native_table_columns_mut(ent)?;
+                        match columns.iter().position(|col| col.name == name) {
+                            Some(_) if columns.len() == 1 => {
+                                return Err(MetastoreError::DropLastColumn(alter_table.name))
+                            }
+                            Some(idx) => {
+                                columns.remove(idx);
+                                if let CatalogEntry::Table(ent) = ent {
+                                    ent.column_comments.remove(&name);
+                                }
+                            }
+                            None if if_exists => (),
+                            None => {
+                                return Err(MetastoreError::MissingColumn {
+                                    table: alter_table.name,
+                                    column: name,
+                                })
+                            }
+                        }
+                    }
+                    AlterTableOperation::WidenColumn { name, arrow_type } => {
+                        let oid = match objs.tables.get(&alter_table.name) {
+                            None => {
+                                return Err(MetastoreError::MissingNamedObject {
+                                    schema: alter_table.schema,
+                                    name: alter_table.name,
+                                })
+                            }
+                            Some(id) => id,
+                        };
+
+                        let ent = self.entries.get_mut(oid)?.unwrap();
+                        if is_partition_column(ent, &name) {
+                            return Err(MetastoreError::AlterPartitionColumn(name));
+                        }
+                        let columns = native_table_columns_mut(ent)?;
+                        let col = match columns.iter_mut().find(|col| col.name == name) {
+                            Some(col) => col,
+                            None => {
+                                return Err(MetastoreError::MissingColumn {
+                                    table: alter_table.name,
+                                    column: name,
+                                })
+                            }
+                        };
+                        if !InternalColumnDefinition::is_widening(&col.arrow_type, &arrow_type) {
+                            return Err(MetastoreError::InvalidColumnWidening {
+                                column: name,
+                                from: col.arrow_type.clone(),
+                                to: arrow_type,
+                            });
+                        }
+                        col.arrow_type = arrow_type;
+                    }
+                    AlterTableOperation::SetComment { column, comment } => {
+                        let oid = match objs.tables.get(&alter_table.name) {
+                            None => {
+                                return Err(MetastoreError::MissingNamedObject {
+                                    schema: alter_table.schema,
+                                    name: alter_table.name,
+                                })
+                            }
+                            Some(id) => id,
+                        };
+
+                        let ent = match self.entries.get_mut(oid)?.unwrap() {
+                            CatalogEntry::Table(ent) => ent,
+                            other => {
+                                return Err(MetastoreError::CommentNotSupported(
+                                    other.get_meta().name.clone(),
+                                ))
+                            }
+                        };
+
+                        match column {
+                            None => ent.comment = comment,
+                            Some(column) => {
+                                // Only check the column exists if we know the
+                                // table's columns.
+                                let columns =
+                                    ent.get_internal_columns().or_else(|| ent.columns.clone());
+                                if let Some(columns) = columns {
+                                    if !columns.iter().any(|col| col.name == column) {
+                                        return Err(MetastoreError::MissingColumn {
+                                            table: alter_table.name,
+                                            column,
+                                        });
+                                    }
+                                }
+
+                                match comment {
+                                    Some(comment) => {
+                                        ent.column_comments.insert(column, comment);
+                                    }
+                                    None => {
+                                        ent.column_comments.remove(&column);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    AlterTableOperation::SetColumns { columns } => {
+                        let oid = match objs.tables.get(&alter_table.name) {
+                            None => {
+                                return Err(MetastoreError::MissingNamedObject {
+                                    schema: alter_table.schema,
+                                    name: alter_table.name,
+                                })
+                            }
+                            Some(id) => id,
+                        };
+
+                        let ent = match self.entries.get_mut(oid)?.unwrap() {
+                            CatalogEntry::Table(ent) if ent.meta.external => ent,
+                            other => {
+                                return Err(MetastoreError::RefreshNotSupported(
+                                    other.get_meta().name.clone(),
+                                ))
+                            }
+                        };
+
+                        // Drop comments for columns that no longer exist.
+                        ent.column_comments
+                            .retain(|name, _| columns.iter().any(|col| &col.name == name));
+                        ent.columns = Some(columns);
+                    }
                 };
             }
             Mutation::AlterDatabase(alter_database) => {
@@ -1275,11 +1488,14 @@ impl BuiltinCatalog {
                     },
                     options: TableOptionsInternal {
                         columns: table.columns.clone(),
+                        partition_by: Vec::new(),
                     }
                     .into(),
                     tunnel_id: None,
                     access_mode: SourceAccessMode::ReadOnly,
                     columns: None,
+                    comment: None,
+                    column_comments: BTreeMap::new(),
                 }),
             )?;
             schema_objects
@@ -1435,6 +1651,33 @@ impl DatabaseCatalog {
     }
 }
 
+/// Get the column definitions for a native table.
+///
+/// Errors if the entry isn't a native table since we don't manage the schemas
+/// of external tables.
+fn native_table_columns_mut(ent: &mut CatalogEntry) -> Result<&mut Vec<InternalColumnDefinition>> {
+    match ent {
+        CatalogEntry::Table(TableEntry {
+            options: TableOptionsV0::Internal(opts),
+            ..
+        }) => Ok(&mut opts.columns),
+        other => Err(MetastoreError::AlterColumnsNotSupported(
+            other.get_meta().name.clone(),
+        )),
+    }
+}
+
+/// Check if `column` is one of the columns a native table is partitioned by.
+fn is_partition_column(ent: &CatalogEntry, column: &str) -> bool {
+    match ent {
+        CatalogEntry::Table(TableEntry {
+            options: TableOptionsV0::Internal(opts),
+            ..
+        }) => opts.partition_by.iter().any(|col| col == column),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -1442,6 +1685,8 @@ mod tests {
     use datafusion::arrow::datatypes::DataType;
     use object_store::memory::InMemory;
     use protogen::metastore::types::options::{
+        CredentialsOptions,
+        CredentialsOptionsDebug,
         DatabaseOptionsDebug,
         InternalColumnDefinition,
         TableOptionsDebug,
@@ -1449,11 +1694,14 @@ mod tests {
     };
     use protogen::metastore::types::service::{
         AlterDatabase,
+        AlterTable,
+        CreateCredentials,
         CreateExternalDatabase,
         CreateExternalTable,
         CreateSchema,
         CreateTable,
         CreateView,
+        DropCredentials,
         DropDatabase,
         DropSchema,
     };
@@ -1542,6 +1790,91 @@ mod tests {
         .unwrap();
     }
 
+    async fn create_credentials(db: &DatabaseCatalog, name: &str) {
+        db.try_mutate_and_commit(
+            version(db).await,
+            vec![Mutation::CreateCredentials(CreateCredentials {
+                name: name.to_string(),
+                options: CredentialsOptions::Debug(CredentialsOptionsDebug {
+                    table_type: "never_ending".to_string(),
+                }),
+                comment: String::new(),
+                or_replace: false,
+            })],
+        )
+        .await
+        .unwrap();
+    }
+
+    fn drop_credentials(names: &[&str], if_exists: bool) -> Vec<Mutation> {
+        names
+            .iter()
+            .map(|name| {
+                Mutation::DropCredentials(DropCredentials {
+                    name: name.to_string(),
+                    if_exists,
+                })
+            })
+            .collect()
+    }
+
+    fn has_credentials(state: &CatalogState, name: &str) -> bool {
+        state
+            .entries
+            .values()
+            .any(|ent| matches!(ent, CatalogEntry::Credentials(_)) && ent.get_meta().name == name)
+    }
+
+    #[tokio::test]
+    async fn drop_missing_credentials() {
+        let db = new_catalog().await;
+
+        let err = db
+            .try_mutate_and_commit(version(&db).await, drop_credentials(&["yoshi"], false))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("yoshi"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn drop_missing_credentials_if_exists() {
+        let db = new_catalog().await;
+
+        db.try_mutate_and_commit(version(&db).await, drop_credentials(&["yoshi"], true))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn drop_some_missing_credentials() {
+        let db = new_catalog().await;
+        create_credentials(&db, "mario").await;
+
+        // Nothing should be dropped if any of the credentials are missing.
+        let err = db
+            .try_mutate_and_commit(
+                version(&db).await,
+                drop_credentials(&["mario", "yoshi"], false),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("yoshi"), "{err}");
+
+        let state = db.get_state().await.unwrap();
+        assert!(has_credentials(&state, "mario"));
+
+        // With if exists, the existing credentials are dropped and the
+        // missing ones skipped.
+        let state = db
+            .try_mutate_and_commit(
+                version(&db).await,
+                drop_credentials(&["mario", "yoshi"], true),
+            )
+            .await
+            .unwrap();
+        assert!(!has_credentials(&state, "mario"));
+    }
+
     #[tokio::test]
     async fn multiple_entries() {
         let db = new_catalog().await;
@@ -2052,6 +2385,7 @@ mod tests {
                         nullable: true,
                         arrow_type: DataType::Utf8,
                     }],
+                    partition_by: Vec::new(),
                 },
             })],
         )
@@ -2078,10 +2412,428 @@ mod tests {
                         nullable: true,
                         arrow_type: DataType::Utf8,
                     }],
+                    partition_by: Vec::new(),
+                },
+            })],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Get the internal columns for the table with the given name.
+    async fn table_columns(db: &DatabaseCatalog, name: &str) -> Vec<InternalColumnDefinition> {
+        let state = db.get_state().await.unwrap();
+        state
+            .entries
+            .values()
+            .find_map(|ent| match ent {
+                CatalogEntry::Table(ent) if ent.meta.name == name => ent.get_internal_columns(),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    async fn create_native_table(db: &DatabaseCatalog, name: &str) {
+        db.try_mutate_and_commit(
+            version(db).await,
+            vec![Mutation::CreateTable(CreateTable {
+                schema: DEFAULT_SCHEMA.to_string(),
+                name: name.to_string(),
+                if_not_exists: false,
+                or_replace: false,
+                options: TableOptionsInternal {
+                    columns: InternalColumnDefinition::from_tuples([
+                        ("mario", DataType::Int64, false),
+                        ("luigi", DataType::Utf8, true),
+                    ]),
+                    partition_by: Vec::new(),
                 },
             })],
         )
         .await
         .unwrap();
     }
+
+    fn alter_table(name: &str, operation: AlterTableOperation) -> Mutation {
+        Mutation::AlterTable(AlterTable {
+            schema: DEFAULT_SCHEMA.to_string(),
+            name: name.to_string(),
+            operation,
+        })
+    }
+
+    #[tokio::test]
+    async fn rename_column() {
+        let db = new_catalog().await;
+        create_native_table(&db, "peach").await;
+
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::RenameColumn {
+                    old_name: "luigi".to_string(),
+                    new_name: "yoshi".to_string(),
+                },
+            )],
+        )
+        .await
+        .unwrap();
+
+        let names: Vec<_> = table_columns(&db, "peach")
+            .await
+            .into_iter()
+            .map(|col| col.name)
+            .collect();
+        assert_eq!(vec!["mario", "yoshi"], names);
+
+        // Missing column.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::RenameColumn {
+                    old_name: "luigi".to_string(),
+                    new_name: "toad".to_string(),
+                },
+            )],
+        )
+        .await
+        .unwrap_err();
+
+        // Duplicate column.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::RenameColumn {
+                    old_name: "mario".to_string(),
+                    new_name: "yoshi".to_string(),
+                },
+            )],
+        )
+        .await
+        .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn add_column() {
+        let db = new_catalog().await;
+        create_native_table(&db, "peach").await;
+
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::AddColumn {
+                    name: "yoshi".to_string(),
+                    arrow_type: DataType::Float64,
+                },
+            )],
+        )
+        .await
+        .unwrap();
+
+        let columns = table_columns(&db, "peach").await;
+        let names: Vec<_> = columns.iter().map(|col| col.name.as_str()).collect();
+        assert_eq!(vec!["mario", "luigi", "yoshi"], names);
+        assert!(columns[2].nullable);
+        assert_eq!(DataType::Float64, columns[2].arrow_type);
+
+        // Duplicate column.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::AddColumn {
+                    name: "mario".to_string(),
+                    arrow_type: DataType::Utf8,
+                },
+            )],
+        )
+        .await
+        .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn widen_column() {
+        let db = new_catalog().await;
+        create_native_table(&db, "peach").await;
+
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![
+                alter_table(
+                    "peach",
+                    AlterTableOperation::AddColumn {
+                        name: "yoshi".to_string(),
+                        arrow_type: DataType::Int32,
+                    },
+                ),
+                alter_table(
+                    "peach",
+                    AlterTableOperation::WidenColumn {
+                        name: "yoshi".to_string(),
+                        arrow_type: DataType::Int64,
+                    },
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let columns = table_columns(&db, "peach").await;
+        assert_eq!(DataType::Int64, columns[2].arrow_type);
+
+        // Narrowing and incompatible changes.
+        for (name, arrow_type) in [("mario", DataType::Int32), ("luigi", DataType::Int64)] {
+            db.try_mutate_and_commit(
+                version(&db).await,
+                vec![alter_table(
+                    "peach",
+                    AlterTableOperation::WidenColumn {
+                        name: name.to_string(),
+                        arrow_type,
+                    },
+                )],
+            )
+            .await
+            .unwrap_err();
+        }
+    }
+
+    #[tokio::test]
+    async fn drop_column() {
+        let db = new_catalog().await;
+        create_native_table(&db, "peach").await;
+
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::DropColumn {
+                    name: "luigi".to_string(),
+                    if_exists: false,
+                },
+            )],
+        )
+        .await
+        .unwrap();
+
+        let names: Vec<_> = table_columns(&db, "peach")
+            .await
+            .into_iter()
+            .map(|col| col.name)
+            .collect();
+        assert_eq!(vec!["mario"], names);
+
+        // Missing column.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::DropColumn {
+                    name: "luigi".to_string(),
+                    if_exists: false,
+                },
+            )],
+        )
+        .await
+        .unwrap_err();
+
+        // Missing column with if exists.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::DropColumn {
+                    name: "luigi".to_string(),
+                    if_exists: true,
+                },
+            )],
+        )
+        .await
+        .unwrap();
+
+        // Can't drop the only column.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::DropColumn {
+                    name: "mario".to_string(),
+                    if_exists: false,
+                },
+            )],
+        )
+        .await
+        .unwrap_err();
+    }
+
+    /// Get the table entry with the given name.
+    async fn table_entry(db: &DatabaseCatalog, name: &str) -> TableEntry {
+        let state = db.get_state().await.unwrap();
+        state
+            .entries
+            .into_values()
+            .find_map(|ent| match ent {
+                CatalogEntry::Table(ent) if ent.meta.name == name => Some(ent),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn set_comment() {
+        let db = new_catalog().await;
+        create_native_table(&db, "peach").await;
+
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![
+                alter_table(
+                    "peach",
+                    AlterTableOperation::SetComment {
+                        column: None,
+                        comment: Some("princess".to_string()),
+                    },
+                ),
+                alter_table(
+                    "peach",
+                    AlterTableOperation::SetComment {
+                        column: Some("luigi".to_string()),
+                        comment: Some("green".to_string()),
+                    },
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let ent = table_entry(&db, "peach").await;
+        assert_eq!(Some("princess".to_string()), ent.comment);
+        assert_eq!(Some(&"green".to_string()), ent.column_comments.get("luigi"));
+
+        // Comment follows a renamed column.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::RenameColumn {
+                    old_name: "luigi".to_string(),
+                    new_name: "yoshi".to_string(),
+                },
+            )],
+        )
+        .await
+        .unwrap();
+
+        let ent = table_entry(&db, "peach").await;
+        assert_eq!(None, ent.column_comments.get("luigi"));
+        assert_eq!(Some(&"green".to_string()), ent.column_comments.get("yoshi"));
+
+        // Removing comments.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![
+                alter_table(
+                    "peach",
+                    AlterTableOperation::SetComment {
+                        column: None,
+                        comment: None,
+                    },
+                ),
+                alter_table(
+                    "peach",
+                    AlterTableOperation::SetComment {
+                        column: Some("yoshi".to_string()),
+                        comment: None,
+                    },
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let ent = table_entry(&db, "peach").await;
+        assert_eq!(None, ent.comment);
+        assert!(ent.column_comments.is_empty());
+
+        // Missing column.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::SetComment {
+                    column: Some("toad".to_string()),
+                    comment: Some("mushroom".to_string()),
+                },
+            )],
+        )
+        .await
+        .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn set_external_table_columns() {
+        let db = new_catalog().await;
+        create_native_table(&db, "peach").await;
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![
+                Mutation::CreateExternalTable(CreateExternalTable {
+                    schema: DEFAULT_SCHEMA.to_string(),
+                    name: "bowser".to_string(),
+                    options: TableOptionsDebug::default().into(),
+                    if_not_exists: false,
+                    or_replace: false,
+                    tunnel: None,
+                    columns: Some(InternalColumnDefinition::from_tuples([
+                        ("koopa", DataType::Int32, true),
+                        ("shell", DataType::Utf8, true),
+                    ])),
+                }),
+                alter_table(
+                    "bowser",
+                    AlterTableOperation::SetComment {
+                        column: Some("shell".to_string()),
+                        comment: Some("green".to_string()),
+                    },
+                ),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let columns = InternalColumnDefinition::from_tuples([
+            ("koopa", DataType::Int64, true),
+            ("troopa", DataType::Utf8, true),
+        ]);
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "bowser",
+                AlterTableOperation::SetColumns {
+                    columns: columns.clone(),
+                },
+            )],
+        )
+        .await
+        .unwrap();
+
+        let ent = table_entry(&db, "bowser").await;
+        assert_eq!(Some(columns.clone()), ent.columns);
+        assert!(ent.column_comments.is_empty());
+
+        // Native tables manage their own columns.
+        db.try_mutate_and_commit(
+            version(&db).await,
+            vec![alter_table(
+                "peach",
+                AlterTableOperation::SetColumns { columns },
+            )],
+        )
+        .await
+        .unwrap_err();
+    }
 }
@@ -1,4 +1,5 @@
 use catalog::errors::CatalogError;
+use datafusion::arrow::datatypes::DataType;
 use protogen::metastore::strategy::{ResolveErrorStrategy, RESOLVE_ERROR_STRATEGY_META};
 use protogen::metastore::types::catalog::CatalogEntry;
 
@@ -38,7 +39,7 @@ pub enum MetastoreError {
     #[error("Missing tunnel: {0}")]
     MissingTunnel(String),
 
-    #[error("Missing credentials: {0}")]
+    #[error("Credentials do not exist: '{0}'")]
     MissingCredentials(String),
 
     #[error("Missing schema: {0}")]
@@ -50,6 +51,31 @@ pub enum MetastoreError {
     #[error("Missing entry: {0}")]
     MissingEntry(u32),
 
+    #[error("Missing column '{column}' in table '{table}'")]
+    MissingColumn { table: String, column: String },
+
+    #[error("Altering columns is only supported for native tables: {0}")]
+    AlterColumnsNotSupported(String),
+
+    #[error("Cannot drop the only column in table '{0}'")]
+    DropLastColumn(String),
+
+    #[error("Cannot change column '{column}' from {from} to {to}, only widening is supported")]
+    InvalidColumnWidening {
+        column: String,
+        from: DataType,
+        to: DataType,
+    },
+
+    #[error("Cannot alter partition column '{0}'")]
+    AlterPartitionColumn(String),
+
+    #[error("Comments are only supported on tables: {0}")]
+    CommentNotSupported(String),
+
+    #[error("Refreshing the schema is only supported for external tables: {0}")]
+    RefreshNotSupported(String),
+
     #[error("Tunnel '{tunnel} not supported for {action}'")]
     TunnelNotSupportedForAction {
         tunnel: String,
@@ -2,12 +2,14 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use object_store::aws::{AmazonS3Builder, S3CopyIfNotExists};
-use object_store::azure::MicrosoftAzureBuilder;
+use object_store::azure::{AzureConfigKey, MicrosoftAzureBuilder};
 use object_store::gcp::GoogleCloudStorageBuilder;
 use object_store::local::LocalFileSystem;
 use object_store::memory::InMemory;
 use object_store::{Error as ObjectStoreError, ObjectStore};
 
+use crate::registry::ObjectStoreRegistry;
+
 /// Configuration options for various types of storage we support.
 #[derive(Debug, Clone, PartialEq)]
 pub enum StorageConfig {
@@ -24,7 +26,8 @@ pub enum StorageConfig {
     },
     Azure {
         account_name: String,
-        access_key: String,
+        access_key: Option<String>,
+        sas_token: Option<String>,
         container_name: Option<String>,
     },
     Local {
@@ -34,6 +37,19 @@ pub enum StorageConfig {
 }
 
 impl StorageConfig {
+    /// Get an object store for this config, reusing a store previously built
+    /// from an equal config if there is one.
+    ///
+    /// Only stores for remote storage are reused. Local and in-memory stores
+    /// are always newly created.
+    pub fn cached_object_store(&self) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
+        match self {
+            StorageConfig::Local { .. } | StorageConfig::Memory => self.new_object_store(),
+            _ => ObjectStoreRegistry::global()
+                .get_or_try_insert(format!("{self:?}"), || self.new_object_store()),
+        }
+    }
+
     /// Create a new object store using this config.
     pub fn new_object_store(&self) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
         Ok(match self {
@@ -44,16 +60,26 @@ impl StorageConfig {
                 endpoint,
                 bucket,
             } => {
-                let mut builder = AmazonS3Builder::new()
+                // Start from the environment so that any standard `AWS_*`
+                // variables (e.g. session tokens) are picked up, with the
+                // explicitly configured values taking precedence.
+                let mut builder = AmazonS3Builder::from_env()
                     .with_access_key_id(access_key_id)
-                    .with_secret_access_key(secret_access_key)
-                    .with_region(region.clone().unwrap_or_default());
+                    .with_secret_access_key(secret_access_key);
+
+                if let Some(region) = region {
+                    builder = builder.with_region(region);
+                }
 
                 if let Some(endpoint) = endpoint {
                     if endpoint.starts_with("http://") {
                         builder = builder.with_allow_http(true);
                     }
-                    builder = builder.with_endpoint(endpoint);
+                    // Custom endpoints (e.g. MinIO) generally only support
+                    // path-style requests.
+                    builder = builder
+                        .with_endpoint(endpoint)
+                        .with_virtual_hosted_style_request(false);
                     if endpoint.contains("r2.cloudflarestorage.com") {
                         // Ensure `ObjectStore::copy_if_not_exists` is enabled on the S3 client for
                         // Cloudflare R2 with the adequate header
@@ -86,11 +112,18 @@ impl StorageConfig {
             StorageConfig::Azure {
                 account_name,
                 access_key,
+                sas_token,
                 container_name,
             } => {
-                let mut builder = MicrosoftAzureBuilder::new()
-                    .with_account(account_name)
-                    .with_access_key(access_key);
+                let mut builder = MicrosoftAzureBuilder::new().with_account(account_name);
+
+                if let Some(access_key) = access_key {
+                    builder = builder.with_access_key(access_key);
+                }
+
+                if let Some(sas_token) = sas_token {
+                    builder = builder.with_config(AzureConfigKey::SasKey, sas_token);
+                }
 
                 if let Some(container_name) = container_name {
                     builder = builder.with_container_name(container_name);
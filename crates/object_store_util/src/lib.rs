@@ -1,4 +1,6 @@
 //! Utilities for the object store crate.
 pub mod conf;
+pub mod limit;
+pub mod registry;
 pub mod shared;
 pub mod temp;
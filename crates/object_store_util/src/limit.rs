@@ -0,0 +1,155 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use object_store::path::Path;
+use object_store::{
+    GetOptions,
+    GetResult,
+    GetResultPayload,
+    ListResult,
+    MultipartId,
+    ObjectMeta,
+    ObjectStore,
+    PutOptions,
+    PutResult,
+    Result,
+};
+use tokio::io::AsyncWrite;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits the number of concurrent GET (and HEAD) requests made against an
+/// object store.
+///
+/// Scans over many objects can otherwise open enough concurrent requests to
+/// get rate limited by the store. Requests beyond the limit wait for a
+/// running request to finish. A streamed GET counts against the limit until
+/// its payload is dropped.
+///
+/// All other requests are passed through unlimited.
+#[derive(Debug)]
+pub struct ConcurrencyLimitedStore {
+    inner: Arc<dyn ObjectStore>,
+    max_concurrent_gets: usize,
+    semaphore: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimitedStore {
+    pub fn new(inner: Arc<dyn ObjectStore>, max_concurrent_gets: usize) -> Self {
+        ConcurrencyLimitedStore {
+            inner,
+            max_concurrent_gets,
+            semaphore: Arc::new(Semaphore::new(max_concurrent_gets)),
+        }
+    }
+
+    async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("semaphore should never be closed")
+    }
+}
+
+impl std::fmt::Display for ConcurrencyLimitedStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ConcurrencyLimitedStore({}, {})",
+            self.max_concurrent_gets, self.inner
+        )
+    }
+}
+
+#[async_trait]
+impl ObjectStore for ConcurrencyLimitedStore {
+    async fn put(&self, location: &Path, bytes: Bytes) -> Result<PutResult> {
+        self.inner.put(location, bytes).await
+    }
+
+    async fn put_opts(&self, location: &Path, bytes: Bytes, opts: PutOptions) -> Result<PutResult> {
+        self.inner.put_opts(location, bytes, opts).await
+    }
+
+    async fn put_multipart(
+        &self,
+        location: &Path,
+    ) -> Result<(MultipartId, Box<dyn AsyncWrite + Unpin + Send>)> {
+        self.inner.put_multipart(location).await
+    }
+
+    async fn abort_multipart(&self, location: &Path, multipart_id: &MultipartId) -> Result<()> {
+        self.inner.abort_multipart(location, multipart_id).await
+    }
+
+    async fn get(&self, location: &Path) -> Result<GetResult> {
+        self.get_opts(location, GetOptions::default()).await
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        let permit = self.acquire().await;
+        let result = self.inner.get_opts(location, options).await?;
+
+        // Hold on to the permit until the payload is fully read.
+        let payload = match result.payload {
+            GetResultPayload::Stream(stream) => GetResultPayload::Stream(
+                stream
+                    .map(move |chunk| {
+                        let _permit = &permit;
+                        chunk
+                    })
+                    .boxed(),
+            ),
+            payload @ GetResultPayload::File(..) => payload,
+        };
+
+        Ok(GetResult { payload, ..result })
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> Result<Bytes> {
+        let _permit = self.acquire().await;
+        self.inner.get_range(location, range).await
+    }
+
+    async fn get_ranges(&self, location: &Path, ranges: &[Range<usize>]) -> Result<Vec<Bytes>> {
+        let _permit = self.acquire().await;
+        self.inner.get_ranges(location, ranges).await
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        let _permit = self.acquire().await;
+        self.inner.head(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        self.inner.delete(location).await
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
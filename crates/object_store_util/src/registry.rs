@@ -0,0 +1,103 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use moka::sync::Cache;
+use object_store::ObjectStore;
+use sha2::{Digest, Sha256};
+
+use crate::limit::ConcurrencyLimitedStore;
+
+/// Maximum number of stores kept in the registry. The least recently used
+/// stores are evicted past this.
+const MAX_STORES: u64 = 256;
+
+/// Stores that haven't been used for this long are evicted.
+const STORE_IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Memoizes built object stores.
+///
+/// Building a store for a remote bucket sets up a new connection pool and
+/// credential provider. Reusing stores built with the same configuration lets
+/// repeated accesses to a bucket share those instead.
+///
+/// Keys include credentials, so the registry only keeps hashes of them.
+pub struct ObjectStoreRegistry {
+    /// Stores keyed by the SHA-256 hash of their keys.
+    stores: Cache<[u8; 32], Arc<dyn ObjectStore>>,
+    /// Maximum number of concurrent GET requests per store, zero if
+    /// unlimited.
+    max_concurrent_gets: AtomicUsize,
+}
+
+impl Default for ObjectStoreRegistry {
+    fn default() -> Self {
+        ObjectStoreRegistry {
+            stores: Cache::builder()
+                .max_capacity(MAX_STORES)
+                .time_to_idle(STORE_IDLE_TIMEOUT)
+                .build(),
+            max_concurrent_gets: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl fmt::Debug for ObjectStoreRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectStoreRegistry")
+            .field("stores", &self.stores.entry_count())
+            .field("max_concurrent_gets", &self.max_concurrent_gets)
+            .finish()
+    }
+}
+
+impl ObjectStoreRegistry {
+    /// Get the registry shared by everything in this process.
+    pub fn global() -> &'static ObjectStoreRegistry {
+        static REGISTRY: OnceLock<ObjectStoreRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(ObjectStoreRegistry::default)
+    }
+
+    /// Set the maximum number of concurrent GET requests made against each
+    /// store built from now on.
+    ///
+    /// Stores that have already been registered keep their previous limit, so
+    /// this should be set on startup.
+    pub fn set_max_concurrent_gets(&self, max_concurrent_gets: Option<usize>) {
+        self.max_concurrent_gets
+            .store(max_concurrent_gets.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// Wrap a newly built store with the configured concurrency limit, if any.
+    pub fn limit(&self, store: Arc<dyn ObjectStore>) -> Arc<dyn ObjectStore> {
+        match self.max_concurrent_gets.load(Ordering::Relaxed) {
+            0 => store,
+            max => Arc::new(ConcurrencyLimitedStore::new(store, max)),
+        }
+    }
+
+    /// Get the store registered for `key`, building and registering one with
+    /// `build` if there isn't one yet. Built stores are wrapped with the
+    /// configured concurrency limit.
+    ///
+    /// The key must identify everything that affects the built store,
+    /// including credentials. Only a hash of the key is kept.
+    ///
+    /// Concurrent calls for the same key may each build a store, in which
+    /// case the last one built is kept.
+    pub fn get_or_try_insert<E>(
+        &self,
+        key: String,
+        build: impl FnOnce() -> Result<Arc<dyn ObjectStore>, E>,
+    ) -> Result<Arc<dyn ObjectStore>, E> {
+        let key: [u8; 32] = Sha256::digest(key.as_bytes()).into();
+        if let Some(store) = self.stores.get(&key) {
+            return Ok(store);
+        }
+
+        let store = self.limit(build()?);
+        self.stores.insert(key, store.clone());
+        Ok(store)
+    }
+}
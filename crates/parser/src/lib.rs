@@ -10,7 +10,7 @@ use sqlparser::ast::{self, ColumnDef, Ident, ObjectName};
 use sqlparser::dialect::GenericDialect;
 use sqlparser::keywords::Keyword;
 use sqlparser::parser::{Parser, ParserError, ParserOptions};
-use sqlparser::tokenizer::{Token, Tokenizer, Word};
+use sqlparser::tokenizer::{Token, Tokenizer, Whitespace, Word};
 
 use self::options::{OptionValue, StatementOptions};
 use crate::errors::{ParseError, Result};
@@ -168,7 +168,11 @@ impl fmt::Display for AlterDatabaseStmt {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AlterTableOperationExtension {
-    SetAccessMode { access_mode: Ident },
+    SetAccessMode {
+        access_mode: Ident,
+    },
+    /// Re-infer the schema of an external table from its source.
+    Refresh,
 }
 
 impl fmt::Display for AlterTableOperationExtension {
@@ -177,6 +181,7 @@ impl fmt::Display for AlterTableOperationExtension {
             Self::SetAccessMode { access_mode } => {
                 write!(f, "SET ACCESS_MODE TO {access_mode}")
             }
+            Self::Refresh => write!(f, "REFRESH"),
         }
     }
 }
@@ -412,6 +417,189 @@ impl fmt::Display for CopyToStmt {
     }
 }
 
+/// Load data from a file into a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyFromStmt {
+    /// Table to load the data into.
+    pub table: ObjectName,
+    /// Location of the file(s) to load.
+    pub source: Ident,
+    /// Optional format of the source file(s). Inferred from the file extension
+    /// if not provided.
+    pub format: Option<Ident>,
+    /// Optional credentials (for cloud storage).
+    pub credentials: Option<Ident>,
+    /// COPY FROM specific options.
+    pub options: StatementOptions,
+}
+
+impl fmt::Display for CopyFromStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "COPY {} FROM {}", self.table, self.source)?;
+        if let Some(format) = self.format.as_ref() {
+            write!(f, " FORMAT {format}")?;
+        }
+        if let Some(creds) = self.credentials.as_ref() {
+            write!(f, " CREDENTIALS {creds}")?;
+        }
+        if !self.options.is_empty() {
+            write!(f, " {}", self.options)?;
+        }
+        Ok(())
+    }
+}
+
+/// Overwrite the contents of a table with the results of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsertOverwriteStmt {
+    /// Table to overwrite.
+    pub table: ObjectName,
+    /// Only replace rows matching this predicate. The entire table is replaced
+    /// if omitted.
+    pub replace_where: Option<ast::Expr>,
+    /// Query producing the new rows.
+    pub source: Box<ast::Query>,
+}
+
+impl fmt::Display for InsertOverwriteStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "INSERT OVERWRITE {}", self.table)?;
+        if let Some(predicate) = &self.replace_where {
+            write!(f, " WHERE {predicate}")?;
+        }
+        write!(f, " {}", self.source)
+    }
+}
+
+/// Remove data files no longer referenced by a native table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VacuumStmt {
+    /// Table to vacuum.
+    pub table: ObjectName,
+    /// Optional retention period in hours. Files removed from the table more
+    /// recently than this are kept.
+    pub retain_hours: Option<u64>,
+    /// Only list the files that would be deleted.
+    pub dry_run: bool,
+}
+
+impl fmt::Display for VacuumStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VACUUM {}", self.table)?;
+        if let Some(hours) = self.retain_hours {
+            write!(f, " RETAIN {hours} HOURS")?;
+        }
+        if self.dry_run {
+            write!(f, " DRY RUN")?;
+        }
+        Ok(())
+    }
+}
+
+/// Remove all rows from one or more native tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruncateStmt {
+    /// Tables to truncate.
+    pub tables: Vec<ObjectName>,
+}
+
+impl fmt::Display for TruncateStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TRUNCATE ")?;
+        let mut sep = "";
+        for table in self.tables.iter() {
+            write!(f, "{sep}{table}")?;
+            sep = ", ";
+        }
+        Ok(())
+    }
+}
+
+/// Cancel the query running on another connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillStmt {
+    /// Id of the connection whose query should be canceled.
+    pub connection_id: String,
+}
+
+impl fmt::Display for KillStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KILL '{}'", self.connection_id)
+    }
+}
+
+/// Declare a cursor over the results of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclareCursorStmt {
+    pub name: Ident,
+    pub query: Box<ast::Query>,
+}
+
+impl fmt::Display for DeclareCursorStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DECLARE {} CURSOR FOR {}", self.name, self.query)
+    }
+}
+
+/// Fetch the next rows from a cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchStmt {
+    pub name: Ident,
+    /// Number of rows to fetch, or `None` to fetch all remaining rows.
+    pub count: Option<u64>,
+}
+
+impl fmt::Display for FetchStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.count {
+            Some(count) => write!(f, "FETCH {count} FROM {}", self.name),
+            None => write!(f, "FETCH ALL FROM {}", self.name),
+        }
+    }
+}
+
+/// Close a cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloseCursorStmt {
+    /// Cursor to close, or `None` to close all cursors.
+    pub name: Option<Ident>,
+}
+
+impl fmt::Display for CloseCursorStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "CLOSE {name}"),
+            None => write!(f, "CLOSE ALL"),
+        }
+    }
+}
+
+/// Compact the small data files of a native table into larger ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimizeStmt {
+    /// Table to optimize.
+    pub table: ObjectName,
+    /// Columns to Z-order the data by. Empty if the files should only be
+    /// compacted.
+    pub zorder_by: Vec<Ident>,
+}
+
+impl fmt::Display for OptimizeStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OPTIMIZE {}", self.table)?;
+        if !self.zorder_by.is_empty() {
+            write!(f, " ZORDER BY (")?;
+            let mut sep = "";
+            for col in self.zorder_by.iter() {
+                write!(f, "{sep}{col}")?;
+                sep = ", ";
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StatementWithExtensions {
     /// Statement parsed by `sqlparser`.
@@ -438,6 +626,24 @@ pub enum StatementWithExtensions {
     DropCredentials(DropCredentialsStmt),
     /// Copy To extension.
     CopyTo(CopyToStmt),
+    /// Copy from extension.
+    CopyFrom(CopyFromStmt),
+    /// Insert overwrite extension.
+    InsertOverwrite(InsertOverwriteStmt),
+    /// Vacuum extension.
+    Vacuum(VacuumStmt),
+    /// Optimize extension.
+    Optimize(OptimizeStmt),
+    /// Truncate extension.
+    Truncate(TruncateStmt),
+    /// Kill (cancel) extension.
+    Kill(KillStmt),
+    /// Declare cursor extension.
+    DeclareCursor(DeclareCursorStmt),
+    /// Fetch from cursor extension.
+    Fetch(FetchStmt),
+    /// Close cursor extension.
+    CloseCursor(CloseCursorStmt),
 }
 
 impl fmt::Display for StatementWithExtensions {
@@ -455,6 +661,15 @@ impl fmt::Display for StatementWithExtensions {
             StatementWithExtensions::CreateCredentials(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::DropCredentials(stmt) => write!(f, "{}", stmt),
             StatementWithExtensions::CopyTo(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::CopyFrom(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::InsertOverwrite(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Vacuum(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Optimize(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Truncate(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Kill(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::DeclareCursor(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::Fetch(stmt) => write!(f, "{}", stmt),
+            StatementWithExtensions::CloseCursor(stmt) => write!(f, "{}", stmt),
         }
     }
 }
@@ -475,7 +690,7 @@ impl GlareDbParser<'_> {
     const SQL_DIALECT: &'static GenericDialect = &GenericDialect {};
 
     pub fn new(mut sql: &str, dialect: Dialect) -> Result<GlareDbParser<'_>, ParserError> {
-        let tokens = Tokenizer::new(Self::SQL_DIALECT, sql).tokenize()?;
+        let tokens = rewrite_time_travel(Tokenizer::new(Self::SQL_DIALECT, sql).tokenize()?);
         let mut parser = Parser::new(Self::SQL_DIALECT)
             .with_options(ParserOptions {
                 trailing_commas: true,
@@ -500,6 +715,86 @@ impl GlareDbParser<'_> {
     }
 }
 
+/// Rewrite `<table> VERSION AS OF <n>` and `<table> TIMESTAMP AS OF '<ts>'`
+/// into the table hint `WITH ("version" = <n>)` (or `"timestamp"`).
+///
+/// sqlparser only parses time travel clauses for a few dialects, but table
+/// hints are accepted everywhere. The planner reads the version back out of
+/// the hints. Hints must come after the table alias, so an explicit alias
+/// following the clause (`t VERSION AS OF 3 AS t3`) is moved in front of it.
+fn rewrite_time_travel(tokens: Vec<Token>) -> Vec<Token> {
+    let next_token =
+        |idx: usize| (idx..tokens.len()).find(|&i| !matches!(tokens[i], Token::Whitespace(_)));
+    let is_word = |idx: Option<usize>, word: &str| match idx.map(|i| &tokens[i]) {
+        Some(Token::Word(w)) => w.quote_style.is_none() && w.value.eq_ignore_ascii_case(word),
+        _ => false,
+    };
+
+    let mut out: Vec<Token> = Vec::with_capacity(tokens.len());
+    let mut idx = 0;
+    while idx < tokens.len() {
+        // The clause has to directly follow a table name (or alias).
+        let follows_name = matches!(
+            out.iter()
+                .rev()
+                .find(|t| !matches!(t, Token::Whitespace(_))),
+            Some(Token::Word(_))
+        );
+        let as_idx = next_token(idx + 1);
+        let of_idx = as_idx.and_then(|i| next_token(i + 1));
+        let lit_idx = of_idx.and_then(|i| next_token(i + 1));
+        let lit = lit_idx.map(|i| &tokens[i]);
+
+        let hint = if !follows_name || !is_word(as_idx, "AS") || !is_word(of_idx, "OF") {
+            None
+        } else if is_word(Some(idx), "VERSION") && matches!(lit, Some(Token::Number(..))) {
+            Some("version")
+        } else if is_word(Some(idx), "TIMESTAMP")
+            && matches!(lit, Some(Token::SingleQuotedString(_)))
+        {
+            Some("timestamp")
+        } else {
+            None
+        };
+
+        let (hint, lit_idx) = match (hint, lit_idx) {
+            (Some(hint), Some(lit_idx)) => (hint, lit_idx),
+            _ => {
+                out.push(tokens[idx].clone());
+                idx += 1;
+                continue;
+            }
+        };
+        idx = lit_idx + 1;
+
+        let alias_as_idx = next_token(idx);
+        let alias_idx = alias_as_idx.and_then(|i| next_token(i + 1));
+        if is_word(alias_as_idx, "AS")
+            && matches!(alias_idx.map(|i| &tokens[i]), Some(Token::Word(_)))
+        {
+            let alias_idx = alias_idx.unwrap();
+            out.extend([
+                Token::make_keyword("AS"),
+                Token::Whitespace(Whitespace::Space),
+                tokens[alias_idx].clone(),
+                Token::Whitespace(Whitespace::Space),
+            ]);
+            idx = alias_idx + 1;
+        }
+
+        out.extend([
+            Token::make_keyword("WITH"),
+            Token::Whitespace(Whitespace::Space),
+            Token::LParen,
+            Token::make_word(hint, Some('"')),
+            Token::Eq,
+            tokens[lit_idx].clone(),
+            Token::RParen,
+        ]);
+    }
+    out
+}
+
 impl<'a> GlareDbParser<'a> {
     pub fn parse_sql(sql: &str) -> Result<VecDeque<StatementWithExtensions>, ParserError> {
         Self::parse(sql, Dialect::Sql)
@@ -557,6 +852,39 @@ impl<'a> GlareDbParser<'a> {
                     self.parser.next_token();
                     self.parse_copy()
                 }
+                Keyword::TRUNCATE => {
+                    self.parser.next_token();
+                    self.parse_truncate()
+                }
+                Keyword::INSERT
+                    if matches!(
+                        self.parser.peek_nth_token(1).token,
+                        Token::Word(w) if w.keyword == Keyword::OVERWRITE
+                    ) =>
+                {
+                    self.parser.next_token();
+                    self.parser.next_token();
+                    self.parse_insert_overwrite()
+                }
+                Keyword::DECLARE => {
+                    self.parser.next_token();
+                    self.parse_declare_cursor()
+                }
+                Keyword::FETCH => {
+                    self.parser.next_token();
+                    self.parse_fetch()
+                }
+                Keyword::CLOSE => {
+                    self.parser.next_token();
+                    self.parse_close_cursor()
+                }
+                _ if self.consume_token(&Token::make_keyword("VACUUM")) => self.parse_vacuum(),
+                _ if self.consume_token(&Token::make_keyword("OPTIMIZE")) => self.parse_optimize(),
+                _ if self.consume_token(&Token::make_keyword("KILL"))
+                    || self.consume_token(&Token::make_keyword("CANCEL")) =>
+                {
+                    self.parse_kill()
+                }
                 _ => Ok(StatementWithExtensions::Statement(
                     self.parser.parse_statement()?,
                 )),
@@ -593,6 +921,14 @@ impl<'a> GlareDbParser<'a> {
         } else if self.parser.parse_keyword(Keyword::CREDENTIALS) {
             // CREATE CREDENTIALS ...
             self.parse_create_credentials(true, or_replace)
+        } else if self.parser.parse_keyword(Keyword::TABLE) {
+            // CREATE TABLE ...
+            self.parser.prev_token();
+            if or_replace {
+                self.parser.prev_token();
+                self.parser.prev_token();
+            }
+            self.parse_create_table()
         } else {
             // Fall back to underlying parser.
 
@@ -608,6 +944,89 @@ impl<'a> GlareDbParser<'a> {
         }
     }
 
+    /// Parse a CREATE TABLE statement with an optional `PARTITIONED BY (<col>,
+    /// ...)` clause.
+    ///
+    /// The underlying parser only accepts hive style partition columns which
+    /// include a data type, so the clause is removed from the statement's tokens
+    /// before handing them off and added back to the parsed statement.
+    fn parse_create_table(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let mut tokens = Vec::new();
+        let mut partition_by = None;
+        let mut depth = 0;
+        loop {
+            let tok = self.parser.peek_token();
+            match &tok.token {
+                Token::EOF => break,
+                Token::SemiColon if depth == 0 => break,
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                Token::Word(w) if depth == 0 && w.keyword == Keyword::PARTITIONED => {
+                    if let Some(cols) = self.parser.maybe_parse(|parser| {
+                        parser.expect_keywords(&[Keyword::PARTITIONED, Keyword::BY])?;
+                        parser.expect_token(&Token::LParen)?;
+                        let cols = parser
+                            .parse_comma_separated(|parser| parser.parse_identifier(false))?;
+                        parser.expect_token(&Token::RParen)?;
+                        Ok(cols)
+                    }) {
+                        if partition_by.is_some() {
+                            return Err(ParserError::ParserError(
+                                "PARTITIONED BY specified more than once".to_string(),
+                            ));
+                        }
+                        partition_by = Some(cols);
+                        continue;
+                    }
+                }
+                _ => (),
+            }
+            tokens.push(self.parser.next_token());
+        }
+
+        let mut parser = Parser::new(Self::SQL_DIALECT)
+            .with_options(ParserOptions {
+                trailing_commas: true,
+                ..Default::default()
+            })
+            .with_tokens_with_locations(tokens);
+        let mut stmt = parser.parse_create()?;
+        if parser.peek_token() != Token::EOF {
+            return self.expected("end of statement", parser.peek_token().token);
+        }
+
+        if let Some(cols) = partition_by {
+            for col in cols.iter() {
+                validate_ident(col)?;
+            }
+            match &mut stmt {
+                ast::Statement::CreateTable {
+                    hive_distribution: hive_distribution @ ast::HiveDistributionStyle::NONE,
+                    ..
+                } => {
+                    *hive_distribution = ast::HiveDistributionStyle::PARTITIONED {
+                        columns: cols
+                            .into_iter()
+                            .map(|name| ColumnDef {
+                                name,
+                                data_type: ast::DataType::Unspecified,
+                                collation: None,
+                                options: Vec::new(),
+                            })
+                            .collect(),
+                    };
+                }
+                _ => {
+                    return Err(ParserError::ParserError(
+                        "PARTITIONED BY specified more than once".to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(StatementWithExtensions::Statement(stmt))
+    }
+
     /// Parse a SQL ALTER statement
     fn parse_alter(&mut self) -> Result<StatementWithExtensions, ParserError> {
         if self.parser.parse_keyword(Keyword::DATABASE) {
@@ -638,6 +1057,9 @@ impl<'a> GlareDbParser<'a> {
             CopyToSource::Query(query)
         } else {
             let table_name = self.parser.parse_object_name(false)?;
+            if self.parser.parse_keyword(Keyword::FROM) {
+                return self.parse_copy_from(table_name);
+            }
             CopyToSource::Table(table_name)
         };
 
@@ -663,6 +1085,189 @@ impl<'a> GlareDbParser<'a> {
         }))
     }
 
+    /// Parse the remainder of a COPY FROM statement, starting after `FROM`.
+    fn parse_copy_from(
+        &mut self,
+        table: ObjectName,
+    ) -> Result<StatementWithExtensions, ParserError> {
+        validate_object_name(&table)?;
+
+        // FROM 'source'
+        let source = self.parser.parse_identifier(false)?;
+
+        // [FORMAT ..]
+        let format = self.parse_data_format()?;
+
+        // [CREDENTIALS ..]
+        let credentials = self.parse_connection_credentials()?;
+
+        // OPTIONS (..)
+        let options = self.parse_options()?;
+
+        Ok(StatementWithExtensions::CopyFrom(CopyFromStmt {
+            table,
+            source,
+            format,
+            credentials,
+            options,
+        }))
+    }
+
+    /// Parse the remainder of an INSERT OVERWRITE statement.
+    ///
+    /// INSERT OVERWRITE [TABLE] <table> [WHERE <predicate>] <query>
+    fn parse_insert_overwrite(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+        let table = self.parser.parse_object_name(false)?;
+        validate_object_name(&table)?;
+
+        let replace_where = if self.parser.parse_keyword(Keyword::WHERE) {
+            Some(self.parser.parse_expr()?)
+        } else {
+            None
+        };
+
+        let source = Box::new(self.parser.parse_query()?);
+
+        Ok(StatementWithExtensions::InsertOverwrite(
+            InsertOverwriteStmt {
+                table,
+                replace_where,
+                source,
+            },
+        ))
+    }
+
+    fn parse_vacuum(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let table = self.parser.parse_object_name(false)?;
+        validate_object_name(&table)?;
+
+        // [RETAIN <n> HOURS]
+        let retain_hours = if self.consume_token(&Token::make_keyword("RETAIN")) {
+            let hours = self.parser.parse_literal_uint()?;
+            self.expect_token(&Token::make_keyword("HOURS"))?;
+            Some(hours)
+        } else {
+            None
+        };
+
+        // [DRY RUN]
+        let dry_run = if self.consume_token(&Token::make_keyword("DRY")) {
+            self.expect_token(&Token::make_keyword("RUN"))?;
+            true
+        } else {
+            false
+        };
+
+        Ok(StatementWithExtensions::Vacuum(VacuumStmt {
+            table,
+            retain_hours,
+            dry_run,
+        }))
+    }
+
+    fn parse_optimize(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        let table = self.parser.parse_object_name(false)?;
+        validate_object_name(&table)?;
+
+        // [ZORDER BY (<col>, ...)]
+        let zorder_by = if self.consume_token(&Token::make_keyword("ZORDER")) {
+            self.parser.expect_keyword(Keyword::BY)?;
+            self.parser.expect_token(&Token::LParen)?;
+            let cols = self
+                .parser
+                .parse_comma_separated(|parser| parser.parse_identifier(false))?;
+            self.parser.expect_token(&Token::RParen)?;
+            for col in cols.iter() {
+                validate_ident(col)?;
+            }
+            cols
+        } else {
+            Vec::new()
+        };
+
+        Ok(StatementWithExtensions::Optimize(OptimizeStmt {
+            table,
+            zorder_by,
+        }))
+    }
+
+    fn parse_truncate(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        // TRUNCATE [TABLE] <table>, ...
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+        let tables = self
+            .parser
+            .parse_comma_separated(|parser| parser.parse_object_name(false))?;
+        for table in tables.iter() {
+            validate_object_name(table)?;
+        }
+
+        Ok(StatementWithExtensions::Truncate(TruncateStmt { tables }))
+    }
+
+    fn parse_kill(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        // KILL '<connection_id>'
+        // CANCEL '<connection_id>'
+        let connection_id = self.parser.parse_literal_string()?;
+        Ok(StatementWithExtensions::Kill(KillStmt { connection_id }))
+    }
+
+    fn parse_declare_cursor(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        // DECLARE <name> [NO SCROLL] CURSOR [WITHOUT HOLD] FOR <query>
+        let name = self.parser.parse_identifier(false)?;
+
+        // Only forward-only cursors that close at the end of the transaction
+        // are supported, which is what you get without these options.
+        let _ = self.parser.parse_keywords(&[Keyword::NO, Keyword::SCROLL]);
+        self.parser.expect_keyword(Keyword::CURSOR)?;
+        let _ = self
+            .parser
+            .parse_keywords(&[Keyword::WITHOUT, Keyword::HOLD]);
+        self.parser.expect_keyword(Keyword::FOR)?;
+
+        let query = self.parser.parse_query()?;
+        Ok(StatementWithExtensions::DeclareCursor(DeclareCursorStmt {
+            name,
+            query,
+        }))
+    }
+
+    fn parse_fetch(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        // FETCH [NEXT | ALL | <count> | FORWARD [ALL | <count>]] [FROM | IN] <name>
+        let count = if self.parser.parse_keyword(Keyword::NEXT) {
+            Some(1)
+        } else {
+            let _ = self.parser.parse_keyword(Keyword::FORWARD);
+            if self.parser.parse_keyword(Keyword::ALL) {
+                None
+            } else if matches!(self.parser.peek_token().token, Token::Number(_, _)) {
+                Some(self.parser.parse_literal_uint()?)
+            } else {
+                Some(1)
+            }
+        };
+
+        let _ = self
+            .parser
+            .parse_one_of_keywords(&[Keyword::FROM, Keyword::IN]);
+        let name = self.parser.parse_identifier(false)?;
+
+        Ok(StatementWithExtensions::Fetch(FetchStmt { name, count }))
+    }
+
+    fn parse_close_cursor(&mut self) -> Result<StatementWithExtensions, ParserError> {
+        // CLOSE <name>
+        // CLOSE ALL
+        let name = if self.parser.parse_keyword(Keyword::ALL) {
+            None
+        } else {
+            Some(self.parser.parse_identifier(false)?)
+        };
+        Ok(StatementWithExtensions::CloseCursor(CloseCursorStmt {
+            name,
+        }))
+    }
+
     /// Report unexpected token.
     fn expected<T>(&self, expected: &str, found: Token) -> Result<T, ParserError> {
         Err(ParserError::ParserError(format!(
@@ -1078,6 +1683,8 @@ impl<'a> GlareDbParser<'a> {
 
             let access_mode = self.parser.parse_identifier(false)?;
             AlterTableOperationExtension::SetAccessMode { access_mode }
+        } else if self.consume_token(&Token::make_keyword("REFRESH")) {
+            AlterTableOperationExtension::Refresh
         } else {
             let operations = self
                 .parser
@@ -1393,9 +2000,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn time_travel_rewritten_to_hints() {
+        let test_cases = [
+            (
+                "SELECT * FROM t VERSION AS OF 3",
+                "SELECT * FROM t WITH (\"version\" = 3)",
+            ),
+            (
+                "SELECT * FROM t TIMESTAMP AS OF '2024-01-01' AS t1 WHERE a = 1",
+                "SELECT * FROM t AS t1 WITH (\"timestamp\" = '2024-01-01') WHERE a = 1",
+            ),
+            (
+                "SELECT * FROM t AS t1 VERSION AS OF 1",
+                "SELECT * FROM t AS t1 WITH (\"version\" = 1)",
+            ),
+            // Not a time travel clause.
+            ("SELECT version AS of FROM t", "SELECT version AS of FROM t"),
+        ];
+
+        for (sql, expected) in test_cases {
+            let stmt = GlareDbParser::parse_sql(sql).unwrap().pop_front().unwrap();
+            assert_eq!(expected, stmt.to_string().as_str());
+        }
+    }
+
     #[test]
     fn alter_table_extension_roundtrips() {
-        let test_cases = ["ALTER TABLE my_db SET ACCESS_MODE TO readonly"];
+        let test_cases = [
+            "ALTER TABLE my_db SET ACCESS_MODE TO readonly",
+            "ALTER TABLE my_db REFRESH",
+        ];
 
         for test_case in test_cases {
             let stmt = GlareDbParser::parse_sql(test_case)
@@ -1429,6 +2064,163 @@ mod tests {
         }
     }
 
+    #[test]
+    fn copy_from_roundtrips() {
+        let test_cases = [
+            "COPY table FROM 's3://bucket/data.csv'",
+            "COPY table FROM './data.parquet' FORMAT parquet",
+            "COPY table FROM 's3://bucket/data.csv' CREDENTIALS aws_creds",
+            "COPY table FROM './data.csv' FORMAT csv OPTIONS (delimiter = ';', has_header = FALSE)",
+        ];
+
+        for test_case in test_cases {
+            let stmt = GlareDbParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert!(matches!(stmt, StatementWithExtensions::CopyFrom(_)));
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn insert_overwrite_roundtrips() {
+        let test_cases = [
+            "INSERT OVERWRITE t1 SELECT * FROM t2",
+            "INSERT OVERWRITE t1 WHERE a = 1 SELECT * FROM t2 WHERE a = 1",
+            "INSERT OVERWRITE public.t1 WHERE a > 1 AND b = 'x' VALUES (2, 'x')",
+        ];
+
+        for test_case in test_cases {
+            let stmt = GlareDbParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert!(matches!(stmt, StatementWithExtensions::InsertOverwrite(_)));
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn vacuum_roundtrips() {
+        let test_cases = [
+            "VACUUM t1",
+            "VACUUM public.t1",
+            "VACUUM t1 RETAIN 24 HOURS",
+            "VACUUM t1 DRY RUN",
+            "VACUUM t1 RETAIN 0 HOURS DRY RUN",
+        ];
+
+        for test_case in test_cases {
+            let stmt = GlareDbParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn create_table_partitioned_by_roundtrips() {
+        let test_cases = [
+            "CREATE TABLE t1 (a INT, b TEXT) PARTITIONED BY (a)",
+            "CREATE TABLE t1 (a INT, b TEXT, c INT) PARTITIONED BY (a, c)",
+            "CREATE OR REPLACE TABLE t1 (a INT) PARTITIONED BY (a)",
+            "CREATE TABLE t1 PARTITIONED BY (a) AS SELECT 1 AS a",
+        ];
+
+        for test_case in test_cases {
+            let stmt = GlareDbParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn optimize_roundtrips() {
+        let test_cases = [
+            "OPTIMIZE t1",
+            "OPTIMIZE public.t1",
+            "OPTIMIZE t1 ZORDER BY (a)",
+            "OPTIMIZE t1 ZORDER BY (a, b)",
+        ];
+
+        for test_case in test_cases {
+            let stmt = GlareDbParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+    }
+
+    #[test]
+    fn truncate_roundtrips() {
+        let test_cases = ["TRUNCATE t1", "TRUNCATE public.t1", "TRUNCATE t1, t2"];
+
+        for test_case in test_cases {
+            let stmt = GlareDbParser::parse_sql(test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert_eq!(test_case, stmt.to_string().as_str());
+        }
+
+        let stmt = GlareDbParser::parse_sql("TRUNCATE TABLE t1")
+            .unwrap()
+            .pop_front()
+            .unwrap();
+        assert_eq!("TRUNCATE t1", stmt.to_string().as_str());
+    }
+
+    #[test]
+    fn kill_roundtrips() {
+        let id = "3b241101-e2bb-4255-8caf-4136c566a962";
+        for test_case in [format!("KILL '{id}'"), format!("CANCEL '{id}'")] {
+            let stmt = GlareDbParser::parse_sql(&test_case)
+                .unwrap()
+                .pop_front()
+                .unwrap();
+            assert!(matches!(stmt, StatementWithExtensions::Kill(_)));
+            assert_eq!(format!("KILL '{id}'"), stmt.to_string());
+        }
+
+        GlareDbParser::parse_sql("KILL 123").unwrap_err();
+    }
+
+    #[test]
+    fn cursor_roundtrips() {
+        let test_cases = [
+            (
+                "DECLARE c CURSOR FOR SELECT 1",
+                "DECLARE c CURSOR FOR SELECT 1",
+            ),
+            (
+                "DECLARE c NO SCROLL CURSOR WITHOUT HOLD FOR SELECT * FROM t",
+                "DECLARE c CURSOR FOR SELECT * FROM t",
+            ),
+            ("FETCH c", "FETCH 1 FROM c"),
+            ("FETCH NEXT FROM c", "FETCH 1 FROM c"),
+            ("FETCH 10 FROM c", "FETCH 10 FROM c"),
+            ("FETCH FORWARD 10 IN c", "FETCH 10 FROM c"),
+            ("FETCH ALL FROM c", "FETCH ALL FROM c"),
+            ("FETCH FORWARD ALL c", "FETCH ALL FROM c"),
+            ("CLOSE c", "CLOSE c"),
+            ("CLOSE ALL", "CLOSE ALL"),
+        ];
+
+        for (sql, expected) in test_cases {
+            let stmt = GlareDbParser::parse_sql(sql).unwrap().pop_front().unwrap();
+            assert_eq!(expected, stmt.to_string(), "{sql}");
+        }
+
+        GlareDbParser::parse_sql("DECLARE c SCROLL CURSOR FOR SELECT 1").unwrap_err();
+        GlareDbParser::parse_sql("DECLARE c CURSOR WITH HOLD FOR SELECT 1").unwrap_err();
+        GlareDbParser::parse_sql("FETCH PRIOR FROM c").unwrap_err();
+    }
+
     #[test]
     fn options_parse() {
         let mut options = BTreeMap::new();
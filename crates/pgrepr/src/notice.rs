@@ -14,11 +14,27 @@ pub enum SqlState {
     // Class 01 — Warning
     Warning,
 
+    // Class 08 — Connection Exception
+    ProtocolViolation,
+
     // Class 0A — Feature Not Supported
     FeatureNotSupported,
 
+    // Class 25 — Invalid Transaction State
+    ActiveSqlTransaction,
+    NoActiveSqlTransaction,
+    InFailedSqlTransaction,
+
     // Class 42 — Syntax Error or Access Rule Violation
     SyntaxError,
+    DatatypeMismatch,
+    IndeterminateDatatype,
+
+    // Class 53 — Insufficient Resources
+    TooManyConnections,
+
+    // Class 57 — Operator Intervention
+    IdleSessionTimeout,
 
     // Class XX — Internal Error
     InternalError,
@@ -29,8 +45,16 @@ impl SqlState {
         match self {
             SqlState::Successful => "00000",
             SqlState::Warning => "01000",
+            SqlState::ProtocolViolation => "08P01",
             SqlState::FeatureNotSupported => "0A000",
+            SqlState::ActiveSqlTransaction => "25001",
+            SqlState::NoActiveSqlTransaction => "25P01",
+            SqlState::InFailedSqlTransaction => "25P02",
             SqlState::SyntaxError => "42601",
+            SqlState::DatatypeMismatch => "42804",
+            SqlState::IndeterminateDatatype => "42P18",
+            SqlState::TooManyConnections => "53300",
+            SqlState::IdleSessionTimeout => "57P05",
             SqlState::InternalError => "XX000",
         }
     }
@@ -30,3 +30,19 @@ pub fn arrow_to_pg_type(df_type: &ArrowType, type_hint: Option<PgType>) -> PgTyp
         _ => return PgType::TEXT,
     })
 }
+
+/// Returns the arrow datatype a value of the given postgres type decodes
+/// into, or `None` if we don't know how to decode values of that type.
+pub fn pg_to_arrow_type(pg_type: &PgType) -> Option<ArrowType> {
+    Some(match pg_type {
+        &PgType::BOOL => ArrowType::Boolean,
+        &PgType::INT2 => ArrowType::Int16,
+        &PgType::INT4 => ArrowType::Int32,
+        &PgType::INT8 => ArrowType::Int64,
+        &PgType::FLOAT4 => ArrowType::Float32,
+        &PgType::FLOAT8 => ArrowType::Float64,
+        &PgType::TEXT | &PgType::VARCHAR | &PgType::BPCHAR | &PgType::NAME => ArrowType::Utf8,
+        &PgType::BYTEA => ArrowType::Binary,
+        _ => return None,
+    })
+}
@@ -1,8 +1,11 @@
 use std::collections::{HashMap, VecDeque};
 use std::ops::DerefMut;
 use std::sync::Arc;
+use std::time::Duration;
 
+use datafusion::arrow::array::{ArrayRef, StringArray};
 use datafusion::arrow::datatypes::DataType;
+use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::physical_plan::SendableRecordBatchStream;
 use datafusion::scalar::ScalarValue;
 use datafusion::variable::VarType;
@@ -13,10 +16,10 @@ use pgrepr::format::Format;
 use pgrepr::scalar::Scalar;
 use sqlexec::context::local::{OutputFields, Portal, PreparedStatement};
 use sqlexec::engine::{Engine, SessionStorageConfig};
-use sqlexec::session::{ExecutionResult, Session};
+use sqlexec::session::{ExecutionResult, Session, TransactionState};
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio_postgres::types::Type as PgType;
-use tracing::{debug, debug_span, warn, Instrument};
+use tracing::{debug, debug_span, info, warn, Instrument};
 use uuid::Uuid;
 
 use crate::auth::{LocalAuthenticator, PasswordMode};
@@ -51,6 +54,8 @@ pub struct ProtocolHandlerConfig {
     /// If the server should be configured for integration tests. This is only
     /// applicable for local databases.
     pub integration_testing: bool,
+    /// Close connections that haven't sent a message within this duration.
+    pub idle_timeout: Option<Duration>,
 }
 
 /// A wrapper around a SQL engine that implements the Postgres frontend/backend
@@ -66,6 +71,47 @@ impl ProtocolHandler {
     }
 
     pub async fn handle_connection<C>(&self, id: Uuid, conn: C) -> Result<()>
+    where
+        C: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (conn, startup) = self.read_startup(conn).await?;
+        match startup {
+            StartupMessage::StartupRequest { params, .. } => self.begin(id, conn, params).await,
+            StartupMessage::CancelRequest { .. } => self.cancel(conn).await,
+            StartupMessage::SSLRequest { .. } => {
+                unreachable!("ssl requests handled during startup")
+            }
+        }
+    }
+
+    /// Refuse a connection because the server can't accept any more clients.
+    ///
+    /// The client is sent a fatal error once it's sent its startup message.
+    /// Cancel requests are still handled.
+    pub async fn refuse_too_many_clients<C>(&self, conn: C) -> Result<()>
+    where
+        C: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (conn, startup) = self.read_startup(conn).await?;
+        match startup {
+            StartupMessage::StartupRequest { .. } => {
+                let mut framed = FramedConn::new(conn);
+                let resp = ErrorResponse::fatal(
+                    pgrepr::notice::SqlState::TooManyConnections,
+                    "sorry, too many clients already",
+                );
+                framed.send(resp.into()).await
+            }
+            StartupMessage::CancelRequest { .. } => self.cancel(conn).await,
+            StartupMessage::SSLRequest { .. } => {
+                unreachable!("ssl requests handled during startup")
+            }
+        }
+    }
+
+    /// Read startup messages until one that isn't an SSL request is
+    /// received, negotiating encryption along the way.
+    async fn read_startup<C>(&self, conn: C) -> Result<(Connection<C>, StartupMessage)>
     where
         C: AsyncRead + AsyncWrite + Unpin,
     {
@@ -75,10 +121,6 @@ impl ProtocolHandler {
             debug!(?startup, "received startup message (local)");
 
             match startup {
-                StartupMessage::StartupRequest { params, .. } => {
-                    self.begin(id, conn, params).await?;
-                    return Ok(());
-                }
                 StartupMessage::SSLRequest { .. } => {
                     conn = match (conn, &self.conf.ssl_conf) {
                         (Connection::Unencrypted(mut conn), Some(conf)) => {
@@ -97,10 +139,7 @@ impl ProtocolHandler {
                         }
                     }
                 }
-                StartupMessage::CancelRequest { .. } => {
-                    self.cancel(conn).await?;
-                    return Ok(());
-                }
+                startup => return Ok((conn, startup)),
             }
         }
     }
@@ -307,7 +346,7 @@ impl ProtocolHandler {
             framed.send(msg).await?;
         }
 
-        let cs = ClientSession::new(sess, framed);
+        let cs = ClientSession::new(sess, framed, self.conf.idle_timeout);
         cs.run().await
     }
 
@@ -327,6 +366,7 @@ impl ProtocolHandler {
 struct ClientSession<C, S> {
     conn: FramedConn<C>,
     session: S,
+    idle_timeout: Option<Duration>,
 }
 
 /// This helper macro is used so we can call some `get_*` methods on the
@@ -351,14 +391,32 @@ where
     C: AsyncRead + AsyncWrite + Unpin,
     S: DerefMut<Target = Session>,
 {
-    fn new(session: S, conn: FramedConn<C>) -> Self {
-        ClientSession { session, conn }
+    fn new(session: S, conn: FramedConn<C>, idle_timeout: Option<Duration>) -> Self {
+        ClientSession {
+            session,
+            conn,
+            idle_timeout,
+        }
     }
 
     async fn run(mut self) -> Result<()> {
         self.ready_for_query().await?;
         loop {
-            let msg = self.conn.read().await?;
+            let msg = match self.idle_timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, self.conn.read()).await {
+                    Ok(msg) => msg?,
+                    Err(_) => {
+                        info!(?timeout, "closing idle connection");
+                        let resp = ErrorResponse::fatal(
+                            pgrepr::notice::SqlState::IdleSessionTimeout,
+                            "terminating connection due to idle timeout",
+                        );
+                        self.conn.send(resp.into()).await?;
+                        return Ok(());
+                    }
+                },
+                None => self.conn.read().await?,
+            };
 
             let msg = match msg {
                 Some(msg) => msg,
@@ -428,12 +486,17 @@ where
     }
 
     /// Send an error response to the client.
+    ///
+    /// This also fails the current transaction block, if any.
     async fn send_error(&mut self, err: ErrorResponse) -> Result<()> {
+        self.session.fail_transaction().await;
         self.conn.send(err.into()).await?;
         Ok(())
     }
 
     async fn ready_for_query(&mut self) -> Result<()> {
+        self.session.end_implicit_transaction();
+
         // Display notice messages before indicating we're ready for the next
         // query. The pg protocol does not presribe a specific flow for notice
         // messages, and so frontends should be capable of handling notices at
@@ -444,9 +507,13 @@ where
                 .await?;
         }
 
-        // TODO: Proper status.
+        let status = match self.session.transaction_state() {
+            TransactionState::Idle => TransactionStatus::Idle,
+            TransactionState::InBlock => TransactionStatus::InBlock,
+            TransactionState::Failed => TransactionStatus::Failed,
+        };
         self.conn
-            .send(BackendMessage::ReadyForQuery(TransactionStatus::Idle))
+            .send(BackendMessage::ReadyForQuery(status))
             .await?;
         self.flush().await
     }
@@ -456,6 +523,12 @@ where
     /// Note that this should only returns errors related to the underlying
     /// connection. All errors resulting from query execution should be sent to
     /// client following by a "ready for query".
+    ///
+    /// Like Postgres, a multi-statement query string is executed as an implicit
+    /// transaction: the first statement to error ends the batch, none of the
+    /// remaining statements are run, and the writes of the statements before
+    /// it are rolled back. See `TransactionState` for what can be rolled back,
+    /// and the behavior within explicit transaction blocks.
     async fn query(&mut self, sql: String) -> Result<()> {
         let session = &mut self.session;
         let conn = &mut self.conn;
@@ -470,10 +543,11 @@ where
 
         // Determines if we send back an empty query response.
         let num_statements = stmts.len();
+        if num_statements > 1 {
+            session.begin_implicit_transaction();
+        }
 
         for stmt in stmts {
-            // TODO: Ensure in transaction.
-
             // Note everything is using unnamed portals/prepared statements.
 
             const UNNAMED: String = String::new();
@@ -513,9 +587,9 @@ where
                 }
             };
 
-            // If we're returning data (SELECT), send back the output fields
-            // before sending back actual data.
-            if let ExecutionResult::Query { .. } = stream {
+            // If we're returning data (SELECT, EXPLAIN), send back the output
+            // fields before sending back actual data.
+            if let ExecutionResult::Query { .. } | ExecutionResult::Explain { .. } = stream {
                 let output_fields =
                     session_do!(self, session, get_portal, &UNNAMED, Portal::output_fields);
                 if let Some(fields) = output_fields {
@@ -523,12 +597,17 @@ where
                 }
             }
 
-            Self::send_result(
+            let ok = Self::send_result(
                 conn,
                 stream,
                 session_do!(self, session, get_portal, &UNNAMED, get_encoding_state),
             )
             .await?;
+            if !ok {
+                // Error already sent while streaming, stop the batch.
+                self.session.fail_transaction().await;
+                return self.ready_for_query().await;
+            }
         }
 
         if num_statements == 0 {
@@ -620,9 +699,12 @@ where
         match object_type {
             DescribeObjectType::Statement => match self.session.get_prepared_statement(&name) {
                 Ok(stmt) => {
-                    // TODO: We don't accept parameters yet. So just send back
-                    // an empty paramaters list.
-                    conn.send(BackendMessage::ParameterDescription(Vec::new()))
+                    let param_types = stmt
+                        .input_parameter_pg_types()
+                        .iter()
+                        .map(|typ| typ.oid() as i32)
+                        .collect();
+                    conn.send(BackendMessage::ParameterDescription(param_types))
                         .await?;
 
                     // Send back row description.
@@ -662,8 +744,6 @@ where
     }
 
     async fn execute(&mut self, portal: String, max_rows: i32) -> Result<()> {
-        // TODO: Ensure in transaction.
-
         let conn = &mut self.conn;
         let session = &mut self.session;
         let stream = match session.execute_portal(&portal, max_rows).await {
@@ -674,12 +754,16 @@ where
         // TODO: This seems to be missing sending back row description. Is it
         // needed? If not, a comment needs to go here.
 
-        Self::send_result(
+        let ok = Self::send_result(
             conn,
             stream,
             session_do!(self, session, get_portal, &portal, get_encoding_state),
         )
-        .await
+        .await?;
+        if !ok {
+            self.session.fail_transaction().await;
+        }
+        Ok(())
     }
 
     async fn close_object(&mut self, object_type: DescribeObjectType, name: String) -> Result<()> {
@@ -699,28 +783,65 @@ where
         Ok(())
     }
 
+    /// Send the result of an execution to the client.
+    ///
+    /// Returns `false` if an error response was sent while streaming the
+    /// result.
     async fn send_result(
         conn: &mut FramedConn<C>,
         stream: ExecutionResult,
         encoding_state: Vec<(PgType, Format)>,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         match stream {
             ExecutionResult::Error(e) => return Err(e.into()),
             ExecutionResult::Query { stream, .. } => {
-                if let Some(num_rows) = Self::stream_batch(conn, stream, encoding_state).await? {
-                    Self::command_complete(conn, format!("SELECT {}", num_rows)).await?;
+                match Self::stream_batch(conn, stream, encoding_state).await? {
+                    Some(num_rows) => {
+                        Self::command_complete(conn, format!("SELECT {}", num_rows)).await?
+                    }
+                    None => return Ok(false),
                 }
             }
+            ExecutionResult::Explain { plan, .. } => {
+                Self::send_explain(conn, &plan, encoding_state).await?;
+                Self::command_complete(conn, "EXPLAIN").await?
+            }
             ExecutionResult::EmptyQuery => conn.send(BackendMessage::EmptyQueryResponse).await?,
             ExecutionResult::Begin => Self::command_complete(conn, "BEGIN").await?,
             ExecutionResult::Commit => Self::command_complete(conn, "COMMIT").await?,
             ExecutionResult::Rollback => Self::command_complete(conn, "ROLLBACK").await?,
+            ExecutionResult::Savepoint => Self::command_complete(conn, "SAVEPOINT").await?,
+            ExecutionResult::ReleaseSavepoint => Self::command_complete(conn, "RELEASE").await?,
+            ExecutionResult::RollbackToSavepoint => {
+                Self::command_complete(conn, "ROLLBACK").await?
+            }
+            ExecutionResult::DeclareCursor => {
+                Self::command_complete(conn, "DECLARE CURSOR").await?
+            }
+            ExecutionResult::CloseCursor => Self::command_complete(conn, "CLOSE CURSOR").await?,
             ExecutionResult::InsertSuccess { rows_inserted } => {
                 // Format is 'INSERT <oid> <num_inserted>'. Oid will always be
                 // zero according to postgres docs.
                 Self::command_complete(conn, format!("INSERT 0 {rows_inserted}")).await?
             }
-            ExecutionResult::CopySuccess => Self::command_complete(conn, "COPY").await?,
+            ExecutionResult::CopySuccess { copied_rows } => {
+                Self::command_complete(conn, format!("COPY {copied_rows}")).await?
+            }
+            ExecutionResult::OverwriteSuccess { rows_written } => {
+                Self::command_complete(conn, format!("INSERT OVERWRITE {rows_written}")).await?
+            }
+            ExecutionResult::OptimizeSuccess {
+                files_removed,
+                files_added,
+            } => {
+                // Format is 'OPTIMIZE <files_removed> <files_added>'. Clients
+                // reading the tag as a row count see the number of files
+                // added.
+                Self::command_complete(conn, format!("OPTIMIZE {files_removed} {files_added}"))
+                    .await?
+            }
+            ExecutionResult::Truncate => Self::command_complete(conn, "TRUNCATE TABLE").await?,
+            ExecutionResult::CancelQuery => Self::command_complete(conn, "KILL").await?,
             ExecutionResult::DeleteSuccess { deleted_rows } => {
                 Self::command_complete(conn, format!("DELETE {}", deleted_rows)).await?
             }
@@ -745,23 +866,33 @@ where
             ExecutionResult::CreateSchema => Self::command_complete(conn, "CREATE SCHEMA").await?,
             ExecutionResult::CreateView => Self::command_complete(conn, "CREATE VIEW").await?,
             ExecutionResult::AlterTable => Self::command_complete(conn, "ALTER TABLE").await?,
+            ExecutionResult::Comment => Self::command_complete(conn, "COMMENT").await?,
             ExecutionResult::AlterDatabase => {
                 Self::command_complete(conn, "ALTER DATABASE").await?
             }
             ExecutionResult::AlterTunnelRotateKeys => {
                 Self::command_complete(conn, "ALTER TUNNEL").await?
             }
-            ExecutionResult::Set => Self::command_complete(conn, "SET").await?,
-            ExecutionResult::DropTables => Self::command_complete(conn, "DROP TABLE").await?,
+            ExecutionResult::Set { variable, value } => {
+                debug!(%variable, %value, "session variable set");
+                // Postgres always completes with a bare "SET" tag, which
+                // clients compare against verbatim.
+                Self::command_complete(conn, "SET").await?
+            }
+            ExecutionResult::DropTables { .. } => {
+                Self::command_complete(conn, "DROP TABLE").await?
+            }
             ExecutionResult::DropViews => Self::command_complete(conn, "DROP VIEW").await?,
-            ExecutionResult::DropSchemas => Self::command_complete(conn, "DROP SCHEMA").await?,
+            ExecutionResult::DropSchemas { .. } => {
+                Self::command_complete(conn, "DROP SCHEMA").await?
+            }
             ExecutionResult::DropDatabase => Self::command_complete(conn, "DROP DATABASE").await?,
             ExecutionResult::DropTunnel => Self::command_complete(conn, "DROP TUNNEL").await?,
             ExecutionResult::DropCredentials => {
                 Self::command_complete(conn, "DROP CREDENTIALS").await?
             }
         };
-        Ok(())
+        Ok(true)
     }
 
     /// Convert an arrow schema into a row descriptor and send it to the client.
@@ -813,6 +944,24 @@ where
         Ok(Some(num_rows))
     }
 
+    /// Sends a rendered explain plan to the client, one row per line of the
+    /// plan.
+    async fn send_explain(
+        conn: &mut FramedConn<C>,
+        plan: &str,
+        encoding_state: Vec<(PgType, Format)>,
+    ) -> Result<()> {
+        let lines: ArrayRef = Arc::new(StringArray::from_iter_values(plan.lines()));
+        let batch = RecordBatch::try_from_iter([("QUERY PLAN", lines)])?;
+
+        conn.set_encoding_state(encoding_state);
+        for row_idx in 0..batch.num_rows() {
+            conn.send(BackendMessage::DataRow(batch.clone(), row_idx))
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn command_complete(conn: &mut FramedConn<C>, tag: impl Into<String>) -> Result<()> {
         conn.send(BackendMessage::CommandComplete { tag: tag.into() })
             .await
@@ -840,11 +989,14 @@ fn decode_param_scalars(
     let param_formats = extend_formats(param_formats, param_values.len())?;
 
     if param_values.len() != types.len() {
-        return Err(ErrorResponse::error_internal(format!(
-            "Invalid number of values provided. Expected: {}, got: {}",
-            types.len(),
-            param_values.len(),
-        )));
+        return Err(ErrorResponse::error(
+            pgrepr::notice::SqlState::ProtocolViolation,
+            format!(
+                "bind message supplies {} parameters, but prepared statement requires {}",
+                param_values.len(),
+                types.len(),
+            ),
+        ));
     }
 
     let mut scalars = Vec::with_capacity(param_values.len());
@@ -875,10 +1027,10 @@ fn decode_param_scalars(
                 scalars.push(scalar);
             }
             None => {
-                return Err(ErrorResponse::error_internal(format!(
-                    "Unknown type at index {}, input types: {:?}",
-                    idx, types
-                )))
+                return Err(ErrorResponse::error(
+                    pgrepr::notice::SqlState::IndeterminateDatatype,
+                    format!("could not determine data type of parameter {str_id}"),
+                ))
             }
         }
     }
@@ -198,6 +198,14 @@ impl ErrorResponse {
         }
     }
 
+    pub fn fatal(code: pgrepr::notice::SqlState, msg: impl Into<String>) -> ErrorResponse {
+        ErrorResponse {
+            severity: ErrorSeverity::Fatal,
+            code,
+            message: msg.into(),
+        }
+    }
+
     pub fn feature_not_supported(msg: impl Into<String>) -> ErrorResponse {
         Self::error(pgrepr::notice::SqlState::FeatureNotSupported, msg)
     }
@@ -218,6 +226,30 @@ impl ErrorResponse {
 impl From<ExecError> for ErrorResponse {
     fn from(e: ExecError) -> Self {
         // TODO: Actually set appropriate codes.
+        match e {
+            ExecError::TransactionAborted => {
+                return ErrorResponse::error(
+                    pgrepr::notice::SqlState::InFailedSqlTransaction,
+                    e.to_string(),
+                )
+            }
+            ExecError::InvalidParameterCount { .. } => {
+                return ErrorResponse::error(
+                    pgrepr::notice::SqlState::ProtocolViolation,
+                    e.to_string(),
+                )
+            }
+            ExecError::InvalidParameterType { .. } => {
+                return ErrorResponse::error(
+                    pgrepr::notice::SqlState::DatatypeMismatch,
+                    e.to_string(),
+                )
+            }
+            ExecError::IrreversibleChanges(..) => {
+                return ErrorResponse::feature_not_supported(e.to_string())
+            }
+            _ => (),
+        }
         ErrorResponse::error_internal(e.to_string())
     }
 }
@@ -1,6 +1,9 @@
 fn main() {
     let mut config = prost_build::Config::new();
-    config.btree_map([".metastore.options.StorageOptions"]);
+    config.btree_map([
+        ".metastore.options.StorageOptions",
+        ".metastore.catalog.TableEntry",
+    ]);
 
     tonic_build::configure()
         .build_server(true)
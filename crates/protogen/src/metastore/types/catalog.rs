@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Display};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -456,6 +456,11 @@ pub struct TableEntry {
     pub tunnel_id: Option<u32>,
     pub access_mode: SourceAccessMode,
     pub columns: Option<Vec<InternalColumnDefinition>>,
+    /// Description of the table set with `COMMENT ON TABLE`.
+    pub comment: Option<String>,
+    /// Descriptions of columns set with `COMMENT ON COLUMN`, keyed by column
+    /// name.
+    pub column_comments: BTreeMap<String, String>,
 }
 
 impl TableEntry {
@@ -505,6 +510,8 @@ impl TryFrom<catalog::TableEntry> for TableEntry {
             tunnel_id: value.tunnel_id,
             access_mode: value.access_mode.try_into()?,
             columns,
+            comment: value.comment,
+            column_comments: value.column_comments,
         })
     }
 }
@@ -525,6 +532,8 @@ impl From<TableEntry> for catalog::TableEntry {
             access_mode: value.access_mode.into(),
             options: None,
             columns,
+            comment: value.comment,
+            column_comments: value.column_comments,
         }
     }
 }
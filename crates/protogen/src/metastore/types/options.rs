@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 use std::fmt::{self, Display};
 use std::hash::Hash;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use datafusion::arrow::array::{ArrayRef, GenericStringArray, StructArray};
@@ -58,6 +59,25 @@ impl InternalColumnDefinition {
     {
         cols.into_iter().map(|col| Arc::new(col.into())).collect()
     }
+
+    /// Check if a column of type `from` can be changed to `to` without losing
+    /// any existing values.
+    ///
+    /// Only integer and float widening is supported.
+    pub fn is_widening(from: &DataType, to: &DataType) -> bool {
+        use DataType::*;
+        matches!(
+            (from, to),
+            (Int8, Int16 | Int32 | Int64)
+                | (Int16, Int32 | Int64)
+                | (Int32, Int64)
+                | (UInt8, UInt16 | UInt32 | UInt64)
+                | (UInt16, UInt32 | UInt64)
+                | (UInt32, UInt64)
+                | (Float16, Float32 | Float64)
+                | (Float32, Float64)
+        )
+    }
 }
 
 impl From<InternalColumnDefinition> for Field {
@@ -705,6 +725,8 @@ pub enum TableOptionsV0 {
     Cassandra(TableOptionsCassandra),
     Excel(TableOptionsExcel),
     Sqlite(TableOptionsObjectStore),
+    Ndjson(TableOptionsObjectStore),
+    Avro(TableOptionsObjectStore),
 }
 
 impl TableOptionsV0 {
@@ -728,9 +750,14 @@ impl TableOptionsV0 {
     pub const CASSANDRA: &'static str = "cassandra";
     pub const EXCEL: &'static str = "excel";
     pub const SQLITE: &'static str = "sqlite";
+    pub const NDJSON: &'static str = "ndjson";
+    pub const AVRO: &'static str = "avro";
 
     pub const fn new_internal(columns: Vec<InternalColumnDefinition>) -> TableOptionsV0 {
-        TableOptionsV0::Internal(TableOptionsInternal { columns })
+        TableOptionsV0::Internal(TableOptionsInternal {
+            columns,
+            partition_by: Vec::new(),
+        })
     }
 
     pub fn as_str(&self) -> &'static str {
@@ -755,6 +782,8 @@ impl TableOptionsV0 {
             TableOptionsV0::Cassandra(_) => Self::CASSANDRA,
             TableOptionsV0::Excel(_) => Self::EXCEL,
             TableOptionsV0::Sqlite(_) => Self::SQLITE,
+            TableOptionsV0::Ndjson(_) => Self::NDJSON,
+            TableOptionsV0::Avro(_) => Self::AVRO,
         }
     }
 }
@@ -782,6 +811,8 @@ impl From<TableOptionsV0> for TableOptionsV1 {
             TableOptionsV0::Cassandra(opts) => TableOptionsV1::new(&opts),
             TableOptionsV0::Excel(opts) => TableOptionsV1::new(&opts),
             TableOptionsV0::Sqlite(opts) => TableOptionsV1::new(&opts),
+            TableOptionsV0::Ndjson(opts) => TableOptionsV1::new(&opts),
+            TableOptionsV0::Avro(opts) => TableOptionsV1::new(&opts),
         }
     }
 }
@@ -802,6 +833,8 @@ impl TryFrom<&TableOptionsV1> for TableOptionsV0 {
                     Self::AZURE => Ok(TableOptionsV0::Azure(obj_store)),
                     Self::LANCE => Ok(TableOptionsV0::Lance(obj_store)),
                     Self::BSON => Ok(TableOptionsV0::Bson(obj_store)),
+                    Self::NDJSON => Ok(TableOptionsV0::Ndjson(obj_store)),
+                    Self::AVRO => Ok(TableOptionsV0::Avro(obj_store)),
                     _ => Err(ProtoConvError::UnknownVariant(value.name.to_string())),
                 }
             }
@@ -904,6 +937,8 @@ impl TryFrom<TableOptionsV0> for options::table_options_v0::Options {
             TableOptionsV0::Cassandra(v) => options::table_options_v0::Options::Cassandra(v.into()),
             TableOptionsV0::Excel(v) => options::table_options_v0::Options::Excel(v.into()),
             TableOptionsV0::Sqlite(v) => options::table_options_v0::Options::Sqlite(v.into()),
+            TableOptionsV0::Ndjson(v) => options::table_options_v0::Options::Ndjson(v.into()),
+            TableOptionsV0::Avro(v) => options::table_options_v0::Options::Avro(v.into()),
         })
     }
 }
@@ -948,6 +983,8 @@ impl TryFrom<options::table_options_v0::Options> for TableOptionsV0 {
             }
             options::table_options_v0::Options::Excel(v) => TableOptionsV0::Excel(v.try_into()?),
             options::table_options_v0::Options::Sqlite(v) => TableOptionsV0::Sqlite(v.try_into()?),
+            options::table_options_v0::Options::Ndjson(v) => TableOptionsV0::Ndjson(v.try_into()?),
+            options::table_options_v0::Options::Avro(v) => TableOptionsV0::Avro(v.try_into()?),
         })
     }
 }
@@ -1010,6 +1047,8 @@ impl From<TableOptionsDebug> for options::TableOptionsDebug {
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TableOptionsInternal {
     pub columns: Vec<InternalColumnDefinition>,
+    /// Names of the columns the table's data is partitioned by on disk.
+    pub partition_by: Vec<String>,
 }
 
 impl From<TableOptionsInternal> for TableOptionsV0 {
@@ -1034,6 +1073,7 @@ impl From<DFSchemaRef> for TableOptionsInternal {
                     arrow_type: col.data_type().clone(),
                 })
                 .collect::<Vec<_>>(),
+            partition_by: Vec::new(),
         }
     }
 }
@@ -1050,6 +1090,7 @@ impl From<SchemaRef> for TableOptionsInternal {
                     arrow_type: col.data_type().clone(),
                 })
                 .collect::<Vec<_>>(),
+            partition_by: Vec::new(),
         }
     }
 }
@@ -1063,6 +1104,7 @@ impl TryFrom<options::TableOptionsInternal> for TableOptionsInternal {
                 .into_iter()
                 .map(|col| col.try_into())
                 .collect::<Result<_, _>>()?,
+            partition_by: value.partition_by,
         })
     }
 }
@@ -1072,6 +1114,7 @@ impl TryFrom<TableOptionsInternal> for options::TableOptionsInternal {
     fn try_from(value: TableOptionsInternal) -> Result<Self, Self::Error> {
         Ok(options::TableOptionsInternal {
             columns: value.columns.into_iter().map(Into::into).collect(),
+            partition_by: value.partition_by,
         })
     }
 }
@@ -2021,6 +2064,15 @@ impl CopyToDestinationOptions {
             Self::Azure(CopyToDestinationOptionsAzure { location, .. }) => location,
         }
     }
+
+    pub fn location_mut(&mut self) -> &mut String {
+        match self {
+            Self::Local(CopyToDestinationOptionsLocal { location }) => location,
+            Self::Gcs(CopyToDestinationOptionsGcs { location, .. }) => location,
+            Self::S3(CopyToDestinationOptionsS3 { location, .. }) => location,
+            Self::Azure(CopyToDestinationOptionsAzure { location, .. }) => location,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -2064,10 +2116,7 @@ pub enum CopyToFormatOptions {
 
 impl Default for CopyToFormatOptions {
     fn default() -> Self {
-        Self::Csv(CopyToFormatOptionsCsv {
-            delim: b',',
-            header: true,
-        })
+        Self::Csv(CopyToFormatOptionsCsv::default())
     }
 }
 
@@ -2099,6 +2148,25 @@ impl CopyToFormatOptions {
 pub struct CopyToFormatOptionsCsv {
     pub delim: u8,
     pub header: bool,
+    pub quote: u8,
+    /// String written for null values.
+    pub null: String,
+    /// Terminate records with "\r\n" instead of "\n".
+    pub crlf: bool,
+    pub compression: CopyToCompression,
+}
+
+impl Default for CopyToFormatOptionsCsv {
+    fn default() -> Self {
+        CopyToFormatOptionsCsv {
+            delim: b',',
+            header: true,
+            quote: b'"',
+            null: String::new(),
+            crlf: false,
+            compression: CopyToCompression::None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -2109,6 +2177,72 @@ pub struct CopyToFormatOptionsParquet {
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct CopyToFormatOptionsJson {
     pub array: bool,
+    pub compression: CopyToCompression,
+}
+
+/// Compression codec applied to files written by COPY TO.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum CopyToCompression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl CopyToCompression {
+    pub const NONE: &'static str = "none";
+    pub const GZIP: &'static str = "gzip";
+    pub const ZSTD: &'static str = "zstd";
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => Self::NONE,
+            Self::Gzip => Self::GZIP,
+            Self::Zstd => Self::ZSTD,
+        }
+    }
+
+    /// File extension for files compressed with this codec, without the
+    /// leading dot.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Gzip => Some("gz"),
+            Self::Zstd => Some("zst"),
+        }
+    }
+
+    /// Get the codec for a file extension, if it's one we write.
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_lowercase().as_str() {
+            "gz" => Some(Self::Gzip),
+            "zst" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for CopyToCompression {
+    type Err = ProtoConvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "" | Self::NONE => Self::None,
+            Self::GZIP | "gz" => Self::Gzip,
+            Self::ZSTD | "zst" => Self::Zstd,
+            other => {
+                return Err(ProtoConvError::ParseError(format!(
+                    "unsupported compression: {other}"
+                )))
+            }
+        })
+    }
+}
+
+impl fmt::Display for CopyToCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -1,3 +1,4 @@
+use datafusion::arrow::datatypes::DataType;
 use datafusion::logical_expr::Signature;
 
 use super::catalog::{FunctionType, SourceAccessMode};
@@ -9,6 +10,7 @@ use super::options::{
     TableOptionsV0,
     TunnelOptions,
 };
+use crate::gen::common::arrow;
 use crate::gen::metastore::service;
 use crate::{gen, FromOptionalField, ProtoConvError};
 
@@ -437,8 +439,42 @@ impl From<CreateExternalDatabase> for service::CreateExternalDatabase {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AlterTableOperation {
-    RenameTable { new_name: String },
-    SetAccessMode { access_mode: SourceAccessMode },
+    RenameTable {
+        new_name: String,
+    },
+    SetAccessMode {
+        access_mode: SourceAccessMode,
+    },
+    RenameColumn {
+        old_name: String,
+        new_name: String,
+    },
+    /// Add a nullable column to the table.
+    AddColumn {
+        name: String,
+        arrow_type: DataType,
+    },
+    DropColumn {
+        name: String,
+        if_exists: bool,
+    },
+    /// Change the type of a column to a wider type that can represent all of
+    /// its existing values, e.g. Int32 to Int64.
+    WidenColumn {
+        name: String,
+        arrow_type: DataType,
+    },
+    /// Set or remove (if `comment` is `None`) the comment on the table, or on
+    /// one of its columns.
+    SetComment {
+        column: Option<String>,
+        comment: Option<String>,
+    },
+    /// Replace the columns of an external table, e.g. after re-inferring
+    /// the schema from its source.
+    SetColumns {
+        columns: Vec<InternalColumnDefinition>,
+    },
 }
 
 impl TryFrom<service::alter_table_operation::Operation> for AlterTableOperation {
@@ -453,6 +489,35 @@ impl TryFrom<service::alter_table_operation::Operation> for AlterTableOperation
             ) => Self::SetAccessMode {
                 access_mode: access_mode.try_into()?,
             },
+            service::alter_table_operation::Operation::AlterTableOperationRenameColumn(
+                service::AlterTableOperationRenameColumn { old_name, new_name },
+            ) => Self::RenameColumn { old_name, new_name },
+            service::alter_table_operation::Operation::AlterTableOperationAddColumn(
+                service::AlterTableOperationAddColumn { name, arrow_type },
+            ) => Self::AddColumn {
+                name,
+                arrow_type: arrow_type.as_ref().required("arrow_type")?,
+            },
+            service::alter_table_operation::Operation::AlterTableOperationDropColumn(
+                service::AlterTableOperationDropColumn { name, if_exists },
+            ) => Self::DropColumn { name, if_exists },
+            service::alter_table_operation::Operation::AlterTableOperationWidenColumn(
+                service::AlterTableOperationWidenColumn { name, arrow_type },
+            ) => Self::WidenColumn {
+                name,
+                arrow_type: arrow_type.as_ref().required("arrow_type")?,
+            },
+            service::alter_table_operation::Operation::AlterTableOperationSetComment(
+                service::AlterTableOperationSetComment { column, comment },
+            ) => Self::SetComment { column, comment },
+            service::alter_table_operation::Operation::AlterTableOperationSetColumns(
+                service::AlterTableOperationSetColumns { columns },
+            ) => Self::SetColumns {
+                columns: columns
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            },
         })
     }
 }
@@ -472,6 +537,49 @@ impl From<AlterTableOperation> for service::alter_table_operation::Operation {
                     },
                 )
             }
+            AlterTableOperation::RenameColumn { old_name, new_name } => {
+                service::alter_table_operation::Operation::AlterTableOperationRenameColumn(
+                    service::AlterTableOperationRenameColumn { old_name, new_name },
+                )
+            }
+            AlterTableOperation::AddColumn { name, arrow_type } => {
+                // We don't support any of the unserializable arrow types.
+                let arrow_type = arrow::ArrowType::try_from(&arrow_type)
+                    .expect("Arrow type must be serializable");
+                service::alter_table_operation::Operation::AlterTableOperationAddColumn(
+                    service::AlterTableOperationAddColumn {
+                        name,
+                        arrow_type: Some(arrow_type),
+                    },
+                )
+            }
+            AlterTableOperation::DropColumn { name, if_exists } => {
+                service::alter_table_operation::Operation::AlterTableOperationDropColumn(
+                    service::AlterTableOperationDropColumn { name, if_exists },
+                )
+            }
+            AlterTableOperation::WidenColumn { name, arrow_type } => {
+                let arrow_type = arrow::ArrowType::try_from(&arrow_type)
+                    .expect("Arrow type must be serializable");
+                service::alter_table_operation::Operation::AlterTableOperationWidenColumn(
+                    service::AlterTableOperationWidenColumn {
+                        name,
+                        arrow_type: Some(arrow_type),
+                    },
+                )
+            }
+            AlterTableOperation::SetComment { column, comment } => {
+                service::alter_table_operation::Operation::AlterTableOperationSetComment(
+                    service::AlterTableOperationSetComment { column, comment },
+                )
+            }
+            AlterTableOperation::SetColumns { columns } => {
+                service::alter_table_operation::Operation::AlterTableOperationSetColumns(
+                    service::AlterTableOperationSetColumns {
+                        columns: columns.into_iter().map(Into::into).collect(),
+                    },
+                )
+            }
         }
     }
 }
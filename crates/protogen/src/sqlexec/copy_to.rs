@@ -64,7 +64,7 @@ pub struct CopyToDestinationOptionsAzure {
 
 #[derive(Clone, PartialEq, Message)]
 pub struct CopyToFormatOptions {
-    #[prost(oneof = "CopyToFormatOptionsEnum", tags = "1, 2, 3")]
+    #[prost(oneof = "CopyToFormatOptionsEnum", tags = "1, 2, 3, 4, 5, 6")]
     pub copy_to_format_options_enum: Option<CopyToFormatOptionsEnum>,
 }
 
@@ -90,12 +90,22 @@ pub struct CopyToFormatOptionsCsv {
     pub delim: u32,
     #[prost(bool, tag = "2")]
     pub header: bool,
+    #[prost(uint32, tag = "3")]
+    pub quote: u32,
+    #[prost(string, tag = "4")]
+    pub null: String,
+    #[prost(bool, tag = "5")]
+    pub crlf: bool,
+    #[prost(string, tag = "6")]
+    pub compression: String,
 }
 
 #[derive(Clone, PartialEq, Message)]
 pub struct CopyToFormatOptionsJson {
     #[prost(bool, tag = "1")]
     pub array: bool,
+    #[prost(string, tag = "2")]
+    pub compression: String,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -152,6 +162,10 @@ impl TryFrom<crate::metastore::types::options::CopyToFormatOptions> for CopyToFo
                         CopyToFormatOptionsCsv {
                             delim: csv.delim as u32,
                             header: csv.header,
+                            quote: csv.quote as u32,
+                            null: csv.null,
+                            crlf: csv.crlf,
+                            compression: csv.compression.to_string(),
                         },
                     )),
                 })
@@ -159,7 +173,10 @@ impl TryFrom<crate::metastore::types::options::CopyToFormatOptions> for CopyToFo
             crate::metastore::types::options::CopyToFormatOptions::Json(json) => {
                 Ok(CopyToFormatOptions {
                     copy_to_format_options_enum: Some(CopyToFormatOptionsEnum::Json(
-                        CopyToFormatOptionsJson { array: json.array },
+                        CopyToFormatOptionsJson {
+                            array: json.array,
+                            compression: json.compression.to_string(),
+                        },
                     )),
                 })
             }
@@ -214,12 +231,24 @@ impl TryFrom<CopyToFormatOptions> for crate::metastore::types::options::CopyToFo
                     crate::metastore::types::options::CopyToFormatOptionsCsv {
                         delim: csv.delim as u8,
                         header: csv.header,
+                        // Unset by older clients.
+                        quote: if csv.quote == 0 {
+                            b'"'
+                        } else {
+                            csv.quote as u8
+                        },
+                        null: csv.null,
+                        crlf: csv.crlf,
+                        compression: csv.compression.parse()?,
                     },
                 ))
             }
             CopyToFormatOptionsEnum::Json(json) => {
                 Ok(crate::metastore::types::options::CopyToFormatOptions::Json(
-                    crate::metastore::types::options::CopyToFormatOptionsJson { array: json.array },
+                    crate::metastore::types::options::CopyToFormatOptionsJson {
+                        array: json.array,
+                        compression: json.compression.parse()?,
+                    },
                 ))
             }
             CopyToFormatOptionsEnum::Bson(_) => {
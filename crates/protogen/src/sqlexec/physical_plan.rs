@@ -41,6 +41,8 @@ pub struct CreateTableExec {
     pub or_replace: bool,
     #[prost(message, tag = "5")]
     pub arrow_schema: Option<Schema>,
+    #[prost(string, repeated, tag = "6")]
+    pub partition_by: Vec<String>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -295,10 +297,81 @@ pub struct DeleteExec {
     pub where_expr: Option<LogicalExprNode>,
 }
 
+#[derive(Clone, PartialEq, Message)]
+pub struct VacuumTableExec {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(uint64, optional, tag = "2")]
+    pub retain_hours: Option<u64>,
+    #[prost(bool, tag = "3")]
+    pub dry_run: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct OptimizeTableExec {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(string, repeated, tag = "2")]
+    pub zorder_by: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct TruncateTablesExec {
+    #[prost(message, repeated, tag = "1")]
+    pub tables: Vec<TableEntry>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct CancelQueryExec {
+    #[prost(bytes, tag = "1")]
+    pub connection_id: Vec<u8>, // UUID
+}
+
 #[derive(Clone, PartialEq, Message)]
 pub struct InsertExec {
     #[prost(bytes, tag = "1")]
     pub provider_id: Vec<u8>, // UUID
+    #[prost(bool, tag = "2")]
+    pub copy_from: bool,
+    #[prost(uint64, tag = "3")]
+    pub catalog_version: u64,
+    #[prost(message, optional, tag = "4")]
+    pub schema_evolution: Option<SchemaEvolution>,
+    #[prost(bool, tag = "5")]
+    pub returning: bool,
+    #[prost(message, optional, tag = "6")]
+    pub on_conflict: Option<OnConflict>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct OnConflict {
+    #[prost(string, repeated, tag = "1")]
+    pub columns: Vec<String>,
+    /// Update conflicting rows instead of skipping them.
+    #[prost(bool, tag = "2")]
+    pub do_update: bool,
+    #[prost(message, repeated, tag = "3")]
+    pub assignments: Vec<UpdateSelector>,
+    #[prost(message, optional, tag = "4")]
+    pub predicate: Option<LogicalExprNode>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct SchemaEvolution {
+    #[prost(string, tag = "1")]
+    pub schema: String,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(message, repeated, tag = "3")]
+    pub operations: Vec<crate::gen::metastore::service::AlterTableOperation>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct InsertOverwriteExec {
+    #[prost(message, tag = "1")]
+    pub table: Option<TableEntry>,
+    #[prost(message, optional, tag = "2")]
+    pub replace_where: Option<LogicalExprNode>,
 }
 
 #[derive(Clone, PartialEq, Message)]
@@ -343,7 +416,7 @@ pub struct AnalyzeExec {
 pub struct ExecutionPlanExtension {
     #[prost(
         oneof = "ExecutionPlanExtensionType",
-        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31"
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35"
     )]
     pub inner: Option<ExecutionPlanExtensionType>,
 }
@@ -417,4 +490,14 @@ pub enum ExecutionPlanExtensionType {
     DataSourceMetricsExecAdapter(DataSourceMetricsExecAdapter),
     #[prost(message, tag = "31")]
     DescribeTable(DescribeTableExec),
+    #[prost(message, tag = "32")]
+    VacuumTableExec(VacuumTableExec),
+    #[prost(message, tag = "33")]
+    OptimizeTableExec(OptimizeTableExec),
+    #[prost(message, tag = "34")]
+    InsertOverwriteExec(InsertOverwriteExec),
+    #[prost(message, tag = "35")]
+    TruncateTablesExec(TruncateTablesExec),
+    #[prost(message, tag = "36")]
+    CancelQueryExec(CancelQueryExec),
 }
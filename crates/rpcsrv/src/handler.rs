@@ -176,6 +176,7 @@ impl RpcHandler {
             req.database_id,
             Uuid::nil(), // TODO: Connection ID?
             self.engine.get_tracker(),
+            self.engine.server_metrics(),
         );
 
         let query_metrics = QueryMetrics {
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -22,6 +23,17 @@ pub enum TestClient {
 }
 
 impl TestClient {
+    /// Take the warnings emitted by the server since the last call.
+    ///
+    /// Flight SQL doesn't surface notices, so it never has warnings.
+    pub async fn take_warnings(&self) -> Vec<String> {
+        match self {
+            Self::Pg(pg_client) => pg_client.take_warnings(),
+            Self::Rpc(rpc_client) => rpc_client.take_warnings().await,
+            Self::FlightSql(_) => Vec::new(),
+        }
+    }
+
     pub async fn close(self) -> Result<()> {
         match self {
             Self::Pg(pg_client) => pg_client.close().await,
@@ -83,3 +95,13 @@ impl ValueEnum for ClientProtocol {
         })
     }
 }
+
+impl fmt::Display for ClientProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientProtocol::Postgres => write!(f, "postgres"),
+            ClientProtocol::Rpc => write!(f, "rpc"),
+            ClientProtocol::FlightSql => write!(f, "flightsql"),
+        }
+    }
+}
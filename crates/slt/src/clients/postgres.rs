@@ -1,17 +1,56 @@
 use std::ops::Deref;
+use std::path::Path;
 use std::sync::Arc;
+use std::task::{ready, Poll};
 
 use anyhow::{anyhow, Result};
 use sqlexec::errors::ExecError;
 use sqllogictest::{AsyncDB, DBOutput, DefaultColumnType};
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::{oneshot, Mutex};
-use tokio_postgres::{Client, Config, NoTls, SimpleQueryMessage};
+use tokio_postgres::{AsyncMessage, Client, Config, Connection, NoTls, SimpleQueryMessage};
+use tokio_postgres_rustls::MakeRustlsConnect;
 use tonic::async_trait;
 
+/// TLS configuration for connecting to a server with TLS enabled.
+#[derive(Clone)]
+pub struct PgTlsConfig {
+    config: Arc<rustls::ClientConfig>,
+}
+
+impl PgTlsConfig {
+    /// Create a new TLS config trusting the webpki roots, along with the
+    /// certificates in `ca_cert` if provided.
+    pub fn new(ca_cert: Option<&Path>) -> Result<Self> {
+        let mut root_store = rustls::RootCertStore::empty();
+        root_store
+            .roots
+            .extend(webpki_roots::TLS_SERVER_ROOTS.iter().map(|r| r.to_owned()));
+
+        if let Some(path) = ca_cert {
+            let cert_bs = std::fs::read(path)
+                .map_err(|e| anyhow!("Failed to read CA cert `{}`: {e}", path.display()))?;
+            for cert in rustls_pemfile::certs(&mut cert_bs.as_slice()) {
+                root_store.add(cert?)?;
+            }
+        }
+
+        let config = rustls::ClientConfig::builder()
+            .with_root_certificates(root_store)
+            .with_no_client_auth();
+
+        Ok(Self {
+            config: Arc::new(config),
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct PgTestClient {
     client: Arc<Client>,
     conn_err_rx: Arc<Mutex<oneshot::Receiver<Result<(), tokio_postgres::Error>>>>,
+    /// Warnings sent by the server that haven't been taken yet.
+    warnings: Arc<std::sync::Mutex<Vec<String>>>,
 }
 
 impl Deref for PgTestClient {
@@ -22,16 +61,40 @@ impl Deref for PgTestClient {
 }
 
 impl PgTestClient {
-    pub async fn new(client_config: &Config) -> Result<Self> {
-        let (client, conn) = client_config.connect(NoTls).await?;
+    /// Connect to the server, using TLS if `tls` is provided.
+    ///
+    /// Whether TLS is required is determined by the ssl mode of the client
+    /// config.
+    pub async fn new(client_config: &Config, tls: Option<&PgTlsConfig>) -> Result<Self> {
         let (conn_err_tx, conn_err_rx) = oneshot::channel();
-        tokio::spawn(async move { conn_err_tx.send(conn.await) });
+        let warnings = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let client = match tls {
+            Some(tls) => {
+                let connector = MakeRustlsConnect::new(tls.config.as_ref().clone());
+                let (client, conn) = client_config.connect(connector).await?;
+                let warnings = warnings.clone();
+                tokio::spawn(async move { conn_err_tx.send(drive_conn(conn, &warnings).await) });
+                client
+            }
+            None => {
+                let (client, conn) = client_config.connect(NoTls).await?;
+                let warnings = warnings.clone();
+                tokio::spawn(async move { conn_err_tx.send(drive_conn(conn, &warnings).await) });
+                client
+            }
+        };
         Ok(Self {
             client: Arc::new(client),
             conn_err_rx: Arc::new(Mutex::new(conn_err_rx)),
+            warnings,
         })
     }
 
+    /// Take the warnings the server has sent since the last call.
+    pub(super) fn take_warnings(&self) -> Vec<String> {
+        std::mem::take(&mut *self.warnings.lock().unwrap())
+    }
+
     pub(super) async fn close(&self) -> Result<()> {
         let PgTestClient { conn_err_rx, .. } = self;
         let mut conn_err_rx = conn_err_rx.lock().await;
@@ -48,6 +111,29 @@ impl PgTestClient {
     }
 }
 
+/// Drive the connection to completion, collecting any warnings sent by the
+/// server.
+async fn drive_conn<S, T>(
+    mut conn: Connection<S, T>,
+    warnings: &std::sync::Mutex<Vec<String>>,
+) -> Result<(), tokio_postgres::Error>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    T: AsyncRead + AsyncWrite + Unpin,
+{
+    futures::future::poll_fn(|cx| loop {
+        match ready!(conn.poll_message(cx)) {
+            Some(Ok(AsyncMessage::Notice(notice))) if notice.severity() == "WARNING" => {
+                warnings.lock().unwrap().push(notice.message().to_string());
+            }
+            Some(Ok(_)) => (),
+            Some(Err(e)) => return Poll::Ready(Err(e)),
+            None => return Poll::Ready(Ok(())),
+        }
+    })
+    .await
+}
+
 #[async_trait]
 impl AsyncDB for PgTestClient {
     type Error = sqlexec::errors::ExecError;
@@ -55,6 +141,7 @@ impl AsyncDB for PgTestClient {
     async fn run(&mut self, sql: &str) -> Result<DBOutput<Self::ColumnType>, Self::Error> {
         let mut output = Vec::new();
         let mut num_columns = 0;
+        let mut rows_affected = 0;
 
         let rows = self
             .simple_query(sql)
@@ -79,12 +166,12 @@ impl AsyncDB for PgTestClient {
                     }
                     output.push(row_output);
                 }
-                SimpleQueryMessage::CommandComplete(_) => {}
+                SimpleQueryMessage::CommandComplete(count) => rows_affected = count,
                 _ => unreachable!(),
             }
         }
         if output.is_empty() && num_columns == 0 {
-            Ok(DBOutput::StatementComplete(0))
+            Ok(DBOutput::StatementComplete(rows_affected))
         } else {
             Ok(DBOutput::Rows {
                 types: vec![DefaultColumnType::Text; num_columns],
@@ -6,6 +6,7 @@ use datafusion_ext::vars::SessionVars;
 use futures::StreamExt;
 use metastore::util::MetastoreClientMode;
 use pgrepr::format::Format;
+use pgrepr::notice::NoticeSeverity;
 use pgrepr::scalar::Scalar;
 use pgrepr::types::arrow_to_pg_type;
 use sqlexec::engine::{Engine, EngineStorageConfig, SessionStorageConfig, TrackedSession};
@@ -38,7 +39,12 @@ impl RpcTestClient {
         let mut session = engine
             .new_local_session_context(SessionVars::default(), SessionStorageConfig::default())
             .await?;
-        let test_id = Uuid::new_v4();
+        // Use the database name as the session id when it's a UUID so the
+        // storage location of a test's database is predictable.
+        let test_id = config
+            .get_dbname()
+            .and_then(|name| name.parse().ok())
+            .unwrap_or_else(Uuid::new_v4);
         session
             .attach_remote_session(remote_client, Some(test_id))
             .await?;
@@ -47,6 +53,18 @@ impl RpcTestClient {
             _engine: Arc::new(engine),
         })
     }
+
+    /// Take the warnings the session has emitted since the last call.
+    pub(super) async fn take_warnings(&self) -> Vec<String> {
+        self.session
+            .lock()
+            .await
+            .take_notices()
+            .into_iter()
+            .filter(|notice| notice.severity == NoticeSeverity::Warning)
+            .map(|notice| notice.message)
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -117,6 +135,10 @@ impl AsyncDB for RpcTestClient {
                         }
                     }
                 }
+                ExecutionResult::Explain { plan, .. } => {
+                    num_columns = 1;
+                    output.extend(plan.lines().map(|line| vec![line.to_owned()]));
+                }
                 ExecutionResult::Error(e) => return Err(e.into()),
                 _ => (),
             }
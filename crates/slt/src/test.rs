@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::fmt::{Debug, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -7,7 +7,7 @@ use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use glob::Pattern;
 use regex::{Captures, Regex};
-use sqllogictest::{parse_with_name, ColumnType, Injected, Record, Runner};
+use sqllogictest::{parse_with_name, ColumnType, Injected, Record, Runner, TestErrorKind};
 use tokio_postgres::Config;
 
 use crate::clients::TestClient;
@@ -69,16 +69,21 @@ impl Debug for Test {
 }
 
 impl Test {
+    /// Execute the test.
+    ///
+    /// If `diff` is set, mismatched query results are reported with a
+    /// row-by-row comparison of the expected and actual rows.
     pub async fn execute(
-        self,
+        &self,
         config: &Config,
         client: TestClient,
         vars: &mut HashMap<String, String>,
+        diff: bool,
     ) -> Result<()> {
         match self {
             Self::File(path) => {
                 let regx = Regex::new(ENV_REGEX).unwrap();
-                let records = parse_file(&regx, &path, vars)?;
+                let records = parse_file(&regx, path, vars)?;
 
                 let mut runner = Runner::new(|| {
                     let client = client.clone();
@@ -88,13 +93,111 @@ impl Test {
                 runner
                     .run_multi_async(records)
                     .await
-                    .map_err(|e| anyhow!("test fail: {}", e))
+                    .map_err(|e| match e.kind() {
+                        TestErrorKind::QueryResultMismatch {
+                            sql,
+                            expected,
+                            actual,
+                        } if diff => anyhow!(
+                            "test fail: query result mismatch\n[SQL] {sql}\nat {}\n{}",
+                            e.location(),
+                            result_diff(&expected, &actual)
+                        ),
+                        _ => anyhow!("test fail: {}", e),
+                    })
             }
             Self::FnTest(fn_test) => fn_test.run(config, client, vars).await,
         }
     }
 }
 
+/// Format the expected and actual results of a query side by side, one row per
+/// line, marking rows that differ and pointing out the first differing cell.
+///
+/// Cells are split on whitespace, matching how results are compared.
+fn result_diff(expected: &str, actual: &str) -> String {
+    let split = |results: &str| -> Vec<Vec<String>> {
+        results
+            .lines()
+            .map(|line| line.split_whitespace().map(str::to_string).collect())
+            .collect()
+    };
+    let expected = split(expected);
+    let actual = split(actual);
+    let num_rows = expected.len().max(actual.len());
+
+    let cell = |rows: &[Vec<String>], row: usize, col: usize| {
+        rows.get(row)
+            .and_then(|cells| cells.get(col))
+            .map(|cell| format!("`{cell}`"))
+            .unwrap_or_else(|| "nothing".to_string())
+    };
+
+    let mut out = format!(
+        "expected {} row(s), got {} row(s)\n",
+        expected.len(),
+        actual.len()
+    );
+
+    let first_diff = (0..num_rows).find_map(|row| {
+        let (exp, act) = (expected.get(row), actual.get(row));
+        if exp == act {
+            return None;
+        }
+        let num_cols = exp
+            .map(Vec::len)
+            .unwrap_or(0)
+            .max(act.map(Vec::len).unwrap_or(0));
+        let col = (0..num_cols)
+            .find(|&col| {
+                exp.and_then(|cells| cells.get(col)) != act.and_then(|cells| cells.get(col))
+            })
+            .unwrap_or(0);
+        Some((row, col))
+    });
+    if let Some((row, col)) = first_diff {
+        writeln!(
+            out,
+            "first difference at row {}, column {}: expected {}, got {}",
+            row + 1,
+            col + 1,
+            cell(&expected, row, col),
+            cell(&actual, row, col),
+        )
+        .unwrap();
+    }
+
+    let joined = |rows: &[Vec<String>], row: usize| {
+        rows.get(row)
+            .map(|cells| cells.join(" "))
+            .unwrap_or_default()
+    };
+    let width = (0..num_rows)
+        .map(|row| joined(&expected, row).len())
+        .chain(["expected".len()])
+        .max()
+        .unwrap_or_default();
+
+    writeln!(out, "       {:<width$} | actual", "expected").unwrap();
+    for row in 0..num_rows {
+        let marker = if expected.get(row) == actual.get(row) {
+            ' '
+        } else {
+            '!'
+        };
+        writeln!(
+            out,
+            "{marker} {:>4} {:<width$} | {}",
+            row + 1,
+            joined(&expected, row),
+            joined(&actual, row),
+        )
+        .unwrap();
+    }
+
+    out
+}
+
 fn parse_file<T: ColumnType>(
     regx: &Regex,
     path: &Path,
@@ -16,7 +16,12 @@
 
 use std::sync::Arc;
 
-use datafusion::arrow::datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema};
+use datafusion::arrow::datatypes::{
+    DataType,
+    Field as ArrowField,
+    Schema as ArrowSchema,
+    TimeUnit,
+};
 use once_cell::sync::Lazy;
 use pgrepr::oid::FIRST_GLAREDB_BUILTIN_ID;
 use protogen::metastore::types::options::InternalColumnDefinition;
@@ -156,6 +161,7 @@ pub static GLARE_TABLES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         ("external", DataType::Boolean, false),
         ("datasource", DataType::Utf8, false),
         ("access_mode", DataType::Utf8, false), // `SourceAccessMode::as_str()`
+        ("description", DataType::Utf8, true),
     ]),
     oid: 16405,
 });
@@ -186,6 +192,7 @@ pub static GLARE_COLUMNS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
         ("column_ordinal", DataType::UInt32, false),
         ("data_type", DataType::Utf8, false),
         ("is_nullable", DataType::Boolean, false),
+        ("description", DataType::Utf8, true),
     ]),
     oid: 16407,
 });
@@ -254,6 +261,31 @@ pub static GLARE_CACHED_EXTERNAL_DATABASE_TABLES: Lazy<BuiltinTable> = Lazy::new
     oid: 16411,
 });
 
+/// Sessions currently open on the server by the same user on the same
+/// database.
+pub static GLARE_SESSIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    schema: INTERNAL_SCHEMA,
+    name: "sessions",
+    columns: InternalColumnDefinition::from_tuples([
+        ("connection_id", DataType::Utf8, false),
+        ("user_name", DataType::Utf8, false),
+        ("database_name", DataType::Utf8, false),
+        (
+            "connected_at",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        ("status", DataType::Utf8, false),
+        ("query_text", DataType::Utf8, true),
+        (
+            "query_started_at",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            true,
+        ),
+    ]),
+    oid: 16412,
+});
+
 impl BuiltinTable {
     /// Check if this table matches the provided schema and name.
     pub fn matches(&self, schema: &str, name: &str) -> bool {
@@ -284,6 +316,7 @@ impl BuiltinTable {
             &GLARE_SSH_KEYS,
             &GLARE_DEPLOYMENT_METADATA,
             &GLARE_CACHED_EXTERNAL_DATABASE_TABLES,
+            &GLARE_SESSIONS,
         ]
     }
 }
@@ -111,8 +111,8 @@ pub fn validate_table_creds_support(table: &str, creds: &str) -> Result<()> {
         (TableOptionsV0::S3_STORAGE, CredentialsOptions::AWS) |
         // Azure
         (TableOptionsV0::AZURE, CredentialsOptions::AZURE) |
-        // Delta & Iceberg & Lance
-        (TableOptionsV0::DELTA | TableOptionsV0::ICEBERG | TableOptionsV0::LANCE, CredentialsOptions::GCP | CredentialsOptions::AWS | CredentialsOptions::AZURE )
+        // Delta & Iceberg & Lance & ndjson & Avro
+        (TableOptionsV0::DELTA | TableOptionsV0::ICEBERG | TableOptionsV0::LANCE | TableOptionsV0::NDJSON | TableOptionsV0::AVRO, CredentialsOptions::GCP | CredentialsOptions::AWS | CredentialsOptions::AZURE )
     ) {
         Ok(())
     } else {
@@ -18,13 +18,16 @@ use datafusion::sql::TableReference;
 use datafusion::variable::VarType;
 use datafusion_ext::runtime::group_pull_up::RuntimeGroupPullUp;
 use datafusion_ext::session_metrics::SessionMetricsHandler;
+use datafusion_ext::session_registry::SessionRegistry;
 use datafusion_ext::vars::SessionVars;
 use datasources::native::access::NativeTableStorage;
 use distexec::scheduler::Scheduler;
+use parking_lot::Mutex;
 use parser::StatementWithExtensions;
 use pgrepr::format::Format;
 use pgrepr::notice::Notice;
-use pgrepr::types::arrow_to_pg_type;
+use pgrepr::types::{arrow_to_pg_type, pg_to_arrow_type};
+use protogen::metastore::types::catalog::ViewEntry;
 use protogen::metastore::types::service::{CreateFunction, Mutation};
 use protogen::rpcsrv::types::service::{
     InitializeSessionRequest,
@@ -35,10 +38,15 @@ use sqlbuiltins::functions::{BuiltinScalarUDF, FunctionRegistry};
 use tokio_postgres::types::Type as PgType;
 use uuid::Uuid;
 
-use super::{new_datafusion_runtime_env, new_datafusion_session_config_opts};
+use super::{
+    new_datafusion_runtime_env,
+    new_datafusion_session_config_opts,
+    new_query_runtime_env,
+};
 use crate::environment::EnvironmentReader;
 use crate::errors::{internal, ExecError, Result};
 use crate::optimizer::DdlInputOptimizationRule;
+use crate::planner::errors::PlanError;
 use crate::planner::logical_plan::{
     FullObjectReference,
     FullSchemaReference,
@@ -80,6 +88,9 @@ pub struct LocalSessionContext {
     notices: Vec<Notice>,
     /// Functions that are available to the session.
     functions: FunctionRegistry,
+    /// Ids of the views currently being planned, used to detect views that
+    /// (indirectly) reference themselves.
+    planning_views: Mutex<Vec<u32>>,
 }
 
 impl LocalSessionContext {
@@ -91,6 +102,7 @@ impl LocalSessionContext {
         catalog_mutator: CatalogMutator,
         native_tables: NativeTableStorage,
         metrics_handler: SessionMetricsHandler,
+        session_registry: Arc<SessionRegistry>,
         spill_path: Option<PathBuf>,
         task_scheduler: Scheduler,
     ) -> Result<LocalSessionContext> {
@@ -106,7 +118,8 @@ impl LocalSessionContext {
             .with_extension(Arc::new(catalog_mutator))
             .with_extension(Arc::new(native_tables.clone()))
             .with_extension(Arc::new(catalog.get_temp_catalog().clone()))
-            .with_extension(Arc::new(task_scheduler.clone()));
+            .with_extension(Arc::new(task_scheduler.clone()))
+            .with_extension(session_registry);
 
         let state = SessionState::new_with_config_rt(conf, Arc::new(runtime))
             .add_optimizer_rule(Arc::new(DdlInputOptimizationRule::new()))
@@ -128,6 +141,7 @@ impl LocalSessionContext {
             task_scheduler,
             notices: Vec::new(),
             functions,
+            planning_views: Mutex::new(Vec::new()),
         })
     }
 
@@ -161,6 +175,14 @@ impl LocalSessionContext {
             .with_extension(Arc::new(CatalogMutator::empty()))
             .with_extension(Arc::new(self.get_native_tables().clone()))
             .with_extension(Arc::new(catalog.get_temp_catalog().clone()));
+        if let Some(registry) = self
+            .df_ctx
+            .state()
+            .config()
+            .get_extension::<SessionRegistry>()
+        {
+            conf = conf.with_extension(registry);
+        }
 
         let state = SessionState::new_with_config_rt(conf, runtime)
             .add_optimizer_rule(Arc::new(DdlInputOptimizationRule::new()))
@@ -261,6 +283,25 @@ impl LocalSessionContext {
             .expect("catalog mutator should be present")
     }
 
+    /// Mark `view` as being planned until the returned guard is dropped.
+    ///
+    /// Errors if the view is already being planned, meaning the view
+    /// references itself.
+    pub(crate) fn begin_view_planning(
+        &self,
+        view: &ViewEntry,
+    ) -> Result<ViewPlanningGuard<'_>, PlanError> {
+        let mut planning = self.planning_views.lock();
+        if planning.contains(&view.meta.id) {
+            return Err(PlanError::RecursiveView(view.meta.name.clone()));
+        }
+        planning.push(view.meta.id);
+        Ok(ViewPlanningGuard {
+            planning_views: &self.planning_views,
+            id: view.meta.id,
+        })
+    }
+
     /// Get a reference to the session variables.
     pub fn get_session_vars(&self) -> SessionVars {
         let cfg = self.df_ctx.copied_config();
@@ -294,7 +335,7 @@ impl LocalSessionContext {
         &mut self,
         name: String,
         stmt: Option<StatementWithExtensions>,
-        _params: Vec<i32>, // TODO: We can use these for providing types for parameters.
+        params: Vec<i32>,
     ) -> Result<()> {
         // Refresh the cached catalog state if necessary
         self.maybe_refresh_state().await?;
@@ -309,12 +350,21 @@ impl LocalSessionContext {
             ));
         }
 
-        let stmt = PreparedStatement::build(stmt, self).await?;
+        let stmt = PreparedStatement::build(stmt, &params, self).await?;
         self.prepared.insert(name, stmt);
 
         Ok(())
     }
 
+    /// Plan a statement without storing it on the session.
+    pub async fn plan_prepared_statement(
+        &mut self,
+        stmt: Option<StatementWithExtensions>,
+    ) -> Result<PreparedStatement> {
+        self.maybe_refresh_state().await?;
+        PreparedStatement::build(stmt, &[], self).await
+    }
+
     /// Bind a planned prepared statement to a portal.
     ///
     /// Internally this will create a logical plan for the statement and store
@@ -342,6 +392,7 @@ impl LocalSessionContext {
         };
 
         // Replace placeholders if necessary.
+        let params = stmt.check_parameters(params)?;
         if let Some(plan) = &mut stmt.plan {
             plan.replace_placeholders(params)?;
         }
@@ -408,9 +459,29 @@ impl LocalSessionContext {
         }
     }
 
+    /// Get the datafusion session state, configured with the session's batch
+    /// size.
+    pub(crate) fn df_state(&self) -> SessionState {
+        let mut state = self.df_ctx.state();
+        state.config_mut().options_mut().execution.batch_size =
+            self.get_session_vars().batch_size();
+        state
+    }
+
     /// Get a datafusion task context to use for physical plan execution.
+    ///
+    /// If `query_memory_limit_bytes` is set, the task context gets its own
+    /// memory pool with that limit so that large sorts and aggregates spill to
+    /// disk.
     pub(crate) fn task_context(&self) -> Arc<TaskContext> {
-        self.df_ctx.task_ctx()
+        let context = TaskContext::from(&self.df_state());
+        match self.get_session_vars().query_memory_limit_bytes() {
+            0 => Arc::new(context),
+            limit => {
+                let runtime = new_query_runtime_env(&context.runtime_env(), limit);
+                Arc::new(context.with_runtime(Arc::new(runtime)))
+            }
+        }
     }
 
     /// Resolve schema reference.
@@ -488,9 +559,16 @@ pub struct PreparedStatement {
 
 impl PreparedStatement {
     /// Create and plan a new prepared statement.
+    ///
+    /// `param_oids` are the parameter types provided by the client, indexed by
+    /// parameter position. They're only used for parameters whose type
+    /// couldn't be inferred from the statement. An oid of 0 means the client
+    /// left the type unspecified. Parameters with no known type at all are
+    /// treated as text, like postgres does for untyped literals.
     // TODO: Not sure if we want to delay the planning portion.
     async fn build(
         mut stmt: Option<StatementWithExtensions>,
+        param_oids: &[i32],
         ctx: &LocalSessionContext,
     ) -> Result<Self> {
         if let Some(inner) = stmt.take() {
@@ -510,13 +588,17 @@ impl PreparedStatement {
             };
 
             // Convert inferred arrow types for parameters into their associated
-            // pg type.
+            // pg type, falling back to the types provided by the client.
             let parameter_types: HashMap<_, _> = plan
                 .get_parameter_types()?
                 .into_iter()
                 .map(|(id, arrow_type)| {
-                    let typ = arrow_type.map(|typ| (arrow_to_pg_type(&typ, None), typ));
-                    (id, typ)
+                    let typ = match arrow_type {
+                        Some(typ) => (arrow_to_pg_type(&typ, None), typ),
+                        None => client_param_type(&id, param_oids)
+                            .unwrap_or((PgType::TEXT, DataType::Utf8)),
+                    };
+                    (id, Some(typ))
                 })
                 .collect();
 
@@ -554,6 +636,82 @@ impl PreparedStatement {
     pub fn input_paramaters(&self) -> Option<&HashMap<String, Option<(PgType, DataType)>>> {
         self.parameter_types.as_ref()
     }
+
+    /// Returns the postgres types of the input parameters ordered by
+    /// position.
+    pub fn input_parameter_pg_types(&self) -> Vec<PgType> {
+        let types = match &self.parameter_types {
+            Some(types) => types,
+            None => return Vec::new(),
+        };
+        (1..=types.len())
+            .map(|idx| match types.get(&format!("${idx}")) {
+                Some(Some((pg_type, _))) => pg_type.clone(),
+                _ => PgType::TEXT,
+            })
+            .collect()
+    }
+
+    /// Check that the provided values match the parameters of the statement,
+    /// returning the values to substitute into the plan.
+    ///
+    /// Untyped nulls are converted to nulls of the parameter's type.
+    pub(crate) fn check_parameters(&self, params: Vec<ScalarValue>) -> Result<Vec<ScalarValue>> {
+        let types = match &self.parameter_types {
+            Some(types) => types,
+            None => return Ok(params),
+        };
+
+        if params.len() != types.len() {
+            return Err(ExecError::InvalidParameterCount {
+                expected: types.len(),
+                got: params.len(),
+            });
+        }
+
+        params
+            .into_iter()
+            .enumerate()
+            .map(|(idx, scalar)| {
+                let param = format!("${}", idx + 1);
+                let expected = match types.get(&param) {
+                    Some(Some((_, typ))) => typ,
+                    _ => return Ok(scalar),
+                };
+                match scalar {
+                    ScalarValue::Null => Ok(ScalarValue::try_from(expected)?),
+                    scalar if &scalar.data_type() == expected => Ok(scalar),
+                    scalar => Err(ExecError::InvalidParameterType {
+                        param,
+                        expected: expected.clone(),
+                        got: scalar.data_type(),
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Get the type the client provided for a parameter, if any.
+fn client_param_type(id: &str, param_oids: &[i32]) -> Option<(PgType, DataType)> {
+    let idx: usize = id.strip_prefix('$')?.parse().ok()?;
+    let oid = *param_oids.get(idx.checked_sub(1)?)?;
+    let pg_type = PgType::from_oid(u32::try_from(oid).ok()?)?;
+    let arrow_type = pg_to_arrow_type(&pg_type)?;
+    Some((pg_type, arrow_type))
+}
+
+/// Marks a view as being planned, see
+/// [`LocalSessionContext::begin_view_planning`].
+pub(crate) struct ViewPlanningGuard<'a> {
+    planning_views: &'a Mutex<Vec<u32>>,
+    id: u32,
+}
+
+impl Drop for ViewPlanningGuard<'_> {
+    fn drop(&mut self) {
+        self.planning_views.lock().retain(|id| *id != self.id);
+    }
 }
 
 #[derive(Debug, Clone)]
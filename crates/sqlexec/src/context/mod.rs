@@ -15,7 +15,7 @@ use std::sync::Arc;
 use catalog::session_catalog::SessionCatalog;
 use datafusion::config::{CatalogOptions, ConfigOptions, Extensions, OptimizerOptions};
 use datafusion::execution::disk_manager::DiskManagerConfig;
-use datafusion::execution::memory_pool::GreedyMemoryPool;
+use datafusion::execution::memory_pool::{FairSpillPool, GreedyMemoryPool};
 use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
 use datafusion_ext::vars::SessionVars;
 use datasources::object_store::init_session_registry;
@@ -73,6 +73,20 @@ pub(crate) fn new_datafusion_runtime_env(
     Ok(runtime)
 }
 
+/// Create a runtime env for a single query that shares everything with
+/// `runtime` except for the memory pool, which is limited to `limit` bytes.
+///
+/// Operators that support spilling (sorts, hash aggregates) write to the disk
+/// manager of `runtime` once they hit the limit instead of failing.
+pub(crate) fn new_query_runtime_env(runtime: &RuntimeEnv, limit: usize) -> RuntimeEnv {
+    RuntimeEnv {
+        memory_pool: Arc::new(FairSpillPool::new(limit)),
+        disk_manager: runtime.disk_manager.clone(),
+        cache_manager: runtime.cache_manager.clone(),
+        object_store_registry: runtime.object_store_registry.clone(),
+    }
+}
+
 /// Create a new datafusion config opts common to both local and remote
 /// sessions.
 // TODO: Remove `vars`.
@@ -11,6 +11,7 @@ use datafusion::datasource::file_format::parquet::ParquetFormat;
 use datafusion::datasource::TableProvider;
 use datafusion::prelude::SessionContext;
 use datafusion_ext::functions::{DefaultTableContextProvider, FuncParamValue};
+use datasources::avro::avro_table;
 use datasources::bigquery::{BigQueryAccessor, BigQueryTableAccess};
 use datasources::bson::table::bson_streaming_table;
 use datasources::cassandra::CassandraTableProvider;
@@ -19,6 +20,7 @@ use datasources::common::url::DatasourceUrl;
 use datasources::debug::DebugTableType;
 use datasources::excel::table::ExcelTableProvider;
 use datasources::excel::ExcelTable;
+use datasources::json::ndjson::ndjson_streaming_table;
 use datasources::json::table::json_streaming_table;
 use datasources::lake::delta::access::{load_table_direct, DeltaLakeAccessor};
 use datasources::lake::iceberg::table::IcebergTable;
@@ -710,6 +712,37 @@ impl<'a> ExternalDispatcher<'a> {
                 )
                 .await?)
             }
+            TableOptionsV0::Ndjson(TableOptionsObjectStore {
+                location,
+                storage_options,
+                schema_sample_size,
+                ..
+            }) => {
+                let source_url = DatasourceUrl::try_new(location)?;
+                let store_access = storage_options_into_store_access(&source_url, storage_options)?;
+                let ignore_errors = storage_options
+                    .inner
+                    .get("ignore_errors")
+                    .map(|val| val.parse::<bool>().unwrap_or(false))
+                    .unwrap_or(false);
+                Ok(ndjson_streaming_table(
+                    store_access,
+                    source_url,
+                    schema,
+                    schema_sample_size.to_owned(),
+                    ignore_errors,
+                )
+                .await?)
+            }
+            TableOptionsV0::Avro(TableOptionsObjectStore {
+                location,
+                storage_options,
+                ..
+            }) => {
+                let source_url = DatasourceUrl::try_new(location)?;
+                let store_access = storage_options_into_store_access(&source_url, storage_options)?;
+                Ok(avro_table(store_access, source_url, &self.df_ctx.state()).await?)
+            }
             TableOptionsV0::Cassandra(TableOptionsCassandra {
                 host,
                 keyspace,
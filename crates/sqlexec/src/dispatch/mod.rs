@@ -11,6 +11,9 @@ use datafusion::datasource::{TableProvider, ViewTable};
 use datafusion::logical_expr::{LogicalPlan, LogicalPlanBuilder};
 use datafusion::prelude::{Column, Expr, SessionContext as DfSessionContext};
 use datafusion_ext::functions::{DefaultTableContextProvider, FuncParamValue};
+use datafusion_ext::planner::TableVersion;
+use datafusion_ext::session_registry::{SessionOwner, SessionRegistry};
+use datafusion_ext::vars::SessionVars;
 use datasources::native::access::NativeTableStorage;
 use parser::GlareDbParser;
 use protogen::metastore::types::catalog::{DatabaseEntry, FunctionEntry, TableEntry, ViewEntry};
@@ -80,6 +83,8 @@ pub enum DispatchError {
     #[error(transparent)]
     JsonDatasource(#[from] datasources::json::errors::JsonError),
     #[error(transparent)]
+    AvroDatasource(#[from] datasources::avro::errors::AvroError),
+    #[error(transparent)]
     ClickhouseDatasource(#[from] datasources::clickhouse::errors::ClickhouseError),
     #[error(transparent)]
     NativeDatasource(#[from] datasources::native::errors::NativeError),
@@ -111,20 +116,26 @@ pub enum DispatchError {
 /// Trait for planning views.
 ///
 /// Currently views aren't that sophisticated as we're only storing the SQL
-/// statement and column aliases. We don't track view dependencies.
+/// statement and column aliases. We don't track view dependencies, views
+/// referencing themselves are only detected while planning.
 #[async_trait]
 pub trait ViewPlanner: Send + Sync {
-    /// Plan a view from SQL, producing a logical plan with the provided
-    /// aliases.
+    /// Plan a view from its SQL, producing a logical plan with the view's
+    /// column aliases.
     ///
     /// If no column aliases are provided, then columns should be returned
     /// as-is.
-    async fn plan_view(&self, sql: &str, col_aliases: &[String]) -> Result<LogicalPlan, PlanError>;
+    async fn plan_view(&self, view: &ViewEntry) -> Result<LogicalPlan, PlanError>;
 }
 
 #[async_trait]
 impl ViewPlanner for LocalSessionContext {
-    async fn plan_view(&self, sql: &str, col_aliases: &[String]) -> Result<LogicalPlan, PlanError> {
+    async fn plan_view(&self, view: &ViewEntry) -> Result<LogicalPlan, PlanError> {
+        // Errors if the view is already being expanded further up the plan.
+        let _guard = self.begin_view_planning(view)?;
+
+        let (sql, col_aliases) = (&view.sql, &view.columns);
+
         // TODO: Instead of doing late planning, we should instead try to insert
         // the contents of the view into the parent query prior to any planning.
         let mut statements = GlareDbParser::parse_sql(sql)?;
@@ -199,9 +210,22 @@ impl<'a> Dispatcher<'a> {
 
         // Builtin tables
         if tbl.meta.builtin {
-            return SystemTableDispatcher::new(self.catalog, self.tables, self.function_registry)
-                .dispatch(tbl)
-                .await;
+            let state = self.df_ctx.state();
+            let config = state.config();
+            let owner = config
+                .options()
+                .extensions
+                .get::<SessionVars>()
+                .map(SessionOwner::from_vars);
+            let sessions = config.get_extension::<SessionRegistry>().zip(owner);
+            return SystemTableDispatcher::new(
+                self.catalog,
+                self.tables,
+                self.function_registry,
+                sessions,
+            )
+            .dispatch(tbl)
+            .await;
         }
 
         // External tables
@@ -221,11 +245,30 @@ impl<'a> Dispatcher<'a> {
         Ok(table.into_table_provider())
     }
 
+    /// Dispatch a previous version of a table.
+    ///
+    /// Only native tables keep their history.
+    pub async fn dispatch_table_at_version(
+        &self,
+        tbl: &TableEntry,
+        version: TableVersion,
+    ) -> Result<Arc<dyn TableProvider>> {
+        if tbl.meta.is_temp || tbl.meta.builtin || tbl.meta.external {
+            return Err(DispatchError::String(format!(
+                "Time travel is only supported for native tables, '{}' is not a native table",
+                tbl.meta.name
+            )));
+        }
+
+        let table = self.tables.load_table_at_version(tbl, version).await?;
+        Ok(table.into_table_provider())
+    }
+
     /// Dispatch a view.
     pub async fn dispatch_view(&self, view: &ViewEntry) -> Result<Arc<dyn TableProvider>> {
         let plan = self
             .view_planner
-            .plan_view(&view.sql, &view.columns)
+            .plan_view(view)
             .await
             .map_err(|e| DispatchError::ViewPlanning(Box::new(e)))?;
         Ok(Arc::new(ViewTable::try_new(plan, None)?))
@@ -1,10 +1,17 @@
 use std::sync::Arc;
 
 use catalog::session_catalog::SessionCatalog;
-use datafusion::arrow::array::{BooleanBuilder, ListBuilder, StringBuilder, UInt32Builder};
+use datafusion::arrow::array::{
+    BooleanBuilder,
+    ListBuilder,
+    StringBuilder,
+    TimestampMicrosecondBuilder,
+    UInt32Builder,
+};
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::datasource::{MemTable, TableProvider};
 use datafusion::logical_expr::TypeSignature;
+use datafusion_ext::session_registry::{SessionOwner, SessionRegistry};
 use datasources::common::ssh::key::SshKey;
 use datasources::common::ssh::SshConnectionParameters;
 use datasources::native::access::NativeTableStorage;
@@ -20,6 +27,7 @@ use sqlbuiltins::builtins::{
     GLARE_DEPLOYMENT_METADATA,
     GLARE_FUNCTIONS,
     GLARE_SCHEMAS,
+    GLARE_SESSIONS,
     GLARE_SSH_KEYS,
     GLARE_TABLES,
     GLARE_TUNNELS,
@@ -35,6 +43,10 @@ pub struct SystemTableDispatcher<'a> {
     catalog: &'a SessionCatalog,
     tables: &'a NativeTableStorage,
     function_registry: &'a FunctionRegistry,
+    /// Sessions open on the server along with the owner of the session
+    /// dispatching, if known. Only the sessions of the same owner are
+    /// listed.
+    sessions: Option<(Arc<SessionRegistry>, SessionOwner)>,
 }
 
 impl<'a> SystemTableDispatcher<'a> {
@@ -42,11 +54,13 @@ impl<'a> SystemTableDispatcher<'a> {
         catalog: &'a SessionCatalog,
         tables: &'a NativeTableStorage,
         function_registry: &'a FunctionRegistry,
+        sessions: Option<(Arc<SessionRegistry>, SessionOwner)>,
     ) -> Self {
         SystemTableDispatcher {
             catalog,
             tables,
             function_registry,
+            sessions,
         }
     }
 
@@ -78,6 +92,8 @@ impl<'a> SystemTableDispatcher<'a> {
             Arc::new(self.build_ssh_keys()?)
         } else if GLARE_DEPLOYMENT_METADATA.matches(schema, name) {
             Arc::new(self.build_glare_deployment_metadata()?)
+        } else if GLARE_SESSIONS.matches(schema, name) {
+            Arc::new(self.build_glare_sessions())
         } else if GLARE_CACHED_EXTERNAL_DATABASE_TABLES.matches(schema, name) {
             self.load_persisted_table(&GLARE_CACHED_EXTERNAL_DATABASE_TABLES)
                 .await?
@@ -291,6 +307,7 @@ impl<'a> SystemTableDispatcher<'a> {
         let mut external = BooleanBuilder::new();
         let mut datasource = StringBuilder::new();
         let mut access_mode = StringBuilder::new();
+        let mut description = StringBuilder::new();
 
         for table in self
             .catalog
@@ -322,6 +339,7 @@ impl<'a> SystemTableDispatcher<'a> {
 
             datasource.append_value(table.options.as_str());
             access_mode.append_value(table.access_mode.as_str());
+            description.append_option(table.comment.as_deref());
         }
 
         // Append temporary tables.
@@ -336,6 +354,7 @@ impl<'a> SystemTableDispatcher<'a> {
             external.append_value(table.meta.external);
             datasource.append_value(table.options.as_str());
             access_mode.append_value(SourceAccessMode::ReadWrite.as_str());
+            description.append_option(table.comment.as_deref());
         }
 
         let batch = RecordBatch::try_new(
@@ -350,6 +369,7 @@ impl<'a> SystemTableDispatcher<'a> {
                 Arc::new(external.finish()),
                 Arc::new(datasource.finish()),
                 Arc::new(access_mode.finish()),
+                Arc::new(description.finish()),
             ],
         )
         .unwrap();
@@ -367,6 +387,7 @@ impl<'a> SystemTableDispatcher<'a> {
         let mut column_ordinal = UInt32Builder::new();
         let mut data_type = StringBuilder::new();
         let mut is_nullable = BooleanBuilder::new();
+        let mut description = StringBuilder::new();
 
         for table in self
             .catalog
@@ -396,6 +417,7 @@ impl<'a> SystemTableDispatcher<'a> {
                 column_ordinal.append_value(i as u32);
                 data_type.append_value(col.arrow_type.to_string());
                 is_nullable.append_value(col.nullable);
+                description.append_option(ent.column_comments.get(&col.name));
             }
         }
 
@@ -409,6 +431,7 @@ impl<'a> SystemTableDispatcher<'a> {
                 Arc::new(column_ordinal.finish()),
                 Arc::new(data_type.finish()),
                 Arc::new(is_nullable.finish()),
+                Arc::new(description.finish()),
             ],
         )
         .unwrap();
@@ -599,6 +622,49 @@ impl<'a> SystemTableDispatcher<'a> {
 
         Ok(MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap())
     }
+
+    fn build_glare_sessions(&self) -> MemTable {
+        let arrow_schema = Arc::new(GLARE_SESSIONS.arrow_schema());
+
+        let mut connection_id = StringBuilder::new();
+        let mut user_name = StringBuilder::new();
+        let mut database_name = StringBuilder::new();
+        let mut connected_at = TimestampMicrosecondBuilder::new().with_timezone("UTC");
+        let mut status = StringBuilder::new();
+        let mut query_text = StringBuilder::new();
+        let mut query_started_at = TimestampMicrosecondBuilder::new().with_timezone("UTC");
+
+        let sessions = self
+            .sessions
+            .as_ref()
+            .map(|(sessions, owner)| sessions.sessions(*owner))
+            .unwrap_or_default();
+        for session in sessions {
+            connection_id.append_value(session.connection_id.to_string());
+            user_name.append_value(session.user_name);
+            database_name.append_value(session.database_name);
+            connected_at.append_value(session.connected_at.timestamp_micros());
+            status.append_value(session.status.as_str());
+            query_text.append_option(session.query_text);
+            query_started_at.append_option(session.query_started_at.map(|t| t.timestamp_micros()));
+        }
+
+        let batch = RecordBatch::try_new(
+            arrow_schema.clone(),
+            vec![
+                Arc::new(connection_id.finish()),
+                Arc::new(user_name.finish()),
+                Arc::new(database_name.finish()),
+                Arc::new(connected_at.finish()),
+                Arc::new(status.finish()),
+                Arc::new(query_text.finish()),
+                Arc::new(query_started_at.finish()),
+            ],
+        )
+        .unwrap();
+
+        MemTable::try_new(arrow_schema, vec![vec![batch]]).unwrap()
+    }
 }
 fn sig_to_string_repr(sig: &TypeSignature) -> Vec<String> {
     match sig {
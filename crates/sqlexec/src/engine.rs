@@ -7,6 +7,8 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use catalog::session_catalog::{ResolveConfig, SessionCatalog};
+use datafusion_ext::session_metrics::ServerMetrics;
+use datafusion_ext::session_registry::SessionRegistry;
 use datafusion_ext::vars::SessionVars;
 use datasources::common::errors::DatasourceCommonError;
 use datasources::common::url::{DatasourceUrl, DatasourceUrlType};
@@ -171,19 +173,34 @@ impl EngineStorageConfig {
                         }
                     }
                     DatasourceUrlType::Azure => {
-                        let account_name = opts.get("account_name").cloned().unwrap_or_else(|| {
-                            std::env::var(AzureConfigKey::AccountName.as_ref().to_uppercase())
-                                .expect(
-                                    "'account_name' in provided storage options or 'AZURE_STORAGE_ACCOUNT_NAME' as env var"
-                                )
+                        let account_name = opts
+                            .get("account_name")
+                            .cloned()
+                            .or_else(|| {
+                                env_var_any(&[
+                                    AzureConfigKey::AccountName.as_ref(),
+                                    "azure_storage_account",
+                                ])
+                            })
+                            .expect(
+                                "'account_name' in provided storage options or 'AZURE_STORAGE_ACCOUNT_NAME' as env var"
+                            );
+
+                        // Either an access key or a SAS token may be used for
+                        // authentication.
+                        let access_key = opts.get("access_key").cloned().or_else(|| {
+                            env_var_any(&[AzureConfigKey::AccessKey.as_ref(), "azure_storage_key"])
                         });
+                        let sas_token = opts
+                            .get("sas_token")
+                            .cloned()
+                            .or_else(|| env_var_any(&["azure_storage_sas_token"]));
 
-                        let access_key = opts.get("access_key").cloned().unwrap_or_else(|| {
-                            std::env::var(AzureConfigKey::AccessKey.as_ref().to_uppercase())
-                                .expect(
-                                    "'access_key' in provided storage options or 'AZURE_STORAGE_ACCOUNT_KEY' as env var"
-                                )
-                        });
+                        if access_key.is_none() && sas_token.is_none() {
+                            return Err(ExecError::InvalidStorageConfig(
+                                "Expected either 'access_key' or 'sas_token' in provided storage options, or 'AZURE_STORAGE_ACCOUNT_KEY' or 'AZURE_STORAGE_SAS_TOKEN' as env var",
+                            ));
+                        }
 
                         // Extract bucket (azure container) from the location URL
                         let container_name = opts
@@ -196,6 +213,7 @@ impl EngineStorageConfig {
                             conf: StorageConfig::Azure {
                                 account_name,
                                 access_key,
+                                sas_token,
                                 container_name,
                             },
                         }
@@ -250,11 +268,12 @@ impl EngineStorageConfig {
 
     /// Create a (potentially prefixed) object store rooted at the location URL.
     ///
-    /// First creates an object store from the underlying storage config, and then
+    /// First gets an object store for the underlying storage config, and then
     /// wraps that up with a `PrefixStore` for non-local storage if there is a path
-    /// specified.
+    /// specified. Stores for remote storage are shared with other sessions using
+    /// the same config.
     pub fn new_object_store(&self) -> Result<Arc<dyn ObjectStore>, ObjectStoreError> {
-        let store = self.conf.new_object_store()?;
+        let store = self.conf.cached_object_store()?;
 
         if matches!(
             self.conf,
@@ -307,6 +326,13 @@ impl EngineStorageConfig {
     }
 }
 
+/// Get the value of the first set environment variable out of the provided
+/// keys. Keys are upper-cased before lookup.
+fn env_var_any(keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| std::env::var(key.to_uppercase()).ok())
+}
+
 /// Hold configuration and clients needed to create database sessions.
 /// An engine is able to support multiple [`Session`]'s across multiple db instances
 pub struct Engine {
@@ -320,6 +346,10 @@ pub struct Engine {
     spill_path: Option<PathBuf>,
     /// Number of active sessions.
     session_counter: Arc<AtomicU64>,
+    /// Counters aggregated across all sessions.
+    server_metrics: Arc<ServerMetrics>,
+    /// Sessions currently open.
+    session_registry: Arc<SessionRegistry>,
     /// Scheduler for running tasks (physical plan).
     task_scheduler: Scheduler,
     /// Task executors.
@@ -385,6 +415,8 @@ impl Engine {
             storage,
             spill_path,
             session_counter: Arc::new(AtomicU64::new(0)),
+            server_metrics: Arc::new(ServerMetrics::default()),
+            session_registry: Arc::new(SessionRegistry::default()),
             task_scheduler,
             _task_executors: task_executors,
             tmp_dir: None,
@@ -396,6 +428,16 @@ impl Engine {
         self.tracker.clone()
     }
 
+    /// Returns the counters aggregated across all sessions of this engine.
+    pub fn server_metrics(&self) -> Arc<ServerMetrics> {
+        self.server_metrics.clone()
+    }
+
+    /// Returns the registry of sessions currently open on this engine.
+    pub fn session_registry(&self) -> Arc<SessionRegistry> {
+        self.session_registry.clone()
+    }
+
     pub async fn from_storage(opts: EngineStorage) -> Result<Engine> {
         match opts {
             EngineStorage::Memory => Self::from_data_dir(None).await,
@@ -472,6 +514,19 @@ impl Engine {
         self.session_counter.load(Ordering::Relaxed)
     }
 
+    /// Check that the engine can reach its metastore and storage.
+    ///
+    /// Errors if either is unavailable.
+    pub async fn health_check(&self) -> Result<()> {
+        let metastore = self.supervisor.init_client(Uuid::nil()).await?;
+        metastore.refresh_cached_state().await?;
+
+        let store = self.storage.new_object_store()?;
+        store.list_with_delimiter(None).await?;
+
+        Ok(())
+    }
+
     /// Create a new local session, initializing it with the provided session
     /// variables.
     // TODO: This is _very_ easy to mess up with the vars since we implement
@@ -528,6 +583,8 @@ impl Engine {
             metastore.into(),
             native,
             self.tracker.clone(),
+            self.server_metrics.clone(),
+            self.session_registry.clone(),
             self.spill_path.clone(),
             self.task_scheduler.clone(),
         )
@@ -27,9 +27,46 @@ pub enum ExecError {
     #[error("Unknown portal with name: {0}")]
     UnknownPortal(String),
 
+    #[error("Invalid number of parameters provided. Expected: {expected}, got: {got}")]
+    InvalidParameterCount { expected: usize, got: usize },
+
+    #[error("Invalid value for parameter {param}. Expected type: {expected}, got: {got}")]
+    InvalidParameterType {
+        param: String,
+        expected: datafusion::arrow::datatypes::DataType,
+        got: datafusion::arrow::datatypes::DataType,
+    },
+
     #[error("Empty search path, unable to resolve schema")]
     EmptySearchPath,
 
+    #[error("Query canceled")]
+    QueryCanceled,
+
+    #[error("canceling statement due to statement timeout")]
+    StatementTimeout,
+
+    #[error("current transaction is aborted, commands ignored until end of transaction block")]
+    TransactionAborted,
+
+    #[error("Cannot execute {0} in a read only session")]
+    ReadOnlySession(String),
+
+    #[error("{0} can only be used in transaction blocks")]
+    NotInTransactionBlock(&'static str),
+
+    #[error("savepoint \"{0}\" does not exist")]
+    UnknownSavepoint(String),
+
+    #[error("cannot roll back {0}: {1}")]
+    IrreversibleChanges(String, &'static str),
+
+    #[error("cursor \"{0}\" does not exist")]
+    UnknownCursor(String),
+
+    #[error("cursor \"{0}\" already exists")]
+    DuplicateCursor(String),
+
     #[error("Unexpected entry type; got: {got}, want: {want}")]
     UnexpectedEntryType {
         got: protogen::metastore::types::catalog::EntryType,
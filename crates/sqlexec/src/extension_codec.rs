@@ -27,6 +27,7 @@ use uuid::Uuid;
 use crate::planner::physical_plan::alter_database::AlterDatabaseExec;
 use crate::planner::physical_plan::alter_table::AlterTableExec;
 use crate::planner::physical_plan::alter_tunnel_rotate_keys::AlterTunnelRotateKeysExec;
+use crate::planner::physical_plan::cancel_query::CancelQueryExec;
 use crate::planner::physical_plan::client_recv::ClientExchangeRecvExec;
 use crate::planner::physical_plan::copy_to::CopyToExec;
 use crate::planner::physical_plan::create_credentials::CreateCredentialsExec;
@@ -45,11 +46,20 @@ use crate::planner::physical_plan::drop_schemas::DropSchemasExec;
 use crate::planner::physical_plan::drop_tables::DropTablesExec;
 use crate::planner::physical_plan::drop_tunnel::DropTunnelExec;
 use crate::planner::physical_plan::drop_views::DropViewsExec;
-use crate::planner::physical_plan::insert::InsertExec;
+use crate::planner::physical_plan::insert::{
+    InsertExec,
+    OnConflict,
+    OnConflictAction,
+    SchemaEvolution,
+};
+use crate::planner::physical_plan::insert_overwrite::InsertOverwriteExec;
+use crate::planner::physical_plan::optimize_table::OptimizeTableExec;
 use crate::planner::physical_plan::remote_scan::{ProviderReference, RemoteScanExec};
 use crate::planner::physical_plan::set_var::SetVarExec;
 use crate::planner::physical_plan::show_var::ShowVarExec;
+use crate::planner::physical_plan::truncate_tables::TruncateTablesExec;
 use crate::planner::physical_plan::update::UpdateExec;
+use crate::planner::physical_plan::vacuum_table::VacuumTableExec;
 use crate::planner::physical_plan::values::ExtValuesExec;
 use crate::remote::provider_cache::ProviderCache;
 
@@ -236,6 +246,7 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     or_replace: ext.or_replace,
                     arrow_schema: Arc::new(schema),
                     source: inputs.first().cloned(),
+                    partition_by: ext.partition_by,
                 })
             }
             proto::ExecutionPlanExtensionType::CreateTempTableExec(ext) => {
@@ -396,7 +407,53 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                         DataFusionError::Internal(format!("Missing proivder for id: {provider_id}"))
                     })?;
 
+                let schema_evolution = ext
+                    .schema_evolution
+                    .map(|evolution| {
+                        Ok::<_, DataFusionError>(SchemaEvolution {
+                            schema: evolution.schema,
+                            name: evolution.name,
+                            operations: evolution
+                                .operations
+                                .into_iter()
+                                .map(|op| op.try_into())
+                                .collect::<Result<_, _>>()?,
+                        })
+                    })
+                    .transpose()?;
+
+                let on_conflict = ext
+                    .on_conflict
+                    .map(|on_conflict| {
+                        let action = if on_conflict.do_update {
+                            let mut assignments = Vec::with_capacity(on_conflict.assignments.len());
+                            for assignment in on_conflict.assignments {
+                                let expr = assignment.expr.ok_or_else(|| {
+                                    DataFusionError::Internal("missing expression".to_string())
+                                })?;
+                                let expr = parse_expr(&expr, registry)?;
+                                assignments.push((assignment.column, expr));
+                            }
+                            let predicate = on_conflict
+                                .predicate
+                                .map(|expr| parse_expr(&expr, registry))
+                                .transpose()?;
+                            OnConflictAction::DoUpdate {
+                                assignments,
+                                predicate,
+                            }
+                        } else {
+                            OnConflictAction::DoNothing
+                        };
+                        Ok::<_, DataFusionError>(OnConflict {
+                            columns: on_conflict.columns,
+                            action,
+                        })
+                    })
+                    .transpose()?;
+
                 Arc::new(InsertExec {
+                    catalog_version: ext.catalog_version,
                     provider: ProviderReference::Provider(prov),
                     source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                         inputs
@@ -406,6 +463,29 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                             })?
                             .clone(),
                     )),
+                    copy_from: ext.copy_from,
+                    schema_evolution,
+                    returning: ext.returning,
+                    on_conflict,
+                })
+            }
+            proto::ExecutionPlanExtensionType::InsertOverwriteExec(ext) => {
+                let replace_where: Option<Expr> = ext
+                    .replace_where
+                    .map(|expr| parse_expr(&expr, registry))
+                    .transpose()?;
+                Arc::new(InsertOverwriteExec {
+                    table: ext
+                        .table
+                        .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+                        .try_into()?,
+                    source: inputs
+                        .first()
+                        .ok_or_else(|| {
+                            DataFusionError::Internal("missing input source".to_string())
+                        })?
+                        .clone(),
+                    replace_where,
                 })
             }
             proto::ExecutionPlanExtensionType::DeleteExec(ext) => {
@@ -421,6 +501,38 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     where_expr,
                 })
             }
+            proto::ExecutionPlanExtensionType::VacuumTableExec(ext) => Arc::new(VacuumTableExec {
+                table: ext
+                    .table
+                    .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+                    .try_into()?,
+                retain_hours: ext.retain_hours,
+                dry_run: ext.dry_run,
+            }),
+            proto::ExecutionPlanExtensionType::OptimizeTableExec(ext) => {
+                Arc::new(OptimizeTableExec {
+                    table: ext
+                        .table
+                        .ok_or_else(|| DataFusionError::Internal("missing table".to_string()))?
+                        .try_into()?,
+                    zorder_by: ext.zorder_by,
+                })
+            }
+            proto::ExecutionPlanExtensionType::TruncateTablesExec(ext) => {
+                Arc::new(TruncateTablesExec {
+                    tables: ext
+                        .tables
+                        .into_iter()
+                        .map(|t| t.try_into())
+                        .collect::<Result<_, _>>()?,
+                })
+            }
+            proto::ExecutionPlanExtensionType::CancelQueryExec(ext) => {
+                let connection_id = Uuid::from_slice(&ext.connection_id).map_err(|e| {
+                    DataFusionError::Plan(format!("failed to decode connection id: {e}"))
+                })?;
+                Arc::new(CancelQueryExec { connection_id })
+            }
             proto::ExecutionPlanExtensionType::CopyToExec(ext) => Arc::new(CopyToExec {
                 format: ext
                     .format
@@ -553,6 +665,7 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                 if_not_exists: exec.if_not_exists,
                 or_replace: exec.or_replace,
                 arrow_schema: Some(exec.arrow_schema.clone().try_into()?),
+                partition_by: exec.partition_by.clone(),
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<CreateTempTableExec>() {
             proto::ExecutionPlanExtensionType::CreateTempTableExec(proto::CreateTempTableExec {
@@ -723,8 +836,62 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                 }
             };
 
+            let on_conflict = match &exec.on_conflict {
+                Some(on_conflict) => {
+                    let (do_update, assignments, predicate) = match &on_conflict.action {
+                        OnConflictAction::DoNothing => (false, Vec::new(), None),
+                        OnConflictAction::DoUpdate {
+                            assignments,
+                            predicate,
+                        } => {
+                            let mut selectors = Vec::with_capacity(assignments.len());
+                            for (col, expr) in assignments {
+                                selectors.push(proto::UpdateSelector {
+                                    column: col.clone(),
+                                    expr: Some(expr.try_into()?),
+                                });
+                            }
+                            let predicate =
+                                predicate.as_ref().map(|expr| expr.try_into()).transpose()?;
+                            (true, selectors, predicate)
+                        }
+                    };
+                    Some(proto::OnConflict {
+                        columns: on_conflict.columns.clone(),
+                        do_update,
+                        assignments,
+                        predicate,
+                    })
+                }
+                None => None,
+            };
+
             proto::ExecutionPlanExtensionType::InsertExec(proto::InsertExec {
                 provider_id: id.into_bytes().to_vec(),
+                copy_from: exec.copy_from,
+                catalog_version: exec.catalog_version,
+                schema_evolution: exec.schema_evolution.as_ref().map(|evolution| {
+                    proto::SchemaEvolution {
+                        schema: evolution.schema.clone(),
+                        name: evolution.name.clone(),
+                        operations: evolution
+                            .operations
+                            .iter()
+                            .map(|op| op.clone().into())
+                            .collect(),
+                    }
+                }),
+                returning: exec.returning,
+                on_conflict,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<InsertOverwriteExec>() {
+            proto::ExecutionPlanExtensionType::InsertOverwriteExec(proto::InsertOverwriteExec {
+                table: Some(exec.table.clone().into()),
+                replace_where: exec
+                    .replace_where
+                    .as_ref()
+                    .map(|expr| expr.try_into())
+                    .transpose()?,
             })
         } else if let Some(exec) = node.as_any().downcast_ref::<DeleteExec>() {
             proto::ExecutionPlanExtensionType::DeleteExec(proto::DeleteExec {
@@ -735,6 +902,25 @@ impl<'a> PhysicalExtensionCodec for GlareDBExtensionCodec<'a> {
                     .map(|expr| expr.try_into())
                     .transpose()?,
             })
+        } else if let Some(exec) = node.as_any().downcast_ref::<VacuumTableExec>() {
+            proto::ExecutionPlanExtensionType::VacuumTableExec(proto::VacuumTableExec {
+                table: Some(exec.table.clone().into()),
+                retain_hours: exec.retain_hours,
+                dry_run: exec.dry_run,
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<OptimizeTableExec>() {
+            proto::ExecutionPlanExtensionType::OptimizeTableExec(proto::OptimizeTableExec {
+                table: Some(exec.table.clone().into()),
+                zorder_by: exec.zorder_by.clone(),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<TruncateTablesExec>() {
+            proto::ExecutionPlanExtensionType::TruncateTablesExec(proto::TruncateTablesExec {
+                tables: exec.tables.iter().cloned().map(|t| t.into()).collect(),
+            })
+        } else if let Some(exec) = node.as_any().downcast_ref::<CancelQueryExec>() {
+            proto::ExecutionPlanExtensionType::CancelQueryExec(proto::CancelQueryExec {
+                connection_id: exec.connection_id.into_bytes().to_vec(),
+            })
         } else if let Some(exec) = node.as_any().downcast_ref::<CopyToExec>() {
             proto::ExecutionPlanExtensionType::CopyToExec(proto::CopyToExec {
                 format: Some(exec.format.clone().try_into()?),
@@ -1,9 +1,11 @@
-use datafusion::logical_expr::{LogicalPlan as DFLogicalPlan, UserDefinedLogicalNode};
+use std::sync::Arc;
+
+use datafusion::logical_expr::{LogicalPlan as DFLogicalPlan, Projection, UserDefinedLogicalNode};
 use datafusion::optimizer::optimizer::Optimizer;
 use datafusion::optimizer::OptimizerRule;
 
 use crate::planner::extension::{ExtensionNode, ExtensionType};
-use crate::planner::logical_plan::{CopyTo, CreateTable, CreateTempTable, Insert};
+use crate::planner::logical_plan::{CopyTo, CreateTable, CreateTempTable, Insert, InsertOverwrite};
 
 fn require_downcast_lp<P: 'static>(plan: &dyn UserDefinedLogicalNode) -> &P {
     match plan.as_any().downcast_ref::<P>() {
@@ -77,9 +79,29 @@ impl OptimizerRule for DdlInputOptimizationRule {
                         let lp = Insert { source, ..lp };
                         Ok(Some(DFLogicalPlan::Extension(lp.into_extension())))
                     }
+                    ExtensionType::InsertOverwrite => {
+                        let lp = require_downcast_lp::<InsertOverwrite>(node).clone();
+                        let source =
+                            self.default_optimizer
+                                .optimize(&lp.source, config, |_, _| {})?;
+
+                        let lp = InsertOverwrite { source, ..lp };
+                        Ok(Some(DFLogicalPlan::Extension(lp.into_extension())))
+                    }
                     _ => Ok(None),
                 }
             }
+            // Inserts with a RETURNING clause are planned as a projection
+            // over the insert.
+            DFLogicalPlan::Projection(projection) => {
+                match self.try_optimize(&projection.input, config)? {
+                    Some(input) => Ok(Some(DFLogicalPlan::Projection(Projection::try_new(
+                        projection.expr.clone(),
+                        Arc::new(input),
+                    )?))),
+                    None => Ok(None),
+                }
+            }
             _ => Ok(None),
         }
     }
@@ -178,6 +200,7 @@ mod test {
                 or_replace: false,
                 schema: schema.clone(),
                 source: Some(plan),
+                partition_by: Vec::new(),
             }
             .into_extension(),
         );
@@ -290,6 +313,10 @@ mod test {
                 source: plan,
                 provider: ProviderReference::RemoteReference(Uuid::nil()),
                 runtime_preference: RuntimePreference::Unspecified,
+                copy_from: false,
+                schema_evolution: None,
+                returning: false,
+                on_conflict: None,
             }
             .into_extension(),
         );
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use datafusion::arrow::datatypes::DataType;
+use datafusion::arrow::datatypes::{DataType, SchemaRef};
 use datafusion::common::OwnedTableReference;
 use datafusion::config::ConfigOptions;
 use datafusion::datasource::DefaultTableSource;
@@ -13,7 +13,7 @@ use datafusion::prelude::Expr;
 use datafusion::sql::TableReference;
 use datafusion::variable::VarProvider;
 use datafusion_ext::functions::FuncParamValue;
-use datafusion_ext::planner::AsyncContextProvider;
+use datafusion_ext::planner::{AsyncContextProvider, TableVersion};
 use datafusion_ext::runtime::table_provider::RuntimeAwareTableProvider;
 use datafusion_ext::vars::CredentialsVarProvider;
 use protogen::metastore::types::catalog::{CatalogEntry, RuntimePreference};
@@ -87,6 +87,76 @@ impl<'a> PartialContextProvider<'a> {
         Ok(provider)
     }
 
+    /// Infer the current schema of an external table from its source,
+    /// ignoring any columns stored in the catalog.
+    pub async fn infer_external_table_schema(
+        &mut self,
+        name: OwnedTableReference,
+    ) -> Result<SchemaRef, PlanError> {
+        let mut table = match self
+            .resolver
+            .resolve_entry_from_reference(TableReference::from(&name))?
+        {
+            ResolvedEntry::Entry(CatalogEntry::Table(table)) if table.meta.external => table,
+            _ => {
+                return Err(PlanError::String(format!(
+                    "'{name}' is not an external table"
+                )))
+            }
+        };
+
+        // The source may only be reachable from the remote session.
+        if self.ctx.exec_client().is_some() {
+            return Err(PlanError::String(
+                "Refreshing external tables is not supported for remote sessions".to_string(),
+            ));
+        }
+
+        table.columns = None;
+        let provider = self.new_dispatcher().dispatch_table(&table).await?;
+        Ok(provider.schema())
+    }
+
+    /// Find a table provider for a previous version of the referenced table.
+    ///
+    /// Versioned providers aren't cached since the same table may be scanned
+    /// at different versions in a single query.
+    async fn resolve_reference_at_version(
+        &mut self,
+        reference: TableReference<'_>,
+        version: TableVersion,
+    ) -> Result<RuntimeAwareTableProvider, PlanError> {
+        let table = match self.resolver.resolve_entry_from_reference(reference)? {
+            ResolvedEntry::Entry(CatalogEntry::Table(table)) => table,
+            ResolvedEntry::Entry(ent) => {
+                return Err(PlanError::String(format!(
+                    "Time travel is not supported for entry type: {}",
+                    ent.entry_type()
+                )))
+            }
+            ResolvedEntry::NeedsExternalResolution { .. } => {
+                return Err(PlanError::String(
+                    "Time travel is not supported for tables in external databases".to_string(),
+                ))
+            }
+        };
+
+        // Native tables live with the remote session, and the remote
+        // dispatcher doesn't know about versions.
+        if self.ctx.exec_client().is_some() {
+            return Err(PlanError::String(
+                "Time travel is not supported for remote tables".to_string(),
+            ));
+        }
+
+        Ok(RuntimeAwareTableProvider::new(
+            RuntimePreference::Local,
+            self.new_dispatcher()
+                .dispatch_table_at_version(&table, version)
+                .await?,
+        ))
+    }
+
     /// Find a table provider for the given reference, taking into account the
     /// session's search path.
     ///
@@ -282,6 +352,20 @@ impl<'a> AsyncContextProvider for PartialContextProvider<'a> {
         Ok(Arc::new(DefaultTableSource::new(Arc::new(provider))))
     }
 
+    async fn get_table_source_at_version(
+        &mut self,
+        name: TableReference<'_>,
+        version: TableVersion,
+    ) -> DataFusionResult<Arc<dyn TableSource>> {
+        let provider = self
+            .resolve_reference_at_version(name.clone(), version)
+            .await
+            .map_err(|e| {
+                DataFusionError::Plan(format!("Unable to fetch table provider for '{name}': {e}"))
+            })?;
+        Ok(Arc::new(DefaultTableSource::new(Arc::new(provider))))
+    }
+
     async fn get_table_function_source(
         &mut self,
         name: TableReference<'_>,
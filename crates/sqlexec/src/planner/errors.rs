@@ -45,6 +45,9 @@ pub enum PlanError {
     #[error("Invalid view statement: {msg}")]
     InvalidViewStatement { msg: &'static str },
 
+    #[error("infinite recursion detected in view: {0}")]
+    RecursiveView(String),
+
     #[error("Invalid delete statement: {msg}")]
     InvalidDeleteStatement { msg: &'static str },
 
@@ -54,6 +57,9 @@ pub enum PlanError {
     #[error("Invalid alter statement: {msg}")]
     InvalidAlterStatement { msg: &'static str },
 
+    #[error("Invalid connection id: {0}")]
+    InvalidConnectionId(String),
+
     #[error("Invalid copy to statement: {source}")]
     InvalidCopyToStatement {
         source: Box<dyn std::error::Error + Send + Sync>,
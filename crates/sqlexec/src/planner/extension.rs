@@ -7,6 +7,7 @@ use super::logical_plan::{
     AlterDatabase,
     AlterTable,
     AlterTunnelRotateKeys,
+    CancelQuery,
     CopyTo,
     CreateCredentials,
     CreateExternalDatabase,
@@ -25,9 +26,13 @@ use super::logical_plan::{
     DropTunnel,
     DropViews,
     Insert,
+    InsertOverwrite,
+    OptimizeTable,
     SetVariable,
     ShowVariable,
+    TruncateTables,
     Update,
+    VacuumTable,
 };
 use crate::errors::{internal, ExecError, Result};
 use crate::LogicalPlan;
@@ -60,7 +65,52 @@ pub enum ExtensionType {
     CopyTo,
     Update,
     Insert,
+    InsertOverwrite,
     Delete,
+    VacuumTable,
+    OptimizeTable,
+    TruncateTables,
+    CancelQuery,
+}
+
+impl ExtensionType {
+    /// Returns true if the extension doesn't modify data or the catalog.
+    ///
+    /// Setting a variable only changes session state, and canceling a query
+    /// only affects another session, so both are allowed in read only
+    /// sessions.
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Self::DescribeTable | Self::SetVariable | Self::ShowVariable | Self::CancelQuery => {
+                true
+            }
+            Self::AlterDatabase
+            | Self::AlterTable
+            | Self::AlterTunnelRotateKeys
+            | Self::CreateCredentials
+            | Self::CreateExternalDatabase
+            | Self::CreateExternalTable
+            | Self::CreateSchema
+            | Self::CreateTable
+            | Self::CreateTempTable
+            | Self::CreateTunnel
+            | Self::CreateView
+            | Self::DropTables
+            | Self::DropCredentials
+            | Self::DropDatabase
+            | Self::DropSchemas
+            | Self::DropTunnel
+            | Self::DropViews
+            | Self::CopyTo
+            | Self::Update
+            | Self::Insert
+            | Self::InsertOverwrite
+            | Self::Delete
+            | Self::VacuumTable
+            | Self::OptimizeTable
+            | Self::TruncateTables => false,
+        }
+    }
 }
 
 impl FromStr for ExtensionType {
@@ -90,7 +140,12 @@ impl FromStr for ExtensionType {
             CopyTo::EXTENSION_NAME => Self::CopyTo,
             Update::EXTENSION_NAME => Self::Update,
             Insert::EXTENSION_NAME => Self::Insert,
+            InsertOverwrite::EXTENSION_NAME => Self::InsertOverwrite,
             Delete::EXTENSION_NAME => Self::Delete,
+            VacuumTable::EXTENSION_NAME => Self::VacuumTable,
+            OptimizeTable::EXTENSION_NAME => Self::OptimizeTable,
+            TruncateTables::EXTENSION_NAME => Self::TruncateTables,
+            CancelQuery::EXTENSION_NAME => Self::CancelQuery,
             _ => return Err(internal!("unknown extension type: {}", s)),
         })
     }
@@ -0,0 +1,52 @@
+use uuid::Uuid;
+
+use super::{
+    DfLogicalPlan,
+    ExtensionNode,
+    UserDefinedLogicalNodeCore,
+    GENERIC_OPERATION_LOGICAL_SCHEMA,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CancelQuery {
+    pub connection_id: Uuid,
+}
+
+impl UserDefinedLogicalNodeCore for CancelQuery {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        Vec::new()
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        Vec::new()
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} connection_id={}",
+            Self::EXTENSION_NAME,
+            self.connection_id
+        )
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for CancelQuery {
+    const EXTENSION_NAME: &'static str = "CancelQuery";
+}
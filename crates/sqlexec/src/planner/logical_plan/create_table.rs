@@ -14,6 +14,7 @@ pub struct CreateTable {
     pub or_replace: bool,
     pub schema: DFSchemaRef,
     pub source: Option<DfLogicalPlan>,
+    pub partition_by: Vec<String>,
 }
 
 impl UserDefinedLogicalNodeCore for CreateTable {
@@ -9,6 +9,7 @@ use super::{
     UserDefinedLogicalNodeCore,
     GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
 };
+use crate::planner::physical_plan::insert::{OnConflict, SchemaEvolution};
 use crate::planner::physical_plan::remote_scan::ProviderReference;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -16,6 +17,18 @@ pub struct Insert {
     pub source: DfLogicalPlan,
     pub provider: ProviderReference,
     pub runtime_preference: RuntimePreference,
+    /// Whether this insert was planned from a `COPY ... FROM` statement.
+    pub copy_from: bool,
+    /// Changes to make to the table's columns before inserting, set when
+    /// schema evolution is enabled and the source doesn't fit the table.
+    pub schema_evolution: Option<SchemaEvolution>,
+    /// Output the inserted rows instead of the number of rows inserted. Set
+    /// for inserts with a RETURNING clause, which is planned as a projection
+    /// over this node.
+    pub returning: bool,
+    /// How to handle rows conflicting with existing rows, set for inserts
+    /// with an ON CONFLICT clause.
+    pub on_conflict: Option<OnConflict>,
 }
 
 impl UserDefinedLogicalNodeCore for Insert {
@@ -28,7 +41,11 @@ impl UserDefinedLogicalNodeCore for Insert {
     }
 
     fn schema(&self) -> &datafusion::common::DFSchemaRef {
-        &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA
+        if self.returning {
+            self.source.schema()
+        } else {
+            &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA
+        }
     }
 
     fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
@@ -0,0 +1,50 @@
+use protogen::metastore::types::catalog::TableEntry;
+
+use super::{
+    DfLogicalPlan,
+    Expr,
+    ExtensionNode,
+    UserDefinedLogicalNodeCore,
+    GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InsertOverwrite {
+    pub table: TableEntry,
+    pub source: DfLogicalPlan,
+    pub replace_where: Option<Expr>,
+}
+
+impl UserDefinedLogicalNodeCore for InsertOverwrite {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![&self.source]
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_AND_COUNT_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        Vec::new()
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::EXTENSION_NAME)
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for InsertOverwrite {
+    const EXTENSION_NAME: &'static str = "InsertOverwrite";
+}
@@ -1,6 +1,7 @@
 mod alter_database;
 mod alter_table;
 mod alter_tunnel_rotate_keys;
+mod cancel_query;
 mod copy_to;
 mod create_credentials;
 mod create_external_database;
@@ -19,9 +20,13 @@ mod drop_tables;
 mod drop_tunnel;
 mod drop_views;
 mod insert;
+mod insert_overwrite;
+mod optimize_table;
 mod set_variable;
 mod show_variable;
+mod truncate_tables;
 mod update;
+mod vacuum_table;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -31,6 +36,7 @@ use std::sync::Arc;
 pub use alter_database::*;
 pub use alter_table::*;
 pub use alter_tunnel_rotate_keys::*;
+pub use cancel_query::*;
 pub use copy_to::*;
 pub use create_credentials::*;
 pub use create_external_database::*;
@@ -40,7 +46,7 @@ pub use create_table::*;
 pub use create_temp_table::*;
 pub use create_tunnel::*;
 pub use create_view::*;
-use datafusion::arrow::datatypes::{DataType, Schema as ArrowSchema};
+use datafusion::arrow::datatypes::{DataType, Field, Schema as ArrowSchema};
 use datafusion::common::{DFField, DFSchema, DFSchemaRef, ParamValues};
 use datafusion::logical_expr::{
     Explain,
@@ -59,7 +65,9 @@ pub use drop_tables::*;
 pub use drop_tunnel::*;
 pub use drop_views::*;
 pub use insert::*;
+pub use insert_overwrite::*;
 use once_cell::sync::Lazy;
+pub use optimize_table::*;
 use parser::sqlparser::ast;
 use protogen::metastore::types::options::{
     CopyToDestinationOptions,
@@ -70,7 +78,9 @@ use protogen::metastore::types::options::{
 };
 pub use set_variable::*;
 pub use show_variable::*;
+pub use truncate_tables::*;
 pub use update::*;
+pub use vacuum_table::*;
 
 use super::physical_plan::{
     GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA,
@@ -126,6 +136,8 @@ pub enum LogicalPlan {
     Datafusion(DfLogicalPlan),
     /// Plans related to transaction management.
     Transaction(TransactionPlan),
+    /// Plans for declaring, fetching from, and closing cursors.
+    Cursor(CursorPlan),
     Noop,
 }
 
@@ -142,6 +154,11 @@ impl LogicalPlan {
     /// one.
     pub fn output_schema(&self) -> Option<ArrowSchema> {
         match self {
+            // Explain output is rendered to text during execution, see
+            // `ExecutionResult::Explain`.
+            LogicalPlan::Datafusion(DfLogicalPlan::Explain(_) | DfLogicalPlan::Analyze(_)) => Some(
+                ArrowSchema::new(vec![Field::new("QUERY PLAN", DataType::Utf8, false)]),
+            ),
             LogicalPlan::Datafusion(plan) => {
                 let schema: ArrowSchema = plan.schema().as_ref().into();
                 Some(schema)
@@ -284,6 +301,9 @@ pub enum TransactionPlan {
     Begin,
     Commit,
     Abort,
+    Savepoint(String),
+    ReleaseSavepoint(String),
+    RollbackToSavepoint(String),
 }
 
 impl From<TransactionPlan> for LogicalPlan {
@@ -291,3 +311,20 @@ impl From<TransactionPlan> for LogicalPlan {
         LogicalPlan::Transaction(plan)
     }
 }
+
+#[derive(Clone, Debug)]
+pub enum CursorPlan {
+    /// Declare a cursor for the query.
+    Declare { name: String, plan: DfLogicalPlan },
+    /// Fetch the next `count` rows from a cursor, or all remaining rows if
+    /// `count` is `None`.
+    Fetch { name: String, count: Option<u64> },
+    /// Close a cursor, or all cursors if no name is provided.
+    Close(Option<String>),
+}
+
+impl From<CursorPlan> for LogicalPlan {
+    fn from(plan: CursorPlan) -> Self {
+        LogicalPlan::Cursor(plan)
+    }
+}
@@ -1,13 +1,23 @@
+use datafusion::common::ToDFSchema;
+
 use super::{
     ast,
     internal,
     DFSchemaRef,
     DfLogicalPlan,
     ExtensionNode,
+    Lazy,
     Result,
     UserDefinedLogicalNodeCore,
-    GENERIC_OPERATION_LOGICAL_SCHEMA,
 };
+use crate::planner::physical_plan::set_var::SET_VARIABLE_PHYSICAL_SCHEMA;
+
+pub static SET_VARIABLE_LOGICAL_SCHEMA: Lazy<DFSchemaRef> = Lazy::new(|| {
+    SET_VARIABLE_PHYSICAL_SCHEMA
+        .clone()
+        .to_dfschema_ref()
+        .unwrap()
+});
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct SetVariable {
@@ -51,7 +61,7 @@ impl UserDefinedLogicalNodeCore for SetVariable {
     }
 
     fn schema(&self) -> &DFSchemaRef {
-        &GENERIC_OPERATION_LOGICAL_SCHEMA
+        &SET_VARIABLE_LOGICAL_SCHEMA
     }
 
     fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
@@ -0,0 +1,47 @@
+use protogen::metastore::types::catalog::TableEntry;
+
+use super::{
+    DfLogicalPlan,
+    ExtensionNode,
+    UserDefinedLogicalNodeCore,
+    GENERIC_OPERATION_LOGICAL_SCHEMA,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TruncateTables {
+    pub tables: Vec<TableEntry>,
+}
+
+impl UserDefinedLogicalNodeCore for TruncateTables {
+    fn name(&self) -> &str {
+        Self::EXTENSION_NAME
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        Vec::new()
+    }
+
+    fn schema(&self) -> &datafusion::common::DFSchemaRef {
+        &GENERIC_OPERATION_LOGICAL_SCHEMA
+    }
+
+    fn expressions(&self) -> Vec<datafusion::prelude::Expr> {
+        Vec::new()
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::EXTENSION_NAME)
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[datafusion::prelude::Expr],
+        _inputs: &[DfLogicalPlan],
+    ) -> Self {
+        self.clone()
+    }
+}
+
+impl ExtensionNode for TruncateTables {
+    const EXTENSION_NAME: &'static str = "TruncateTables";
+}
@@ -3,10 +3,12 @@ use std::fmt;
 use std::sync::Arc;
 
 use catalog::mutator::CatalogMutator;
+use catalog::session_catalog::{ResolveConfig, SessionCatalog};
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
 use datafusion::execution::TaskContext;
+use datafusion::logical_expr::{cast, ident, lit, Expr};
 use datafusion::physical_expr::PhysicalSortExpr;
 use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
 use datafusion::physical_plan::{
@@ -17,8 +19,13 @@ use datafusion::physical_plan::{
     SendableRecordBatchStream,
     Statistics,
 };
+use datafusion::scalar::ScalarValue;
+use datasources::native::access::{arrow_to_delta_safe, NativeTableStorage};
 use futures::stream;
+use protogen::metastore::types::catalog::{CatalogState, TableEntry};
+use protogen::metastore::types::options::InternalColumnDefinition;
 use protogen::metastore::types::service::{self, AlterTableOperation, Mutation};
+use sqlbuiltins::builtins::DEFAULT_CATALOG;
 
 use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
 
@@ -59,7 +66,7 @@ impl ExecutionPlan for AlterTableExec {
             Ok(self)
         } else {
             Err(DataFusionError::Plan(
-                "Cannot change children for AlterTableRenameExec".to_string(),
+                "Cannot change children for AlterTableExec".to_string(),
             ))
         }
     }
@@ -80,7 +87,12 @@ impl ExecutionPlan for AlterTableExec {
             .get_extension::<CatalogMutator>()
             .expect("context should have catalog mutator");
 
-        let stream = stream::once(alter_table_rename(mutator, self.clone()));
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+
+        let stream = stream::once(alter_table(mutator, storage, self.clone()));
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -99,22 +111,238 @@ impl DisplayAs for AlterTableExec {
     }
 }
 
-async fn alter_table_rename(
+async fn alter_table(
     mutator: Arc<CatalogMutator>,
+    storage: Arc<NativeTableStorage>,
     plan: AlterTableExec,
 ) -> DataFusionResult<RecordBatch> {
+    apply_alter_table(
+        &mutator,
+        &storage,
+        plan.catalog_version,
+        &plan.schema,
+        &plan.name,
+        vec![plan.operation.clone()],
+    )
+    .await?;
+
+    Ok(match plan.operation {
+        AlterTableOperation::SetComment { .. } => new_operation_batch("comment"),
+        _ => new_operation_batch("alter_table"),
+    })
+}
+
+/// Apply `operations` to a table in the catalog, altering the table's data
+/// if any of them change its columns.
+///
+/// The mutations are validated by the catalog first. Changes to the table's
+/// data are then committed before the catalog so that they can be undone
+/// (by restoring the previous version of the table) if committing the
+/// catalog fails.
+///
+/// Returns the altered table entry.
+async fn apply_alter_table(
+    mutator: &CatalogMutator,
+    storage: &NativeTableStorage,
+    catalog_version: u64,
+    schema: &str,
+    name: &str,
+    operations: Vec<AlterTableOperation>,
+) -> DataFusionResult<TableEntry> {
+    let alters_columns = operations.iter().any(|operation| match operation {
+        AlterTableOperation::RenameTable { .. }
+        | AlterTableOperation::SetAccessMode { .. }
+        | AlterTableOperation::SetComment { .. }
+        | AlterTableOperation::SetColumns { .. } => false,
+        AlterTableOperation::RenameColumn { .. }
+        | AlterTableOperation::AddColumn { .. }
+        | AlterTableOperation::DropColumn { .. }
+        | AlterTableOperation::WidenColumn { .. } => true,
+    });
+
+    let (state, ent) =
+        validate_alter_table(mutator, catalog_version, schema, name, &operations).await?;
+
+    let previous_version = if alters_columns {
+        Some(alter_storage(storage, &ent, &operations).await?)
+    } else {
+        None
+    };
+
+    commit_alter_table(
+        mutator,
+        storage,
+        catalog_version,
+        state,
+        &ent,
+        previous_version,
+    )
+    .await?;
+
+    Ok(ent)
+}
+
+/// Validate `operations` against the catalog without committing them.
+///
+/// Returns the catalog state with the operations applied, along with the
+/// altered table entry.
+pub(crate) async fn validate_alter_table(
+    mutator: &CatalogMutator,
+    catalog_version: u64,
+    schema: &str,
+    name: &str,
+    operations: &[AlterTableOperation],
+) -> DataFusionResult<(Arc<CatalogState>, TableEntry)> {
     // TODO: Error if schemas between references differ.
-    mutator
-        .mutate_and_commit(
-            plan.catalog_version,
-            [Mutation::AlterTable(service::AlterTable {
-                schema: plan.schema,
-                name: plan.name,
-                operation: plan.operation,
-            })],
-        )
+    let mutations = operations.iter().map(|operation| {
+        Mutation::AlterTable(service::AlterTable {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            operation: operation.clone(),
+        })
+    });
+    let state = mutator
+        .mutate(catalog_version, mutations)
         .await
         .map_err(|e| DataFusionError::Execution(format!("failed to alter table: {e}")))?;
 
-    Ok(new_operation_batch("alter_table"))
+    let catalog = SessionCatalog::new(
+        state.clone(),
+        ResolveConfig {
+            default_schema_oid: 0,
+            session_schema_oid: 0,
+        },
+    );
+    // The table may have been renamed.
+    let name = operations
+        .iter()
+        .rev()
+        .find_map(|operation| match operation {
+            AlterTableOperation::RenameTable { new_name } => Some(new_name.as_str()),
+            _ => None,
+        })
+        .unwrap_or(name);
+    let ent = catalog
+        .resolve_table(DEFAULT_CATALOG, schema, name)
+        .ok_or_else(|| DataFusionError::Execution("Missing table after catalog alter".to_string()))?
+        .clone();
+
+    Ok((state, ent))
+}
+
+/// Commit a validated alter to the catalog.
+///
+/// If the table's data was changed first, `previous_version` is the version
+/// of the table before the change, which the table is restored to if
+/// committing fails.
+pub(crate) async fn commit_alter_table(
+    mutator: &CatalogMutator,
+    storage: &NativeTableStorage,
+    catalog_version: u64,
+    state: Arc<CatalogState>,
+    ent: &TableEntry,
+    previous_version: Option<i64>,
+) -> DataFusionResult<()> {
+    let commit = mutator
+        .commit_state(catalog_version, state.as_ref().clone())
+        .await;
+    if let Err(e) = commit {
+        if let Some(version) = previous_version {
+            storage.restore_table(ent, version).await.map_err(|restore_err| {
+                DataFusionError::Execution(format!(
+                    "failed to commit catalog state: {e}, and failed to restore table: {restore_err}"
+                ))
+            })?;
+        }
+        return Err(DataFusionError::Execution(format!(
+            "failed to commit catalog state: {e}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Change the table's data to match its altered columns in a single delta
+/// commit, returning the version of the table before the change.
+///
+/// Adding columns only changes the table's metadata. Renaming, dropping or
+/// widening a column rewrites the table, since the files would otherwise be
+/// read with the old columns. Delta supports renames and drops through
+/// column mapping, but the delta-rs version we use can't read tables with it
+/// enabled.
+async fn alter_storage(
+    storage: &NativeTableStorage,
+    ent: &TableEntry,
+    operations: &[AlterTableOperation],
+) -> DataFusionResult<i64> {
+    let only_adds = operations.iter().all(|operation| {
+        !matches!(
+            operation,
+            AlterTableOperation::RenameColumn { .. }
+                | AlterTableOperation::DropColumn { .. }
+                | AlterTableOperation::WidenColumn { .. }
+        )
+    });
+
+    let result = if only_adds {
+        let columns = operations
+            .iter()
+            .filter_map(|operation| match operation {
+                AlterTableOperation::AddColumn { name, arrow_type } => {
+                    Some((name.clone(), arrow_type.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        storage.add_columns(ent, columns).await
+    } else {
+        let exprs = ent
+            .get_internal_columns()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|col| column_expr(operations, col))
+            .collect::<DataFusionResult<Vec<_>>>()?;
+        storage.rewrite_table(ent, exprs).await
+    };
+
+    result.map_err(|e| DataFusionError::Execution(format!("failed to alter table in storage: {e}")))
+}
+
+/// Get the expression producing the altered column `col` from the table's
+/// existing data.
+pub(crate) fn column_expr(
+    operations: &[AlterTableOperation],
+    col: InternalColumnDefinition,
+) -> DataFusionResult<Expr> {
+    let operation = operations.iter().find(|operation| match operation {
+        AlterTableOperation::RenameColumn { new_name: name, .. }
+        | AlterTableOperation::AddColumn { name, .. }
+        | AlterTableOperation::WidenColumn { name, .. } => &col.name == name,
+        _ => false,
+    });
+
+    Ok(match operation {
+        Some(AlterTableOperation::RenameColumn { old_name, new_name }) => {
+            ident(old_name).alias(new_name)
+        }
+        Some(AlterTableOperation::AddColumn { .. }) => {
+            // Types that get downgraded when stored in delta need metadata on
+            // the field to read them back, which we can't attach here.
+            let delta_field = arrow_to_delta_safe(&col.arrow_type)
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+            if delta_field.metadata.is_some() {
+                return Err(DataFusionError::Execution(format!(
+                    "Adding a column of type {} is not supported",
+                    col.arrow_type
+                )));
+            }
+
+            // Existing rows get nulls for the new column.
+            cast(lit(ScalarValue::Null), col.arrow_type).alias(col.name)
+        }
+        Some(AlterTableOperation::WidenColumn { .. }) => {
+            cast(ident(&col.name), col.arrow_type).alias(col.name)
+        }
+        _ => ident(col.name),
+    })
 }
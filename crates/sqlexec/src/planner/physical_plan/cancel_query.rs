@@ -0,0 +1,115 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::Schema;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datafusion_ext::session_registry::{SessionOwner, SessionRegistry};
+use datafusion_ext::vars::SessionVars;
+use futures::stream;
+use uuid::Uuid;
+
+use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+
+/// Cancel the query running on another connection of this server.
+///
+/// Only connections of the same user on the same database can be canceled,
+/// see [`SessionOwner`].
+#[derive(Debug, Clone)]
+pub struct CancelQueryExec {
+    pub connection_id: Uuid,
+}
+
+impl ExecutionPlan for CancelQueryExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            Err(DataFusionError::Plan(
+                "Cannot change children for CancelQueryExec".to_string(),
+            ))
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "CancelQueryExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let registry = context.session_config().get_extension::<SessionRegistry>();
+        let owner = context
+            .session_config()
+            .options()
+            .extensions
+            .get::<SessionVars>()
+            .map(SessionOwner::from_vars);
+        let connection_id = self.connection_id;
+
+        let stream = stream::once(async move {
+            let (registry, owner) = registry.zip(owner).ok_or_else(|| {
+                DataFusionError::Execution(
+                    "Canceling queries isn't supported by this session".to_string(),
+                )
+            })?;
+            registry
+                .cancel_query(connection_id, owner)
+                .map_err(|e| DataFusionError::Execution(e.to_string()))?;
+            Ok(new_operation_batch("cancel_query"))
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for CancelQueryExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CancelQueryExec")
+    }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
 use datafusion::execution::TaskContext;
 use datafusion::physical_expr::{PhysicalSortExpr, PhysicalSortRequirement};
@@ -33,7 +34,11 @@ use datasources::object_store::local::LocalStoreAccess;
 use datasources::object_store::s3::S3StoreAccess;
 use datasources::object_store::ObjStoreAccess;
 use futures::stream;
-use protogen::metastore::types::options::{CopyToDestinationOptions, CopyToFormatOptions};
+use protogen::metastore::types::options::{
+    CopyToCompression,
+    CopyToDestinationOptions,
+    CopyToFormatOptions,
+};
 
 use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
 
@@ -215,7 +220,7 @@ fn get_sink_for_obj(
     location: &str,
 ) -> DataFusionResult<Box<dyn DataSink>> {
     let store = access
-        .create_store()
+        .cached_store()
         .map_err(|e| DataFusionError::External(Box::new(e)))?;
 
     let path = access
@@ -229,6 +234,10 @@ fn get_sink_for_obj(
             CsvSinkOpts {
                 delim: csv_opts.delim,
                 header: csv_opts.header,
+                quote: csv_opts.quote,
+                null: csv_opts.null.clone(),
+                crlf: csv_opts.crlf,
+                compression: file_compression_type(csv_opts.compression),
             },
         )),
         CopyToFormatOptions::Parquet(parquet_opts) => Box::new(ParquetSink::from_obj_store(
@@ -276,8 +285,17 @@ fn get_sink_for_obj(
         CopyToFormatOptions::Json(json_opts) => Box::new(JsonSink::from_obj_store(
             store,
             path,
-            JsonSinkOpts::with_array_format(json_opts.array),
+            JsonSinkOpts::with_array_format(json_opts.array)
+                .with_compression(file_compression_type(json_opts.compression)),
         )),
     };
     Ok(sink)
 }
+
+fn file_compression_type(compression: CopyToCompression) -> FileCompressionType {
+    match compression {
+        CopyToCompression::None => FileCompressionType::UNCOMPRESSED,
+        CopyToCompression::Gzip => FileCompressionType::GZIP,
+        CopyToCompression::Zstd => FileCompressionType::ZSTD,
+    }
+}
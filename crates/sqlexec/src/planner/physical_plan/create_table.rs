@@ -21,6 +21,7 @@ use datafusion::physical_plan::{
 };
 use datasources::native::access::{NativeTable, NativeTableStorage, SaveMode};
 use futures::{stream, StreamExt};
+use protogen::metastore::types::options::TableOptionsInternal;
 use protogen::metastore::types::service;
 use protogen::metastore::types::service::Mutation;
 use sqlbuiltins::builtins::DEFAULT_CATALOG;
@@ -38,6 +39,7 @@ pub struct CreateTableExec {
     pub or_replace: bool,
     pub arrow_schema: SchemaRef,
     pub source: Option<Arc<dyn ExecutionPlan>>,
+    pub partition_by: Vec<String>,
 }
 
 impl ExecutionPlan for CreateTableExec {
@@ -75,6 +77,7 @@ impl ExecutionPlan for CreateTableExec {
             or_replace: self.or_replace,
             arrow_schema: self.arrow_schema.clone(),
             source: children.first().cloned(),
+            partition_by: self.partition_by.clone(),
         }))
     }
 
@@ -134,7 +137,10 @@ impl CreateTableExec {
                 [Mutation::CreateTable(service::CreateTable {
                     schema: self.tbl_reference.schema.clone().into_owned(),
                     name: self.tbl_reference.name.clone().into_owned(),
-                    options: self.arrow_schema.into(),
+                    options: TableOptionsInternal {
+                        partition_by: self.partition_by,
+                        ..self.arrow_schema.into()
+                    },
                     if_not_exists,
                     or_replace,
                 })],
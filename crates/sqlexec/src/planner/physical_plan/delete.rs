@@ -17,9 +17,10 @@ use datafusion::physical_plan::{
     Statistics,
 };
 use datafusion::prelude::Expr;
-use datasources::native::access::NativeTableStorage;
+use datasources::native::access::{session_connection_id, NativeTableStorage};
 use futures::stream;
 use protogen::metastore::types::catalog::TableEntry;
+use uuid::Uuid;
 
 use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
 
@@ -79,7 +80,8 @@ impl ExecutionPlan for DeleteExec {
             .get_extension::<NativeTableStorage>()
             .expect("context should have native table storage");
 
-        let stream = stream::once(delete(self.clone(), storage));
+        let connection_id = session_connection_id(context.session_config());
+        let stream = stream::once(delete(self.clone(), storage, connection_id));
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -101,11 +103,12 @@ impl DisplayAs for DeleteExec {
 async fn delete(
     plan: DeleteExec,
     storage: impl AsRef<NativeTableStorage>,
+    connection_id: Uuid,
 ) -> DataFusionResult<RecordBatch> {
     let storage = storage.as_ref();
 
     let num_deleted = storage
-        .delete_rows_where(&plan.table, plan.where_expr)
+        .delete_rows_where(&plan.table, plan.where_expr, connection_id)
         .await
         .map_err(|e| DataFusionError::Execution(format!("failed to delete: {e}")))?;
 
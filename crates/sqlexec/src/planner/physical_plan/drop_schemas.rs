@@ -20,7 +20,7 @@ use datafusion::physical_plan::{
 use futures::stream;
 use protogen::metastore::types::service::{self, Mutation};
 
-use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
 use crate::planner::logical_plan::OwnedFullSchemaReference;
 
 #[derive(Debug, Clone)]
@@ -37,7 +37,7 @@ impl ExecutionPlan for DropSchemasExec {
     }
 
     fn schema(&self) -> Arc<Schema> {
-        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+        GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
     }
 
     fn output_partitioning(&self) -> Partitioning {
@@ -104,6 +104,7 @@ async fn drop_schemas(
     mutator: Arc<CatalogMutator>,
     plan: DropSchemasExec,
 ) -> DataFusionResult<RecordBatch> {
+    let count = plan.schema_references.len() as u64;
     let drops: Vec<_> = plan
         .schema_references
         .into_iter()
@@ -121,5 +122,5 @@ async fn drop_schemas(
         .await
         .map_err(|e| DataFusionError::Execution(format!("failed to drop schemas: {e}")))?;
 
-    Ok(new_operation_batch("drop_schemas"))
+    Ok(new_operation_with_count_batch("drop_schemas", count))
 }
@@ -23,7 +23,7 @@ use protogen::metastore::types::service::{self, Mutation};
 use sqlbuiltins::functions::table::system::remove_delta_tables::DeleteDeltaTablesOperation;
 use sqlbuiltins::functions::table::system::SystemOperationExec;
 
-use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
 use crate::planner::logical_plan::OwnedFullObjectReference;
 
 #[derive(Debug, Clone)]
@@ -40,7 +40,7 @@ impl ExecutionPlan for DropTablesExec {
     }
 
     fn schema(&self) -> Arc<Schema> {
-        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+        GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
     }
 
     fn output_partitioning(&self) -> Partitioning {
@@ -130,5 +130,9 @@ async fn drop_tables(
         .collect::<Vec<_>>()
         .await;
 
-    Ok(new_operation_batch("drop_tables"))
+    // Tables that didn't exist (with IF EXISTS) have no entry.
+    Ok(new_operation_with_count_batch(
+        "drop_tables",
+        plan.tbl_entries.len() as u64,
+    ))
 }
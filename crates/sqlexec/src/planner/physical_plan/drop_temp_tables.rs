@@ -19,7 +19,7 @@ use datafusion::physical_plan::{
 };
 use futures::stream;
 
-use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
 use crate::planner::logical_plan::OwnedFullObjectReference;
 
 #[derive(Debug, Clone)]
@@ -35,7 +35,7 @@ impl ExecutionPlan for DropTempTablesExec {
     }
 
     fn schema(&self) -> Arc<Schema> {
-        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+        GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
     }
 
     fn output_partitioning(&self) -> Partitioning {
@@ -101,9 +101,10 @@ async fn drop_tables(
         .get_extension::<TempCatalog>()
         .unwrap();
 
+    let count = plan.tbl_references.len() as u64;
     for temp_table in plan.tbl_references {
         temp_objects.drop_table(&temp_table.name);
     }
 
-    Ok(new_operation_batch("drop_temp_tables"))
+    Ok(new_operation_with_count_batch("drop_tables", count))
 }
@@ -0,0 +1,86 @@
+//! Rendering of physical plans for EXPLAIN.
+
+use std::fmt::{self, Write as _};
+
+use datafusion::physical_plan::{DisplayFormatType, ExecutionPlan};
+
+/// Render a physical plan as an indented tree, one operator per line with
+/// its children indented beneath it.
+///
+/// Verbose rendering uses the verbose display of each operator, and
+/// additionally shows the output partitioning and ordering of every
+/// operator.
+pub fn render_physical_tree(plan: &dyn ExecutionPlan, verbose: bool) -> String {
+    let mut rendered = String::new();
+    render_node(plan, verbose, 0, &mut rendered);
+    rendered.truncate(rendered.trim_end().len());
+    rendered
+}
+
+fn render_node(plan: &dyn ExecutionPlan, verbose: bool, depth: usize, out: &mut String) {
+    let format = if verbose {
+        DisplayFormatType::Verbose
+    } else {
+        DisplayFormatType::Default
+    };
+
+    // Writing to a string can't fail.
+    let _ = write!(
+        out,
+        "{:indent$}{}",
+        "",
+        OneLine(plan, format),
+        indent = depth * 2
+    );
+    if verbose {
+        let _ = write!(out, " (partitioning={}", plan.output_partitioning());
+        if let Some(ordering) = plan.output_ordering() {
+            let ordering = ordering
+                .iter()
+                .map(|expr| expr.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = write!(out, ", ordering=[{ordering}]");
+        }
+        out.push(')');
+    }
+    out.push('\n');
+
+    for child in plan.children() {
+        render_node(child.as_ref(), verbose, depth + 1, out);
+    }
+}
+
+/// Displays a single operator without its children.
+struct OneLine<'a>(&'a dyn ExecutionPlan, DisplayFormatType);
+
+impl fmt::Display for OneLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_as(self.1, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use datafusion::arrow::datatypes::Schema;
+    use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+    use datafusion::physical_plan::empty::EmptyExec;
+
+    use super::*;
+
+    #[test]
+    fn render_tree() {
+        let plan = CoalescePartitionsExec::new(Arc::new(EmptyExec::new(Arc::new(Schema::empty()))));
+
+        assert_eq!(
+            "CoalescePartitionsExec\n  EmptyExec",
+            render_physical_tree(&plan, false)
+        );
+        assert_eq!(
+            "CoalescePartitionsExec (partitioning=UnknownPartitioning(1))\n  EmptyExec (partitioning=UnknownPartitioning(1))",
+            render_physical_tree(&plan, true)
+        );
+    }
+}
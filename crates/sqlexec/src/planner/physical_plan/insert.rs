@@ -1,16 +1,24 @@
 use std::any::Any;
 use std::fmt;
+use std::fs::File;
 use std::sync::Arc;
 
-use datafusion::arrow::datatypes::{DataType, Schema};
+use catalog::mutator::CatalogMutator;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::ipc::reader::FileReader;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::datasource::TableProvider;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
 use datafusion::execution::context::SessionState;
+use datafusion::execution::disk_manager::RefCountedTempFile;
 use datafusion::execution::TaskContext;
-use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::logical_expr::Expr;
+use datafusion::physical_expr::{PhysicalSortExpr, PhysicalSortRequirement};
 use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
-use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::common::IPCWriter;
+use datafusion::physical_plan::memory::MemoryStream;
+use datafusion::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+use datafusion::physical_plan::stream::{RecordBatchReceiverStream, RecordBatchStreamAdapter};
 use datafusion::physical_plan::{
     DisplayAs,
     DisplayFormatType,
@@ -21,15 +29,60 @@ use datafusion::physical_plan::{
 };
 use datafusion::scalar::ScalarValue;
 use datafusion_ext::metrics::WriteOnlyDataSourceMetricsExecAdapter;
-use futures::{stream, StreamExt};
+use datasources::native::access::{NativeTable, NativeTableStorage};
+use datasources::native::insert::{MergeMatchedAction, MergeNotMatchedAction};
+use futures::{stream, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
+use protogen::metastore::types::service::AlterTableOperation;
 
+use super::alter_table::{column_expr, commit_alter_table, validate_alter_table};
 use super::remote_scan::ProviderReference;
 use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
 
+/// Changes to a native table's columns to make before inserting into it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SchemaEvolution {
+    pub schema: String,
+    pub name: String,
+    pub operations: Vec<AlterTableOperation>,
+}
+
+/// How to handle inserted rows conflicting with existing rows of a native
+/// table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnConflict {
+    /// Rows conflict when they have equal values for all of these columns.
+    pub columns: Vec<String>,
+    pub action: OnConflictAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OnConflictAction {
+    /// Skip the conflicting rows.
+    DoNothing,
+    /// Update the existing rows.
+    ///
+    /// Expressions reference the existing row with the merge target alias,
+    /// and the row proposed for insertion with the merge source alias.
+    DoUpdate {
+        assignments: Vec<(String, Expr)>,
+        predicate: Option<Expr>,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct InsertExec {
+    pub catalog_version: u64,
     pub provider: ProviderReference,
     pub source: Arc<WriteOnlyDataSourceMetricsExecAdapter>,
+    /// Report the inserted rows as copied rows.
+    pub copy_from: bool,
+    /// Alter the table to fit the source before inserting.
+    pub schema_evolution: Option<SchemaEvolution>,
+    /// Output the inserted rows instead of the number of rows inserted.
+    pub returning: bool,
+    /// Merge the source into the table instead of appending to it.
+    pub on_conflict: Option<OnConflict>,
 }
 
 impl ExecutionPlan for InsertExec {
@@ -38,7 +91,11 @@ impl ExecutionPlan for InsertExec {
     }
 
     fn schema(&self) -> Arc<Schema> {
-        GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
+        if self.returning {
+            self.source.schema()
+        } else {
+            GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
+        }
     }
 
     fn output_partitioning(&self) -> Partitioning {
@@ -49,6 +106,15 @@ impl ExecutionPlan for InsertExec {
         None
     }
 
+    fn required_input_ordering(&self) -> Vec<Option<Vec<PhysicalSortRequirement>>> {
+        // Keep any ordering of the source (e.g. from an ORDER BY) so that
+        // the table may write rows in that order.
+        vec![self
+            .source
+            .output_ordering()
+            .map(PhysicalSortRequirement::from_sort_exprs)]
+    }
+
     fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
         vec![self.source.clone()]
     }
@@ -58,10 +124,15 @@ impl ExecutionPlan for InsertExec {
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
         Ok(Arc::new(InsertExec {
+            catalog_version: self.catalog_version,
             provider: self.provider.clone(),
             source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                 children.first().unwrap().clone(),
             )),
+            copy_from: self.copy_from,
+            schema_evolution: self.schema_evolution.clone(),
+            returning: self.returning,
+            on_conflict: self.on_conflict.clone(),
         }))
     }
 
@@ -77,17 +148,7 @@ impl ExecutionPlan for InsertExec {
         }
 
         let this = self.clone();
-        let stream = stream::once(async move {
-            match this.provider {
-                ProviderReference::RemoteReference(_) => Err(DataFusionError::Internal(
-                    "required table provider, found remote reference to insert".to_string(),
-                )),
-                ProviderReference::Provider(provider) => {
-                    // TODO: Add background job to track storage for native tables.
-                    Self::do_insert(provider, this.source, context).await
-                }
-            }
-        });
+        let stream = stream::once(this.insert(context)).try_flatten();
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -102,49 +163,507 @@ impl ExecutionPlan for InsertExec {
 
 impl DisplayAs for InsertExec {
     fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "InsertExec")
+        match (self.copy_from, self.returning, &self.on_conflict) {
+            (true, _, _) => write!(f, "InsertExec: copy_from=true"),
+            (false, true, _) => write!(f, "InsertExec: returning=true"),
+            (false, false, Some(on_conflict)) => write!(
+                f,
+                "InsertExec: on_conflict=[{}]",
+                on_conflict.columns.join(", ")
+            ),
+            (false, false, None) => write!(f, "InsertExec"),
+        }
     }
 }
 
 impl InsertExec {
+    /// Insert the source into the table, returning the stream to output.
+    ///
+    /// Returned rows are spilled to disk as they're inserted, and read back
+    /// once the insert completes.
+    async fn insert(
+        self,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        let provider = match self.provider {
+            ProviderReference::RemoteReference(_) => {
+                return Err(DataFusionError::Internal(
+                    "required table provider, found remote reference to insert".to_string(),
+                ))
+            }
+            ProviderReference::Provider(provider) => provider,
+        };
+
+        // TODO: Add background job to track storage for native tables.
+        let operation = if self.copy_from { "copy" } else { "insert" };
+
+        let schema = self.schema();
+        let (source, spill): (Arc<dyn ExecutionPlan>, _) = if self.returning {
+            let file = context
+                .runtime_env()
+                .disk_manager
+                .create_tmp_file("InsertExec returning")?;
+            let spill = Arc::new(SpillExec::try_new(
+                Self::single_partition(self.source),
+                file,
+            )?);
+            (spill.clone(), Some(spill))
+        } else {
+            (self.source, None)
+        };
+
+        let batch = match (&self.schema_evolution, &self.on_conflict) {
+            (Some(evolution), _) => {
+                Self::do_evolving_insert(
+                    self.catalog_version,
+                    evolution,
+                    source,
+                    context,
+                    operation,
+                )
+                .await?
+            }
+            (None, Some(on_conflict)) => {
+                Self::do_merge(provider, source, context, on_conflict).await?
+            }
+            (None, None) => Self::do_insert(provider, source, context, operation).await?,
+        };
+
+        match spill {
+            Some(spill) => spill.read_back(),
+            None => Ok(Box::pin(MemoryStream::try_new(vec![batch], schema, None)?)),
+        }
+    }
+
+    /// Insert into a native table while altering its columns to fit the
+    /// source.
+    ///
+    /// The alter is validated by the catalog first, then the altered
+    /// existing rows and the source are written to the table in a single
+    /// commit. The catalog is committed last, restoring the table to its
+    /// previous version if that fails.
+    async fn do_evolving_insert(
+        catalog_version: u64,
+        evolution: &SchemaEvolution,
+        source: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+        operation: &str,
+    ) -> DataFusionResult<RecordBatch> {
+        let mutator = context
+            .session_config()
+            .get_extension::<CatalogMutator>()
+            .expect("context should have catalog mutator");
+
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+
+        let (state, ent) = validate_alter_table(
+            &mutator,
+            catalog_version,
+            &evolution.schema,
+            &evolution.name,
+            &evolution.operations,
+        )
+        .await?;
+
+        let columns = ent.get_internal_columns().unwrap_or_default();
+        let table_schema = Arc::new(Schema::new(
+            columns
+                .iter()
+                .map(|col| Field::new(&col.name, col.arrow_type.clone(), col.nullable))
+                .collect::<Vec<_>>(),
+        ));
+        let exprs = columns
+            .into_iter()
+            .map(|col| column_expr(&evolution.operations, col))
+            .collect::<DataFusionResult<Vec<_>>>()?;
+
+        let source = NotNullCheckExec::wrap(Self::single_partition(source), &table_schema);
+        let session_state = SessionState::new_with_config_rt(
+            context.session_config().clone(),
+            context.runtime_env(),
+        );
+        let (previous_version, inserted_rows) = storage
+            .insert_with_new_schema(&ent, exprs, source, session_state)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        commit_alter_table(
+            &mutator,
+            &storage,
+            catalog_version,
+            state,
+            &ent,
+            Some(previous_version),
+        )
+        .await?;
+
+        Ok(new_operation_with_count_batch(operation, inserted_rows))
+    }
+
+    /// Merge the partitions of `source`, keeping any ordering.
+    fn single_partition(source: Arc<dyn ExecutionPlan>) -> Arc<dyn ExecutionPlan> {
+        match (
+            source.output_partitioning().partition_count(),
+            source.output_ordering(),
+        ) {
+            (1, _) => source,
+            (_, Some(ordering)) => {
+                Arc::new(SortPreservingMergeExec::new(ordering.to_vec(), source))
+            }
+            (_, None) => Arc::new(CoalescePartitionsExec::new(source)),
+        }
+    }
+
     pub async fn do_insert(
         table: Arc<dyn TableProvider>,
         source: Arc<dyn ExecutionPlan>,
         context: Arc<TaskContext>,
+        operation: &str,
     ) -> DataFusionResult<RecordBatch> {
         let state = SessionState::new_with_config_rt(
             context.session_config().clone(),
             context.runtime_env(),
         );
 
-        let source = if source.output_partitioning().partition_count() != 1 {
-            Arc::new(CoalescePartitionsExec::new(source))
-        } else {
-            source
+        let source = NotNullCheckExec::wrap(Self::single_partition(source), &table.schema());
+        let exec = table.insert_into(&state, source, false).await?;
+        let inserted_rows = Self::sum_counts(exec, context).await?;
+
+        Ok(new_operation_with_count_batch(operation, inserted_rows))
+    }
+
+    /// Merge the source into a native table, inserting rows that don't
+    /// conflict with existing rows and handling the rest according to the
+    /// conflict action.
+    async fn do_merge(
+        table: Arc<dyn TableProvider>,
+        source: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+        on_conflict: &OnConflict,
+    ) -> DataFusionResult<RecordBatch> {
+        let table = table
+            .as_any()
+            .downcast_ref::<NativeTable>()
+            .ok_or_else(|| {
+                DataFusionError::Execution(
+                    "ON CONFLICT is only supported for native tables".to_string(),
+                )
+            })?;
+
+        let matched = match &on_conflict.action {
+            OnConflictAction::DoNothing => MergeMatchedAction::DoNothing,
+            OnConflictAction::DoUpdate {
+                assignments,
+                predicate,
+            } => MergeMatchedAction::UpdateSet {
+                assignments: assignments.clone(),
+                predicate: predicate.clone(),
+            },
         };
 
-        let exec = table.insert_into(&state, source, false).await?;
+        let source = NotNullCheckExec::wrap(source, &table.schema());
+        let exec = table.merge_exec(
+            source,
+            on_conflict.columns.clone(),
+            matched,
+            MergeNotMatchedAction::Insert,
+        );
+        let affected_rows = Self::sum_counts(exec, context).await?;
 
+        Ok(new_operation_with_count_batch("insert", affected_rows))
+    }
+
+    /// Drain the output of a write exec, summing the row counts it outputs.
+    async fn sum_counts(
+        exec: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<u64> {
         let mut stream = exec.execute(0, context)?;
 
-        let mut inserted_rows = 0_u64;
+        let mut rows = 0_u64;
         while let Some(res) = stream.next().await {
             // Drain stream to write everything.
             let res = res?;
-            // Each res should have the count of rows inserted.
+            // Each res should have the count of rows written.
             let count =
                 datafusion::arrow::compute::cast(res.column(0).as_ref(), &DataType::UInt64)?;
             for row_idx in 0..count.len() {
                 let s = ScalarValue::try_from_array(&count, row_idx)?;
                 match s {
                     ScalarValue::UInt64(Some(v)) => {
-                        inserted_rows += v;
+                        rows += v;
                     }
                     _ => unreachable!("scalar value should be of UInt64 type"),
                 };
             }
         }
 
-        Ok(new_operation_with_count_batch("insert", inserted_rows))
+        Ok(rows)
+    }
+}
+
+/// Passes through its single partition input, writing each batch to a spill
+/// file so that the rows can be read back once the input has been consumed.
+struct SpillExec {
+    input: Arc<dyn ExecutionPlan>,
+    file: Arc<RefCountedTempFile>,
+    /// Taken once the input has been consumed.
+    writer: Arc<Mutex<Option<IPCWriter>>>,
+}
+
+impl SpillExec {
+    fn try_new(input: Arc<dyn ExecutionPlan>, file: RefCountedTempFile) -> DataFusionResult<Self> {
+        let writer = IPCWriter::new(file.path(), &input.schema())?;
+        Ok(SpillExec {
+            input,
+            file: Arc::new(file),
+            writer: Arc::new(Mutex::new(Some(writer))),
+        })
+    }
+
+    /// Read back the spilled rows.
+    ///
+    /// Errors if the input hasn't been consumed.
+    fn read_back(&self) -> DataFusionResult<SendableRecordBatchStream> {
+        if self.writer.lock().is_some() {
+            return Err(DataFusionError::Internal(
+                "spilled rows read before the input was consumed".to_string(),
+            ));
+        }
+
+        let file = self.file.clone();
+        let mut builder = RecordBatchReceiverStream::builder(self.schema(), 2);
+        let tx = builder.tx();
+        builder.spawn_blocking(move || {
+            let reader = FileReader::try_new(File::open(file.path())?, None)?;
+            for batch in reader {
+                if tx.blocking_send(batch.map_err(Into::into)).is_err() {
+                    // The output stream was dropped.
+                    break;
+                }
+            }
+            Ok(())
+        });
+        Ok(builder.build())
+    }
+}
+
+impl ExecutionPlan for SpillExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        self.input.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Err(DataFusionError::Plan(
+            "Cannot change children for SpillExec".to_string(),
+        ))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "SpillExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let writer = self.writer.clone();
+        let input = self.input.execute(0, context)?;
+        let batches = input.map(move |batch| {
+            let batch = batch?;
+            match writer.lock().as_mut() {
+                Some(writer) => writer.write(&batch)?,
+                None => {
+                    return Err(DataFusionError::Internal(
+                        "SpillExec executed more than once".to_string(),
+                    ))
+                }
+            }
+            Ok(batch)
+        });
+
+        // Finish the file once the input is exhausted.
+        let writer = self.writer.clone();
+        let finish = stream::once(async move {
+            if let Some(mut writer) = writer.lock().take() {
+                writer.finish()?;
+            }
+            Ok::<Option<RecordBatch>, DataFusionError>(None)
+        });
+        let stream = batches
+            .map_ok(Some)
+            .chain(finish)
+            .try_filter_map(futures::future::ok);
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        self.input.statistics()
+    }
+}
+
+impl fmt::Debug for SpillExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpillExec")
+            .field("input", &self.input)
+            .field("file", &self.file)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DisplayAs for SpillExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SpillExec")
+    }
+}
+
+/// Rejects rows with null values for columns the table declares as NOT NULL,
+/// before they reach the table's write path.
+///
+/// The input is expected to already be projected to the table's schema, so
+/// columns are matched by position. Output batches carry the table's
+/// nullability.
+#[derive(Debug)]
+struct NotNullCheckExec {
+    input: Arc<dyn ExecutionPlan>,
+    /// Input schema with the nullability of the table's columns.
+    schema: SchemaRef,
+    /// Non-nullable columns as (index, name).
+    not_null: Vec<(usize, String)>,
+}
+
+impl NotNullCheckExec {
+    /// Wrap `input` if the table has any non-nullable columns.
+    fn wrap(input: Arc<dyn ExecutionPlan>, table_schema: &SchemaRef) -> Arc<dyn ExecutionPlan> {
+        if input.schema().fields().len() != table_schema.fields().len() {
+            return input;
+        }
+        let not_null: Vec<_> = table_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !field.is_nullable())
+            .map(|(idx, field)| (idx, field.name().clone()))
+            .collect();
+        if not_null.is_empty() {
+            return input;
+        }
+        let fields: Vec<_> = input
+            .schema()
+            .fields()
+            .iter()
+            .zip(table_schema.fields())
+            .map(|(field, table_field)| {
+                field
+                    .as_ref()
+                    .clone()
+                    .with_nullable(table_field.is_nullable())
+            })
+            .collect();
+        Arc::new(NotNullCheckExec {
+            input,
+            schema: Arc::new(Schema::new(fields)),
+            not_null,
+        })
+    }
+}
+
+impl ExecutionPlan for NotNullCheckExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.input.output_partitioning()
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(NotNullCheckExec {
+            input: children.first().unwrap().clone(),
+            schema: self.schema.clone(),
+            not_null: self.not_null.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        let schema = self.schema.clone();
+        let not_null = self.not_null.clone();
+        let stream = self.input.execute(partition, context)?.map(move |batch| {
+            let batch = batch?;
+            for (idx, name) in &not_null {
+                if batch.column(*idx).null_count() > 0 {
+                    return Err(DataFusionError::Execution(format!(
+                        "null value in column \"{name}\" violates not-null constraint"
+                    )));
+                }
+            }
+            Ok(RecordBatch::try_new(
+                schema.clone(),
+                batch.columns().to_vec(),
+            )?)
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        self.input.statistics()
+    }
+}
+
+impl DisplayAs for NotNullCheckExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NotNullCheckExec")
     }
 }
@@ -0,0 +1,133 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::array::UInt64Array;
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datafusion::prelude::Expr;
+use datasources::native::access::NativeTableStorage;
+use futures::{stream, StreamExt};
+use protogen::metastore::types::catalog::TableEntry;
+
+use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
+
+/// Overwrite a native table with the output of `source`.
+///
+/// If `replace_where` is set, only rows matching the predicate are replaced,
+/// and every row produced by `source` must match it.
+#[derive(Debug, Clone)]
+pub struct InsertOverwriteExec {
+    pub table: TableEntry,
+    pub source: Arc<dyn ExecutionPlan>,
+    pub replace_where: Option<Expr>,
+}
+
+impl ExecutionPlan for InsertOverwriteExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.source.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(InsertOverwriteExec {
+            table: self.table.clone(),
+            source: children.first().unwrap().clone(),
+            replace_where: self.replace_where.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "InsertOverwriteExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+
+        let stream = stream::once(overwrite(self.clone(), storage, context));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for InsertOverwriteExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.replace_where {
+            Some(predicate) => write!(f, "InsertOverwriteExec: replace_where={predicate}"),
+            None => write!(f, "InsertOverwriteExec"),
+        }
+    }
+}
+
+async fn overwrite(
+    plan: InsertOverwriteExec,
+    storage: impl AsRef<NativeTableStorage>,
+    context: Arc<TaskContext>,
+) -> DataFusionResult<RecordBatch> {
+    let table = storage
+        .as_ref()
+        .load_table(&plan.table)
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to load table: {e}")))?;
+
+    let mut stream = table
+        .overwrite_exec(plan.source, plan.replace_where)
+        .execute(0, context)?;
+
+    // The insert exec emits a single count batch once the write completes.
+    let mut count = 0;
+    while let Some(batch) = stream.next().await {
+        let batch = batch?;
+        if let Some(counts) = batch.column(0).as_any().downcast_ref::<UInt64Array>() {
+            count = counts.iter().flatten().sum();
+        }
+    }
+
+    Ok(new_operation_with_count_batch("overwrite", count))
+}
@@ -1,6 +1,7 @@
 pub mod alter_database;
 pub mod alter_table;
 pub mod alter_tunnel_rotate_keys;
+pub mod cancel_query;
 pub mod client_recv;
 pub mod client_send;
 pub mod copy_to;
@@ -21,13 +22,18 @@ pub mod drop_tables;
 pub mod drop_temp_tables;
 pub mod drop_tunnel;
 pub mod drop_views;
+pub mod explain;
 pub mod insert;
+pub mod insert_overwrite;
+pub mod optimize_table;
 pub mod remote_exec;
 pub mod remote_scan;
 pub mod send_recv;
 pub mod set_var;
 pub mod show_var;
+pub mod truncate_tables;
 pub mod update;
+pub mod vacuum_table;
 pub mod values;
 
 use std::sync::Arc;
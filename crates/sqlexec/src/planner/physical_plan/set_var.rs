@@ -2,7 +2,9 @@ use std::any::Any;
 use std::fmt;
 use std::sync::Arc;
 
-use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::array::StringArray;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
 use datafusion::execution::TaskContext;
 use datafusion::physical_expr::PhysicalSortExpr;
@@ -18,8 +20,17 @@ use datafusion::physical_plan::{
 use datafusion::variable::VarType;
 use datafusion_ext::vars::SessionVars;
 use futures::stream;
-
-use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+use once_cell::sync::Lazy;
+
+/// Output schema for SET, a single row with the operation, and the variable
+/// and value that were set.
+pub static SET_VARIABLE_PHYSICAL_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("$operation", DataType::Utf8, false),
+        Field::new("$variable", DataType::Utf8, false),
+        Field::new("$value", DataType::Utf8, false),
+    ]))
+});
 
 #[derive(Debug, Clone)]
 pub struct SetVarExec {
@@ -33,7 +44,7 @@ impl ExecutionPlan for SetVarExec {
     }
 
     fn schema(&self) -> Arc<Schema> {
-        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+        SET_VARIABLE_PHYSICAL_SCHEMA.clone()
     }
 
     fn output_partitioning(&self) -> Partitioning {
@@ -84,7 +95,15 @@ impl ExecutionPlan for SetVarExec {
             let mut vars = vars.write();
             vars.set(&this.variable, &this.values, VarType::UserDefined)?;
 
-            Ok(new_operation_batch("set"))
+            let batch = RecordBatch::try_new(
+                SET_VARIABLE_PHYSICAL_SCHEMA.clone(),
+                vec![
+                    Arc::new(StringArray::from(vec!["set"])),
+                    Arc::new(StringArray::from(vec![this.variable])),
+                    Arc::new(StringArray::from(vec![this.values])),
+                ],
+            )?;
+            Ok(batch)
         });
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
@@ -0,0 +1,113 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datasources::native::access::{session_connection_id, NativeTableStorage};
+use futures::stream;
+use protogen::metastore::types::catalog::TableEntry;
+use uuid::Uuid;
+
+use super::{new_operation_batch, GENERIC_OPERATION_PHYSICAL_SCHEMA};
+
+#[derive(Debug, Clone)]
+pub struct TruncateTablesExec {
+    pub tables: Vec<TableEntry>,
+}
+
+impl ExecutionPlan for TruncateTablesExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        GENERIC_OPERATION_PHYSICAL_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            Err(DataFusionError::Plan(
+                "Cannot change children for TruncateTablesExec".to_string(),
+            ))
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "TruncateTablesExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+
+        let connection_id = session_connection_id(context.session_config());
+        let stream = stream::once(truncate_tables(self.clone(), storage, connection_id));
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for TruncateTablesExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TruncateTablesExec")
+    }
+}
+
+async fn truncate_tables(
+    plan: TruncateTablesExec,
+    storage: impl AsRef<NativeTableStorage>,
+    connection_id: Uuid,
+) -> DataFusionResult<RecordBatch> {
+    storage
+        .as_ref()
+        .truncate_tables(&plan.tables, connection_id)
+        .await
+        .map_err(|e| DataFusionError::Execution(format!("failed to truncate: {e}")))?;
+
+    Ok(new_operation_batch("truncate"))
+}
@@ -17,9 +17,10 @@ use datafusion::physical_plan::{
     Statistics,
 };
 use datafusion::prelude::Expr;
-use datasources::native::access::NativeTableStorage;
+use datasources::native::access::{session_connection_id, NativeTableStorage};
 use futures::stream;
 use protogen::metastore::types::catalog::TableEntry;
+use uuid::Uuid;
 
 use super::{new_operation_with_count_batch, GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA};
 
@@ -80,7 +81,8 @@ impl ExecutionPlan for UpdateExec {
             .get_extension::<NativeTableStorage>()
             .expect("context should have native table storage");
 
-        let stream = stream::once(update(self.clone(), storage));
+        let connection_id = session_connection_id(context.session_config());
+        let stream = stream::once(update(self.clone(), storage, connection_id));
 
         Ok(Box::pin(RecordBatchStreamAdapter::new(
             self.schema(),
@@ -102,11 +104,12 @@ impl DisplayAs for UpdateExec {
 async fn update(
     plan: UpdateExec,
     storage: impl AsRef<NativeTableStorage>,
+    connection_id: Uuid,
 ) -> DataFusionResult<RecordBatch> {
     let storage = storage.as_ref();
 
     let num_updated = storage
-        .update_rows_where(&plan.table, plan.updates, plan.where_expr)
+        .update_rows_where(&plan.table, plan.updates, plan.where_expr, connection_id)
         .await
         .map_err(|e| DataFusionError::Execution(format!("failed to update: {e}")))?;
 
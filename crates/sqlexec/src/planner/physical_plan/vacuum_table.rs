@@ -0,0 +1,113 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::Schema;
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+use datafusion::execution::TaskContext;
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs,
+    DisplayFormatType,
+    ExecutionPlan,
+    Partitioning,
+    SendableRecordBatchStream,
+    Statistics,
+};
+use datafusion_ext::vars::SessionVars;
+use datasources::native::access::NativeTableStorage;
+use datasources::native::vacuum::VACUUM_SCHEMA;
+use futures::{stream, TryStreamExt};
+use protogen::metastore::types::catalog::TableEntry;
+
+#[derive(Debug, Clone)]
+pub struct VacuumTableExec {
+    pub table: TableEntry,
+    pub retain_hours: Option<u64>,
+    pub dry_run: bool,
+}
+
+impl ExecutionPlan for VacuumTableExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Arc<Schema> {
+        VACUUM_SCHEMA.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(self)
+        } else {
+            Err(DataFusionError::Plan(
+                "Cannot change children for VacuumTableExec".to_string(),
+            ))
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        if partition != 0 {
+            return Err(DataFusionError::Execution(
+                "VacuumTableExec only supports 1 partition".to_string(),
+            ));
+        }
+
+        let storage = context
+            .session_config()
+            .get_extension::<NativeTableStorage>()
+            .expect("context should have native table storage");
+        let enforce_retention = context
+            .session_config()
+            .options()
+            .extensions
+            .get::<SessionVars>()
+            .map_or(true, |vars| vars.vacuum_enforce_retention());
+
+        let this = self.clone();
+        let stream = stream::once(async move {
+            let table = storage.load_table(&this.table).await.map_err(|e| {
+                DataFusionError::Execution(format!("failed to load table for vacuum: {e}"))
+            })?;
+            table
+                .vacuum_exec(this.retain_hours, enforce_retention, this.dry_run)
+                .execute(0, context)
+        })
+        .try_flatten();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> DataFusionResult<Statistics> {
+        Ok(Statistics::new_unknown(self.schema().as_ref()))
+    }
+}
+
+impl DisplayAs for VacuumTableExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VacuumTableExec")
+    }
+}
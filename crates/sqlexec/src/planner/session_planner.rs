@@ -3,6 +3,7 @@ use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use arrow_util::pretty::fmt_dtype;
 use datafusion::arrow::datatypes::{
     DataType,
     Field,
@@ -12,13 +13,25 @@ use datafusion::arrow::datatypes::{
     DECIMAL_DEFAULT_SCALE,
 };
 use datafusion::common::parsers::CompressionTypeVariant;
-use datafusion::common::{OwnedSchemaReference, OwnedTableReference, ToDFSchema};
-use datafusion::logical_expr::{cast, col, LogicalPlanBuilder};
+use datafusion::common::tree_node::{Transformed, TreeNode};
+use datafusion::common::{Column, DFSchema, OwnedSchemaReference, OwnedTableReference, ToDFSchema};
+use datafusion::logical_expr::{
+    cast,
+    col,
+    lit,
+    EmptyRelation,
+    Expr,
+    LogicalPlan as DfLogicalPlan,
+    LogicalPlanBuilder,
+};
+use datafusion::scalar::ScalarValue;
 use datafusion::sql::planner::{object_name_to_table_reference, PlannerContext};
 use datafusion::sql::TableReference;
 use datafusion_ext::conversion::convert;
-use datafusion_ext::planner::SqlQueryPlanner;
+use datafusion_ext::functions::FuncParamValue;
+use datafusion_ext::planner::{infer_func_for_file, InsertSource, SqlQueryPlanner};
 use datafusion_ext::{AsyncContextProvider, IdentNormalizer};
+use datasources::avro::avro_table;
 use datasources::bigquery::{BigQueryAccessor, BigQueryTableAccess};
 use datasources::cassandra::{CassandraAccess, CassandraAccessState};
 use datasources::clickhouse::{ClickhouseAccess, ClickhouseTableRef};
@@ -28,10 +41,11 @@ use datasources::common::url::{DatasourceUrl, DatasourceUrlType};
 use datasources::debug::DebugTableType;
 use datasources::lake::delta::access::{load_table_direct, DeltaLakeAccessor};
 use datasources::lake::iceberg::table::IcebergTable;
-use datasources::lake::storage_options_into_object_store;
+use datasources::lake::{storage_options_into_object_store, storage_options_into_store_access};
 use datasources::lance::LanceTable;
 use datasources::mongodb::{MongoDbAccessor, MongoDbConnection};
 use datasources::mysql::{MysqlAccessor, MysqlDbConnection, MysqlTableAccess};
+use datasources::native::insert::{MERGE_SOURCE_ALIAS, MERGE_TARGET_ALIAS};
 use datasources::object_store::azure::AzureStoreAccess;
 use datasources::object_store::gcs::GcsStoreAccess;
 use datasources::object_store::local::LocalStoreAccess;
@@ -43,15 +57,18 @@ use datasources::sqlserver::SqlServerAccess;
 use object_store::aws::AmazonS3ConfigKey;
 use object_store::azure::AzureConfigKey;
 use object_store::gcp::GoogleConfigKey;
-use parser::options::StatementOptions;
+use parser::options::{OptionValue, StatementOptions};
 use parser::sqlparser::ast::{
     self,
     ColumnOption,
+    CommentObject,
     DescribeAlias,
     FromTable,
     Ident,
     ObjectName,
     ObjectType,
+    SelectItem,
+    ShowStatementFilter,
 };
 use parser::{
     self,
@@ -61,6 +78,8 @@ use parser::{
     AlterTableStmtExtension,
     AlterTunnelAction,
     AlterTunnelStmt,
+    CloseCursorStmt,
+    CopyFromStmt,
     CopyToSource,
     CopyToStmt,
     CreateCredentialStmt,
@@ -68,10 +87,17 @@ use parser::{
     CreateExternalDatabaseStmt,
     CreateExternalTableStmt,
     CreateTunnelStmt,
+    DeclareCursorStmt,
     DropCredentialsStmt,
     DropDatabaseStmt,
     DropTunnelStmt,
+    FetchStmt,
+    InsertOverwriteStmt,
+    KillStmt,
+    OptimizeStmt,
     StatementWithExtensions,
+    TruncateStmt,
+    VacuumStmt,
 };
 use protogen::metastore::types::catalog::{
     CatalogEntry,
@@ -81,6 +107,7 @@ use protogen::metastore::types::catalog::{
     TableEntry,
 };
 use protogen::metastore::types::options::{
+    CopyToCompression,
     CopyToDestinationOptions,
     CopyToDestinationOptionsAzure,
     CopyToDestinationOptionsGcs,
@@ -113,6 +140,7 @@ use protogen::metastore::types::options::{
     DatabaseOptionsSqlite,
     DeltaLakeCatalog,
     DeltaLakeUnityCatalog,
+    InternalColumnDefinition,
     StorageOptions,
     TableOptionsBigQuery,
     TableOptionsCassandra,
@@ -145,9 +173,11 @@ use sqlbuiltins::validation::{
     validate_table_tunnel_support,
 };
 use tracing::debug;
+use uuid::Uuid;
 
 use super::context_builder::PartialContextProvider;
 use super::extension::ExtensionNode;
+use super::physical_plan::insert::{OnConflict, OnConflictAction, SchemaEvolution};
 use super::physical_plan::remote_scan::ProviderReference;
 use crate::context::local::LocalSessionContext;
 use crate::planner::errors::{internal, PlanError, Result};
@@ -155,6 +185,7 @@ use crate::planner::logical_plan::{
     AlterDatabase,
     AlterTable,
     AlterTunnelRotateKeys,
+    CancelQuery,
     CopyTo,
     CreateCredentials,
     CreateExternalDatabase,
@@ -164,6 +195,7 @@ use crate::planner::logical_plan::{
     CreateTempTable,
     CreateTunnel,
     CreateView,
+    CursorPlan,
     Delete,
     DescribeTable,
     DropCredentials,
@@ -174,11 +206,16 @@ use crate::planner::logical_plan::{
     DropViews,
     FullObjectReference,
     Insert,
+    InsertOverwrite,
     LogicalPlan,
+    OptimizeTable,
     SetVariable,
     ShowVariable,
     TransactionPlan,
+    TruncateTables,
     Update,
+    VacuumTable,
+    DESCRIBE_TABLE_LOGICAL_SCHEMA,
 };
 use crate::planner::preprocess::{preprocess, CastOIDReplacer, EscapedStringToDoubleQuoted};
 use crate::remote::table::StubRemoteTableProvider;
@@ -250,7 +287,7 @@ impl<'a> SessionPlanner<'a> {
             StatementWithExtensions::DropDatabase(stmt) => self.plan_drop_database(stmt),
             StatementWithExtensions::AlterDatabase(stmt) => self.plan_alter_database(stmt),
             StatementWithExtensions::AlterTableExtension(stmt) => {
-                self.plan_alter_table_extension(stmt)
+                self.plan_alter_table_extension(stmt).await
             }
             StatementWithExtensions::CreateTunnel(stmt) => self.plan_create_tunnel(stmt),
             StatementWithExtensions::DropTunnel(stmt) => self.plan_drop_tunnel(stmt),
@@ -260,6 +297,17 @@ impl<'a> SessionPlanner<'a> {
             }
             StatementWithExtensions::DropCredentials(stmt) => self.plan_drop_credentials(stmt),
             StatementWithExtensions::CopyTo(stmt) => self.plan_copy_to(stmt).await,
+            StatementWithExtensions::CopyFrom(stmt) => self.plan_copy_from(stmt).await,
+            StatementWithExtensions::InsertOverwrite(stmt) => {
+                self.plan_insert_overwrite(stmt).await
+            }
+            StatementWithExtensions::Vacuum(stmt) => self.plan_vacuum(stmt),
+            StatementWithExtensions::Optimize(stmt) => self.plan_optimize(stmt),
+            StatementWithExtensions::Truncate(stmt) => self.plan_truncate(stmt),
+            StatementWithExtensions::Kill(stmt) => self.plan_kill(stmt),
+            StatementWithExtensions::DeclareCursor(stmt) => self.plan_declare_cursor(stmt).await,
+            StatementWithExtensions::Fetch(stmt) => self.plan_fetch(stmt),
+            StatementWithExtensions::CloseCursor(stmt) => self.plan_close_cursor(stmt),
         }
     }
 
@@ -912,6 +960,62 @@ impl<'a> SessionPlanner<'a> {
                     schema_sample_size,
                 })
             }
+            TableOptionsV0::NDJSON => {
+                let location: String = m.remove_required("location")?;
+                let mut storage_options = StorageOptions::try_from(m)?;
+                if let Some(creds) = creds_options {
+                    storage_options_with_credentials(&mut storage_options, creds);
+                }
+                let schema_sample_size = Some(
+                    storage_options
+                        .inner
+                        .get("schema_sample_size")
+                        .map(|strint| strint.parse())
+                        .unwrap_or(Ok(100))?,
+                );
+                if let Some(ignore_errors) = storage_options.inner.get("ignore_errors") {
+                    ignore_errors.parse::<bool>().map_err(|_| {
+                        PlanError::String(format!(
+                            "invalid value for 'ignore_errors': {ignore_errors}"
+                        ))
+                    })?;
+                }
+                TableOptionsV0::Ndjson(TableOptionsObjectStore {
+                    location,
+                    storage_options,
+                    name: None,
+                    file_type: Some(TableOptionsV0::NDJSON.to_string()),
+                    compression: None,
+                    schema_sample_size,
+                })
+            }
+            TableOptionsV0::AVRO => {
+                let location: String = m.remove_required("location")?;
+                let mut storage_options = StorageOptions::try_from(m)?;
+                if let Some(creds) = creds_options {
+                    storage_options_with_credentials(&mut storage_options, creds);
+                }
+                // Validate that the objects exist and their schemas can be
+                // read.
+                let source_url = DatasourceUrl::try_new(&location)?;
+                let store_access = storage_options_into_store_access(&source_url, &storage_options)
+                    .map_err(|e| PlanError::InvalidExternalTable {
+                        source: Box::new(e),
+                    })?;
+                let _table = avro_table(store_access, source_url, &self.ctx.df_ctx().state())
+                    .await
+                    .map_err(|e| PlanError::InvalidExternalTable {
+                        source: Box::new(e),
+                    })?;
+                TableOptionsV0::Avro(TableOptionsObjectStore {
+                    location,
+                    storage_options,
+                    name: None,
+                    file_type: Some(TableOptionsV0::AVRO.to_string()),
+                    compression: None,
+                    schema_sample_size: None,
+                })
+            }
             TableOptionsV0::EXCEL => {
                 let location: String = m.remove_required("location")?;
                 let mut storage_options = StorageOptions::try_from(m)?;
@@ -1123,7 +1227,17 @@ impl<'a> SessionPlanner<'a> {
         match statement {
             ast::Statement::StartTransaction { .. } => Ok(TransactionPlan::Begin.into()),
             ast::Statement::Commit { .. } => Ok(TransactionPlan::Commit.into()),
+            ast::Statement::Rollback {
+                savepoint: Some(name),
+                ..
+            } => Ok(TransactionPlan::RollbackToSavepoint(normalize_ident(name)).into()),
             ast::Statement::Rollback { .. } => Ok(TransactionPlan::Abort.into()),
+            ast::Statement::Savepoint { name } => {
+                Ok(TransactionPlan::Savepoint(normalize_ident(name)).into())
+            }
+            ast::Statement::ReleaseSavepoint { name } => {
+                Ok(TransactionPlan::ReleaseSavepoint(normalize_ident(name)).into())
+            }
 
             ast::Statement::Query(q) => {
                 let mut planner = SqlQueryPlanner::new(&mut context_provider);
@@ -1144,6 +1258,25 @@ impl<'a> SessionPlanner<'a> {
                     .await?;
                 Ok(LogicalPlan::Datafusion(plan))
             }
+            // DESCRIBE <query>
+            ast::Statement::Explain {
+                describe_alias: DescribeAlias::Describe,
+                statement,
+                ..
+            } => {
+                let query = match *statement {
+                    ast::Statement::Query(query) => query,
+                    other => {
+                        return Err(PlanError::UnsupportedSQLStatement(format!(
+                            "DESCRIBE {other}"
+                        )))
+                    }
+                };
+                // Only plan the query, it's never executed.
+                let mut planner = SqlQueryPlanner::new(&mut context_provider);
+                let plan = planner.query_to_plan(*query).await?;
+                Ok(LogicalPlan::Datafusion(describe_plan_schema(&plan)?))
+            }
             // DESCRIBE <table_name>
             ast::Statement::ExplainTable {
                 describe_alias: DescribeAlias::Describe,
@@ -1204,6 +1337,8 @@ impl<'a> SessionPlanner<'a> {
                 columns,
                 query,
                 temporary,
+                hive_distribution,
+                like,
                 ..
             } => {
                 validate_object_name(&name)?;
@@ -1251,19 +1386,78 @@ impl<'a> SessionPlanner<'a> {
                         .build()?;
 
                     (Some(source), fields)
+                } else if let Some(like) = like {
+                    // CREATE TABLE <name> LIKE <table>
+                    //
+                    // Copies the column names, types and nullability of the
+                    // existing table, without any of its data.
+                    if !columns.is_empty() {
+                        return Err(PlanError::String(
+                            "Cannot specify columns with CREATE TABLE ... LIKE".to_string(),
+                        ));
+                    }
+                    validate_object_name(&like)?;
+                    let like = object_name_to_table_ref(like)?;
+                    let like_source = context_provider.get_table_source(like).await?;
+                    let fields = like_source
+                        .schema()
+                        .fields()
+                        .iter()
+                        .map(|field| field.as_ref().clone())
+                        .collect();
+                    (None, fields)
                 } else {
                     let mut arrow_cols = Vec::with_capacity(columns.len());
                     for column in columns.into_iter() {
                         validate_ident(&column.name)?;
                         let name = normalize_ident(column.name);
                         let data_type = convert_data_type(&column.data_type)?;
-                        let field = Field::new(name, data_type, /* nullable = */ true);
+                        let nullable = !column
+                            .options
+                            .iter()
+                            .any(|opt| matches!(opt.option, ColumnOption::NotNull));
+                        let field = Field::new(name, data_type, nullable);
                         arrow_cols.push(field);
                     }
                     (None, arrow_cols)
                 };
 
+                let partition_by = match hive_distribution {
+                    ast::HiveDistributionStyle::NONE => Vec::new(),
+                    ast::HiveDistributionStyle::PARTITIONED { columns } => {
+                        let mut partition_by = Vec::with_capacity(columns.len());
+                        for column in columns {
+                            if column.data_type != ast::DataType::Unspecified {
+                                return Err(PlanError::UnsupportedFeature(
+                                    "PARTITIONED BY with column types",
+                                ));
+                            }
+                            validate_ident(&column.name)?;
+                            let name = normalize_ident(column.name);
+                            if !arrow_cols.iter().any(|field| field.name() == &name) {
+                                return Err(PlanError::String(format!(
+                                    "Partition column '{name}' does not exist in table"
+                                )));
+                            }
+                            partition_by.push(name);
+                        }
+                        if partition_by.len() == arrow_cols.len() {
+                            return Err(PlanError::String(
+                                "Cannot partition a table by all of its columns".to_string(),
+                            ));
+                        }
+                        partition_by
+                    }
+                    _ => return Err(PlanError::UnsupportedFeature("table distribution options")),
+                };
+
                 if temporary {
+                    if !partition_by.is_empty() {
+                        return Err(PlanError::UnsupportedFeature(
+                            "PARTITIONED BY with temporary tables",
+                        ));
+                    }
+
                     let table_name = match table_name {
                         TableReference::Bare { table } => table.into_owned(),
                         _ => return Err(internal!("cannot specify schema with temporary tables")),
@@ -1293,6 +1487,7 @@ impl<'a> SessionPlanner<'a> {
                         if_not_exists,
                         or_replace,
                         source,
+                        partition_by,
                     };
                     Ok(create_table.into_logical_plan())
                 }
@@ -1326,6 +1521,22 @@ impl<'a> SessionPlanner<'a> {
 
                 let query_string = query.to_string();
 
+                // When replacing a view, the new body must not reference the
+                // view being replaced, even indirectly.
+                let existing = if or_replace {
+                    EntryResolver::from_context(self.ctx)
+                        .resolve_entry_from_reference(name.clone())
+                        .ok()
+                } else {
+                    None
+                };
+                let _guard = match &existing {
+                    Some(ResolvedEntry::Entry(CatalogEntry::View(view))) => {
+                        Some(self.ctx.begin_view_planning(view)?)
+                    }
+                    _ => None,
+                };
+
                 // Check that this is a valid body.
                 // TODO: Avoid cloning.
                 let mut planner = SqlQueryPlanner::new(&mut context_provider);
@@ -1363,8 +1574,8 @@ impl<'a> SessionPlanner<'a> {
                 partitioned: None,
                 after_columns,
                 table: false,
-                on: None,
-                returning: None,
+                on,
+                returning,
                 ignore: _,
                 ..
             } if after_columns.is_empty() => {
@@ -1372,60 +1583,15 @@ impl<'a> SessionPlanner<'a> {
                     msg: "Nothing to insert: source empty",
                 })?;
 
-                validate_object_name(&table_name)?;
-                let table_name = object_name_to_table_ref(table_name)?;
-
-                let columns = columns
-                    .into_iter()
-                    .map(|col| {
-                        validate_ident(&col)?;
-                        Ok(normalize_ident(col))
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-
-                let mut planner = SqlQueryPlanner::new(&mut context_provider);
-                let source = planner
-                    .insert_to_source_plan(&table_name, &columns, source)
-                    .await?;
-
-                let access_mode = self
-                    .get_access_mode(table_name.clone())?
-                    .unwrap_or(SourceAccessMode::ReadOnly);
-
-                if !access_mode.has_write_access() {
-                    return Err(PlanError::ObjectNotAllowedToWriteInto(
-                        table_name.to_owned_reference(),
-                    ));
-                }
-
-                let state = self.ctx.df_ctx().state();
-                let mut ctx_provider = PartialContextProvider::new(self.ctx, &state)?;
-
-                let provider = ctx_provider.table_provider(table_name).await?;
-
-                let (runtime_preference, provider) = match (
-                    provider.preference,
-                    provider
-                        .provider
-                        .as_any()
-                        .downcast_ref::<StubRemoteTableProvider>(),
-                ) {
-                    (RuntimePreference::Remote, Some(stub)) => (
-                        RuntimePreference::Remote,
-                        ProviderReference::RemoteReference(stub.id()),
-                    ),
-                    _ => (
-                        RuntimePreference::Local,
-                        ProviderReference::Provider(provider.provider),
-                    ),
-                };
-
-                Ok(Insert {
-                    source,
-                    provider,
-                    runtime_preference,
-                }
-                .into_logical_plan())
+                self.plan_insert(
+                    table_name,
+                    columns,
+                    InsertSource::Query(source),
+                    returning,
+                    on,
+                    false,
+                )
+                .await
             }
 
             ast::Statement::AlterTable {
@@ -1440,15 +1606,15 @@ impl<'a> SessionPlanner<'a> {
                 }
                 let operation = operations.pop().unwrap();
 
-                match operation {
-                    ast::AlterTableOperation::RenameTable { table_name } => {
-                        validate_object_name(&name)?;
-                        let name = object_name_to_table_ref(name)?;
-                        let name = self.ctx.resolve_table_ref(name)?;
+                validate_object_name(&name)?;
+                let name = object_name_to_table_ref(name)?;
+                let name = self.ctx.resolve_table_ref(name)?;
 
-                        let schema = name.schema.into_owned();
-                        let name = name.name.into_owned();
+                let schema = name.schema.into_owned();
+                let name = name.name.into_owned();
 
+                let operation = match operation {
+                    ast::AlterTableOperation::RenameTable { table_name } => {
                         let new_name = match table_name {
                             ObjectName(mut objs) if objs.len() == 1 => objs.pop().unwrap(),
                             _ => {
@@ -1460,15 +1626,80 @@ impl<'a> SessionPlanner<'a> {
                         validate_ident(&new_name)?;
                         let new_name = normalize_ident(new_name);
 
-                        Ok(AlterTable {
-                            schema,
-                            name,
-                            operation: AlterTableOperation::RenameTable { new_name },
+                        AlterTableOperation::RenameTable { new_name }
+                    }
+                    ast::AlterTableOperation::RenameColumn {
+                        old_column_name,
+                        new_column_name,
+                    } => {
+                        validate_ident(&new_column_name)?;
+                        AlterTableOperation::RenameColumn {
+                            old_name: normalize_ident(old_column_name),
+                            new_name: normalize_ident(new_column_name),
+                        }
+                    }
+                    ast::AlterTableOperation::AddColumn { column_def, .. } => {
+                        validate_ident(&column_def.name)?;
+                        let arrow_type = convert_data_type(&column_def.data_type)?;
+                        AlterTableOperation::AddColumn {
+                            name: normalize_ident(column_def.name),
+                            arrow_type,
+                        }
+                    }
+                    ast::AlterTableOperation::DropColumn {
+                        column_name,
+                        if_exists,
+                        ..
+                    } => AlterTableOperation::DropColumn {
+                        name: normalize_ident(column_name),
+                        if_exists,
+                    },
+                    other => return Err(PlanError::UnsupportedSQLStatement(other.to_string())),
+                };
+
+                Ok(AlterTable {
+                    schema,
+                    name,
+                    operation,
+                }
+                .into_logical_plan())
+            }
+
+            // COMMENT ON TABLE/COLUMN
+            ast::Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                if_exists,
+            } => {
+                if if_exists {
+                    return Err(PlanError::UnsupportedFeature("COMMENT IF EXISTS"));
+                }
+
+                let (table_name, column) = match object_type {
+                    CommentObject::Table => (object_name, None),
+                    CommentObject::Column => {
+                        let ObjectName(mut idents) = object_name;
+                        if idents.len() < 2 {
+                            return Err(PlanError::String(
+                                "column name must be qualified with a table name".to_string(),
+                            ));
                         }
-                        .into_logical_plan())
+                        let column = normalize_ident(idents.pop().unwrap());
+                        (ObjectName(idents), Some(column))
                     }
-                    other => Err(PlanError::UnsupportedSQLStatement(other.to_string())),
+                };
+
+                validate_object_name(&table_name)?;
+                let name = object_name_to_table_ref(table_name)?;
+                let name = self.ctx.resolve_table_ref(name)?;
+
+                Ok(AlterTable {
+                    schema: name.schema.into_owned(),
+                    name: name.name.into_owned(),
+                    operation: AlterTableOperation::SetComment { column, comment },
                 }
+                .into_logical_plan())
             }
 
             // Drop tables
@@ -1555,6 +1786,16 @@ impl<'a> SessionPlanner<'a> {
                 // Normalize variables
                 let mut variable: Vec<_> = variable.into_iter().map(normalize_ident).collect();
 
+                // SHOW SCHEMAS
+                if variable == ["schemas"] {
+                    return self
+                        .plan_catalog_query(
+                            "SELECT schema_name FROM glare_catalog.schemas ORDER BY schema_name"
+                                .to_string(),
+                        )
+                        .await;
+                }
+
                 let variable = if is_show_transaction_isolation_level(&variable) {
                     // SHOW TRANSACTION ISOLATION LEVEL
                     // Alias of "SHOW transaction_isolation".
@@ -1570,6 +1811,65 @@ impl<'a> SessionPlanner<'a> {
                 Ok(ShowVariable::new(variable).into_logical_plan())
             }
 
+            // SHOW TABLES [{FROM | IN} <schema>] [LIKE <pattern> | WHERE <expr>]
+            //
+            // Lists tables and views in a schema. Defaults to the first schema
+            // in the search path.
+            ast::Statement::ShowTables {
+                db_name, filter, ..
+            } => {
+                let schema = match db_name {
+                    Some(ident) => normalize_ident(ident),
+                    None => self.ctx.first_nonimplicit_schema()?,
+                };
+                let schema = quote_literal(&schema);
+
+                let query = format!(
+                    "SELECT * FROM (\
+                        SELECT schema_name AS table_schema, table_name, \
+                            CASE WHEN external THEN 'FOREIGN' ELSE 'BASE TABLE' END AS table_type \
+                        FROM glare_catalog.tables WHERE schema_name = {schema} \
+                        UNION ALL \
+                        SELECT schema_name AS table_schema, view_name AS table_name, 'VIEW' AS table_type \
+                        FROM glare_catalog.views WHERE schema_name = {schema}\
+                    ) AS t{} ORDER BY table_name",
+                    show_filter_predicate(filter, "table_name"),
+                );
+                self.plan_catalog_query(query).await
+            }
+
+            // SHOW COLUMNS FROM <table> [LIKE <pattern> | WHERE <expr>]
+            ast::Statement::ShowColumns {
+                table_name, filter, ..
+            } => {
+                validate_object_name(&table_name)?;
+                let table_name = object_name_to_table_ref(table_name)?;
+                let resolver = EntryResolver::from_context(self.ctx);
+                let entry = resolver
+                    .resolve_entry_from_reference(table_name)?
+                    .try_into_table_entry()?;
+
+                let query = format!(
+                    "SELECT * FROM (\
+                        SELECT column_name, data_type, is_nullable, column_ordinal \
+                        FROM glare_catalog.columns WHERE table_oid = {}\
+                    ) AS t{} ORDER BY column_ordinal",
+                    entry.meta.id,
+                    show_filter_predicate(filter, "column_name"),
+                );
+                let plan = match self.plan_catalog_query(query).await? {
+                    LogicalPlan::Datafusion(plan) => LogicalPlanBuilder::from(plan)
+                        .project(vec![
+                            col("column_name"),
+                            col("data_type"),
+                            col("is_nullable"),
+                        ])?
+                        .build()?,
+                    _ => unreachable!("catalog queries are always datafusion plans"),
+                };
+                Ok(LogicalPlan::Datafusion(plan))
+            }
+
             // "DELETE FROM <table> WHERE <expression>"
             //
             // deletes rows from a table that matches the expression.
@@ -1756,6 +2056,112 @@ impl<'a> SessionPlanner<'a> {
         .into_logical_plan())
     }
 
+    fn plan_vacuum(&self, stmt: VacuumStmt) -> Result<LogicalPlan> {
+        validate_object_name(&stmt.table)?;
+        let table_name = object_name_to_table_ref(stmt.table)?;
+
+        let resolver = EntryResolver::from_context(self.ctx);
+        let table = resolver
+            .resolve_entry_from_reference(table_name)?
+            .try_into_table_entry()?;
+        if table.meta.external {
+            return Err(PlanError::UnsupportedFeature("VACUUM with external tables"));
+        }
+
+        Ok(VacuumTable {
+            table,
+            retain_hours: stmt.retain_hours,
+            dry_run: stmt.dry_run,
+        }
+        .into_logical_plan())
+    }
+
+    fn plan_truncate(&self, stmt: TruncateStmt) -> Result<LogicalPlan> {
+        let resolver = EntryResolver::from_context(self.ctx);
+
+        let mut tables = Vec::with_capacity(stmt.tables.len());
+        for name in stmt.tables {
+            validate_object_name(&name)?;
+            let table_name = object_name_to_table_ref(name)?;
+            let table = resolver
+                .resolve_entry_from_reference(table_name)?
+                .try_into_table_entry()?;
+            if table.meta.external {
+                return Err(PlanError::UnsupportedFeature(
+                    "TRUNCATE with external tables",
+                ));
+            }
+            if table.meta.is_temp {
+                return Err(PlanError::UnsupportedFeature(
+                    "TRUNCATE with temporary tables",
+                ));
+            }
+            tables.push(table);
+        }
+
+        Ok(TruncateTables { tables }.into_logical_plan())
+    }
+
+    fn plan_kill(&self, stmt: KillStmt) -> Result<LogicalPlan> {
+        let connection_id = Uuid::parse_str(&stmt.connection_id)
+            .map_err(|_| PlanError::InvalidConnectionId(stmt.connection_id))?;
+        Ok(CancelQuery { connection_id }.into_logical_plan())
+    }
+
+    async fn plan_declare_cursor(&self, stmt: DeclareCursorStmt) -> Result<LogicalPlan> {
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        let plan = planner.query_to_plan(*stmt.query).await?;
+        Ok(CursorPlan::Declare {
+            name: normalize_ident(stmt.name),
+            plan,
+        }
+        .into())
+    }
+
+    fn plan_fetch(&self, stmt: FetchStmt) -> Result<LogicalPlan> {
+        Ok(CursorPlan::Fetch {
+            name: normalize_ident(stmt.name),
+            count: stmt.count,
+        }
+        .into())
+    }
+
+    fn plan_close_cursor(&self, stmt: CloseCursorStmt) -> Result<LogicalPlan> {
+        Ok(CursorPlan::Close(stmt.name.map(normalize_ident)).into())
+    }
+
+    fn plan_optimize(&self, stmt: OptimizeStmt) -> Result<LogicalPlan> {
+        validate_object_name(&stmt.table)?;
+        let table_name = object_name_to_table_ref(stmt.table)?;
+
+        let resolver = EntryResolver::from_context(self.ctx);
+        let table = resolver
+            .resolve_entry_from_reference(table_name)?
+            .try_into_table_entry()?;
+        if table.meta.external {
+            return Err(PlanError::UnsupportedFeature(
+                "OPTIMIZE with external tables",
+            ));
+        }
+
+        let columns = table.get_internal_columns().unwrap_or_default();
+        let mut zorder_by = Vec::with_capacity(stmt.zorder_by.len());
+        for col in stmt.zorder_by {
+            let col = normalize_ident(col);
+            if !columns.iter().any(|c| c.name == col) {
+                return Err(PlanError::String(format!(
+                    "Column '{col}' does not exist in table '{}'",
+                    table.meta.name
+                )));
+            }
+            zorder_by.push(col);
+        }
+
+        Ok(OptimizeTable { table, zorder_by }.into_logical_plan())
+    }
+
     fn plan_alter_tunnel(&self, stmt: AlterTunnelStmt) -> Result<LogicalPlan> {
         validate_ident(&stmt.name)?;
         let name = normalize_ident(stmt.name);
@@ -1795,10 +2201,13 @@ impl<'a> SessionPlanner<'a> {
         Ok(AlterDatabase { name, operation }.into_logical_plan())
     }
 
-    fn plan_alter_table_extension(&self, stmt: AlterTableStmtExtension) -> Result<LogicalPlan> {
+    async fn plan_alter_table_extension(
+        &self,
+        stmt: AlterTableStmtExtension,
+    ) -> Result<LogicalPlan> {
         validate_object_name(&stmt.name)?;
-        let name = object_name_to_table_ref(stmt.name)?;
-        let name = self.ctx.resolve_table_ref(name)?;
+        let table_ref = object_name_to_table_ref(stmt.name)?;
+        let name = self.ctx.resolve_table_ref(table_ref.clone())?;
         let schema = name.schema.into_owned();
         let name = name.name.into_owned();
 
@@ -1808,6 +2217,16 @@ impl<'a> SessionPlanner<'a> {
                     .map_err(|e| PlanError::String(format!("{e}")))?;
                 AlterTableOperation::SetAccessMode { access_mode }
             }
+            parser::AlterTableOperationExtension::Refresh => {
+                let state = self.ctx.df_ctx().state();
+                let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+                let schema = context_provider
+                    .infer_external_table_schema(table_ref)
+                    .await?;
+                let columns =
+                    InternalColumnDefinition::from_arrow_fields(schema.fields()).collect();
+                AlterTableOperation::SetColumns { columns }
+            }
         };
 
         Ok(AlterTable {
@@ -1818,6 +2237,450 @@ impl<'a> SessionPlanner<'a> {
         .into_logical_plan())
     }
 
+    /// Plan an insert of `source` into `table_name`.
+    ///
+    /// If `returning` is provided, the insert outputs the result of the
+    /// RETURNING expressions for the inserted rows instead of the number of
+    /// rows inserted.
+    ///
+    /// `copy_from` marks inserts planned from a `COPY ... FROM` statement so
+    /// that the result is reported as a copy.
+    async fn plan_insert(
+        &self,
+        table_name: ObjectName,
+        columns: Vec<Ident>,
+        source: InsertSource,
+        returning: Option<Vec<SelectItem>>,
+        on: Option<ast::OnInsert>,
+        copy_from: bool,
+    ) -> Result<LogicalPlan> {
+        validate_object_name(&table_name)?;
+        let table_name = object_name_to_table_ref(table_name)?;
+
+        let columns = columns
+            .into_iter()
+            .map(|col| {
+                validate_ident(&col)?;
+                Ok(normalize_ident(col))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        let (source, schema_evolution) = match self.schema_evolution_target(&table_name) {
+            Some(ent) => {
+                let (source, schema) = planner
+                    .insert_to_evolved_source_plan(&table_name, &columns, source)
+                    .await?;
+                (source, self.plan_schema_evolution(&ent, &schema)?)
+            }
+            None => {
+                let source = planner
+                    .insert_to_source_plan(&table_name, &columns, source)
+                    .await?;
+                (source, None)
+            }
+        };
+
+        let access_mode = self
+            .get_access_mode(table_name.clone())?
+            .unwrap_or(SourceAccessMode::ReadOnly);
+
+        if !access_mode.has_write_access() {
+            return Err(PlanError::ObjectNotAllowedToWriteInto(
+                table_name.to_owned_reference(),
+            ));
+        }
+
+        let returning = match returning {
+            Some(returning) => Some(
+                planner
+                    .insert_returning_to_exprs(&source, returning)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        let on_conflict = match on {
+            Some(_) if returning.is_some() => {
+                return Err(PlanError::UnsupportedFeature("RETURNING with ON CONFLICT"))
+            }
+            // The merge can't alter the table's columns in the same commit.
+            Some(_) if schema_evolution.is_some() => {
+                return Err(PlanError::UnsupportedFeature(
+                    "schema evolution with ON CONFLICT",
+                ))
+            }
+            Some(on) => Some(
+                self.plan_on_conflict(&mut planner, &table_name, &source, on)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        let provider = context_provider.table_provider(table_name).await?;
+
+        let (runtime_preference, provider) = match (
+            provider.preference,
+            provider
+                .provider
+                .as_any()
+                .downcast_ref::<StubRemoteTableProvider>(),
+        ) {
+            (RuntimePreference::Remote, Some(stub)) => (
+                RuntimePreference::Remote,
+                ProviderReference::RemoteReference(stub.id()),
+            ),
+            _ => (
+                RuntimePreference::Local,
+                ProviderReference::Provider(provider.provider),
+            ),
+        };
+
+        let insert = Insert {
+            source,
+            provider,
+            runtime_preference,
+            copy_from,
+            schema_evolution,
+            returning: returning.is_some(),
+            on_conflict,
+        };
+
+        match returning {
+            Some(exprs) => {
+                let plan =
+                    LogicalPlanBuilder::from(DfLogicalPlan::Extension(insert.into_extension()))
+                        .project(exprs)?
+                        .build()?;
+                Ok(LogicalPlan::Datafusion(plan))
+            }
+            None => Ok(insert.into_logical_plan()),
+        }
+    }
+
+    /// Plan the ON CONFLICT clause of an insert into `table_name`.
+    ///
+    /// Only native tables are supported. Rows conflict when they're equal on
+    /// all of the conflict target columns. DO UPDATE expressions may reference
+    /// the existing row by the table's name (or unqualified), and the row
+    /// proposed for insertion as `excluded`.
+    async fn plan_on_conflict(
+        &self,
+        planner: &mut SqlQueryPlanner<'_, PartialContextProvider<'_>>,
+        table_name: &OwnedTableReference,
+        source: &DfLogicalPlan,
+        on: ast::OnInsert,
+    ) -> Result<OnConflict> {
+        let on_conflict = match on {
+            ast::OnInsert::OnConflict(on_conflict) => on_conflict,
+            _ => return Err(PlanError::UnsupportedFeature("ON DUPLICATE KEY UPDATE")),
+        };
+
+        let is_native = EntryResolver::from_context(self.ctx)
+            .resolve_entry_from_reference(table_name.clone())
+            .ok()
+            .and_then(|ent| ent.try_into_table_entry().ok())
+            .is_some_and(|ent| !ent.meta.external && !ent.meta.is_temp);
+        if !is_native {
+            return Err(PlanError::UnsupportedFeature(
+                "ON CONFLICT for tables other than native tables",
+            ));
+        }
+
+        let source_schema = Schema::from(source.schema().as_ref());
+        let check_column = |col: &str| {
+            source_schema.field_with_name(col).map(|_| ()).map_err(|_| {
+                PlanError::String(format!(
+                    "Column '{col}' does not exist in table '{table_name}'"
+                ))
+            })
+        };
+
+        let columns = match on_conflict.conflict_target {
+            Some(ast::ConflictTarget::Columns(columns)) => columns
+                .into_iter()
+                .map(|col| {
+                    validate_ident(&col)?;
+                    let col = normalize_ident(col);
+                    check_column(&col)?;
+                    Ok(col)
+                })
+                .collect::<Result<Vec<_>>>()?,
+            Some(ast::ConflictTarget::OnConstraint(_)) => {
+                return Err(PlanError::UnsupportedFeature("ON CONFLICT ON CONSTRAINT"))
+            }
+            None => {
+                return Err(PlanError::InvalidInsertStatement {
+                    msg: "ON CONFLICT requires conflict target columns",
+                })
+            }
+        };
+
+        let do_update = match on_conflict.action {
+            ast::OnConflictAction::DoNothing => {
+                return Ok(OnConflict {
+                    columns,
+                    action: OnConflictAction::DoNothing,
+                })
+            }
+            ast::OnConflictAction::DoUpdate(do_update) => do_update,
+        };
+
+        // Existing rows are referenced by the table's name, rows proposed for
+        // insertion by `excluded`.
+        let target = DFSchema::try_from_qualified_schema(table_name.table(), &source_schema)?;
+        let excluded = DFSchema::try_from_qualified_schema("excluded", &source_schema)?;
+        let schema = target.join(&excluded)?;
+
+        // Rewrite column references to the aliases used by the merge.
+        let qualify = |expr: Expr| {
+            expr.transform_up(&|expr| {
+                Ok(match expr {
+                    Expr::Column(col) => {
+                        let alias = match &col.relation {
+                            Some(relation) if relation.table() == "excluded" => MERGE_SOURCE_ALIAS,
+                            _ => MERGE_TARGET_ALIAS,
+                        };
+                        Transformed::Yes(Expr::Column(Column::new(Some(alias), col.name)))
+                    }
+                    other => Transformed::No(other),
+                })
+            })
+        };
+
+        let mut assignments = Vec::with_capacity(do_update.assignments.len());
+        for assignment in do_update.assignments {
+            if assignment.id.len() != 1 {
+                return Err(PlanError::UnsupportedSQLStatement(
+                    "ON CONFLICT DO UPDATE with table reference in column name".to_string(),
+                ));
+            }
+            let column = normalize_ident(assignment.id.into_iter().next().unwrap());
+            check_column(&column)?;
+            let expr = planner
+                .sql_to_expr(assignment.value, &schema, &mut PlannerContext::new())
+                .await?;
+            assignments.push((column, qualify(expr)?));
+        }
+
+        let predicate = match do_update.selection {
+            Some(selection) => {
+                let expr = planner
+                    .sql_to_expr(selection, &schema, &mut PlannerContext::new())
+                    .await?;
+                Some(qualify(expr)?)
+            }
+            None => None,
+        };
+
+        Ok(OnConflict {
+            columns,
+            action: OnConflictAction::DoUpdate {
+                assignments,
+                predicate,
+            },
+        })
+    }
+
+    /// Get the table to evolve the schema of when inserting into `table_name`.
+    ///
+    /// Returns `None` unless schema evolution is enabled and the table is a
+    /// native table.
+    fn schema_evolution_target(&self, table_name: &OwnedTableReference) -> Option<TableEntry> {
+        if !self.ctx.get_session_vars().enable_schema_evolution() {
+            return None;
+        }
+
+        let ent = EntryResolver::from_context(self.ctx)
+            .resolve_entry_from_reference(table_name.clone())
+            .ok()?
+            .try_into_table_entry()
+            .ok()?;
+        if ent.meta.external || ent.meta.is_temp {
+            return None;
+        }
+        Some(ent)
+    }
+
+    /// Get the alterations needed for the columns of `ent` to match the
+    /// evolved `schema`.
+    ///
+    /// Returns `None` if the schema hasn't changed.
+    fn plan_schema_evolution(
+        &self,
+        ent: &TableEntry,
+        schema: &Schema,
+    ) -> Result<Option<SchemaEvolution>> {
+        let columns = ent.get_internal_columns().unwrap_or_default();
+
+        let mut operations = Vec::new();
+        for (idx, field) in schema.fields().iter().enumerate() {
+            match columns.get(idx) {
+                Some(col)
+                    if InternalColumnDefinition::is_widening(
+                        &col.arrow_type,
+                        field.data_type(),
+                    ) =>
+                {
+                    operations.push(AlterTableOperation::WidenColumn {
+                        name: col.name.clone(),
+                        arrow_type: field.data_type().clone(),
+                    })
+                }
+                Some(_) => (),
+                None => operations.push(AlterTableOperation::AddColumn {
+                    name: field.name().clone(),
+                    arrow_type: field.data_type().clone(),
+                }),
+            }
+        }
+
+        if operations.is_empty() {
+            return Ok(None);
+        }
+
+        let schema = self
+            .ctx
+            .get_session_catalog()
+            .get_by_oid(ent.meta.parent)
+            .ok_or_else(|| internal!("missing schema for table: {}", ent.meta.name))?
+            .get_meta()
+            .name
+            .clone();
+
+        Ok(Some(SchemaEvolution {
+            schema,
+            name: ent.meta.name.clone(),
+            operations,
+        }))
+    }
+
+    /// Plan an INSERT OVERWRITE statement.
+    ///
+    /// Omitting the predicate replaces the entire contents of the table.
+    async fn plan_insert_overwrite(&self, stmt: InsertOverwriteStmt) -> Result<LogicalPlan> {
+        validate_object_name(&stmt.table)?;
+        let table_name = object_name_to_table_ref(stmt.table)?;
+
+        let resolver = EntryResolver::from_context(self.ctx);
+        let ent = resolver
+            .resolve_entry_from_reference(table_name.clone())?
+            .try_into_table_entry()?;
+        if ent.meta.external {
+            return Err(PlanError::UnsupportedFeature(
+                "INSERT OVERWRITE with external tables",
+            ));
+        }
+        if ent.meta.is_temp {
+            return Err(PlanError::UnsupportedFeature(
+                "INSERT OVERWRITE with temporary tables",
+            ));
+        }
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+
+        let replace_where = match stmt.replace_where {
+            Some(predicate) => {
+                let schema = context_provider
+                    .get_table_source(table_name.clone())
+                    .await?
+                    .schema()
+                    .to_dfschema()?;
+                let mut planner = SqlQueryPlanner::new(&mut context_provider);
+                Some(
+                    planner
+                        .sql_to_expr(predicate, &schema, &mut PlannerContext::new())
+                        .await?,
+                )
+            }
+            None => None,
+        };
+
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        let source = planner
+            .insert_to_source_plan(&table_name, &Vec::new(), InsertSource::Query(stmt.source))
+            .await?;
+
+        Ok(InsertOverwrite {
+            table: ent,
+            source,
+            replace_where,
+        }
+        .into_logical_plan())
+    }
+
+    /// Plan a generated query against the system catalog.
+    async fn plan_catalog_query(&self, query: String) -> Result<LogicalPlan> {
+        let query = match parser::parse_sql(&query)?.pop_front() {
+            Some(StatementWithExtensions::Statement(ast::Statement::Query(q))) => q,
+            _ => unreachable!(),
+        };
+
+        let state = self.ctx.df_ctx().state();
+        let mut context_provider = PartialContextProvider::new(self.ctx, &state)?;
+        let mut planner = SqlQueryPlanner::new(&mut context_provider);
+        Ok(LogicalPlan::Datafusion(
+            planner.query_to_plan(*query).await?,
+        ))
+    }
+
+    /// Plan a COPY FROM statement.
+    ///
+    /// This is planned as an insert into the table from the table function
+    /// that reads the source format, e.g. `read_csv` for CSV files.
+    async fn plan_copy_from(&self, stmt: CopyFromStmt) -> Result<LogicalPlan> {
+        let source = normalize_ident(stmt.source);
+
+        let func = match stmt.format.map(normalize_ident) {
+            Some(format) => match format.as_str() {
+                "csv" => OwnedTableReference::bare("read_csv"),
+                "parquet" => OwnedTableReference::bare("read_parquet"),
+                "json" => OwnedTableReference::bare("read_json"),
+                "ndjson" | "jsonl" => OwnedTableReference::bare("read_ndjson"),
+                "bson" => OwnedTableReference::bare("read_bson"),
+                other => {
+                    return Err(PlanError::String(format!(
+                        "Unsupported format for COPY FROM: {other}"
+                    )))
+                }
+            },
+            None => infer_func_for_file(&source)?,
+        };
+
+        let mut args = vec![FuncParamValue::Scalar(ScalarValue::Utf8(Some(source)))];
+        if let Some(creds) = stmt.credentials.map(normalize_ident) {
+            args.push(FuncParamValue::Ident(creds));
+        }
+        let mut opts = HashMap::with_capacity(stmt.options.m.len());
+        for (key, val) in stmt.options.m {
+            let val = match val {
+                OptionValue::QuotedLiteral(s) | OptionValue::UnquotedLiteral(s) => {
+                    ScalarValue::Utf8(Some(s))
+                }
+                OptionValue::Boolean(b) => ScalarValue::Boolean(Some(b)),
+                OptionValue::Number(n) => match n.parse::<i64>() {
+                    Ok(n) => ScalarValue::Int64(Some(n)),
+                    Err(_) => ScalarValue::Float64(Some(n.parse::<f64>().map_err(|_| {
+                        PlanError::String(format!("Invalid number for option '{key}': {n}"))
+                    })?)),
+                },
+                OptionValue::Secret(_) => {
+                    return Err(PlanError::UnsupportedFeature(
+                        "secrets in COPY FROM options",
+                    ))
+                }
+            };
+            opts.insert(key, FuncParamValue::Scalar(val));
+        }
+
+        let source = InsertSource::TableFunction { func, args, opts };
+        self.plan_insert(stmt.table, Vec::new(), source, None, None, true)
+            .await
+    }
+
     async fn plan_copy_to(&self, stmt: CopyToStmt) -> Result<LogicalPlan> {
         let query = match stmt.source {
             CopyToSource::Table(table) => {
@@ -1906,7 +2769,7 @@ impl<'a> SessionPlanner<'a> {
             Ok(bucket)
         }
 
-        let dest = match destination {
+        let mut dest = match destination {
             CopyToDestinationOptions::LOCAL => {
                 CopyToDestinationOptions::Local(CopyToDestinationOptionsLocal { location })
             }
@@ -1994,12 +2857,29 @@ impl<'a> SessionPlanner<'a> {
             }
         };
 
-        let loc = dest.location();
-        let loc = Path::new(loc);
-        let ext = loc
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .map(|ext| ext.to_lowercase());
+        let loc = Path::new(dest.location());
+        let ext_of = |path: &Path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| ext.to_lowercase())
+        };
+        let mut ext = ext_of(loc);
+
+        // A compression extension (e.g. "data.csv.gz") implies the codec, with
+        // the format coming from the extension before it.
+        let ext_compression = ext.as_deref().and_then(CopyToCompression::from_extension);
+        if ext_compression.is_some() {
+            ext = loc.file_stem().and_then(|stem| ext_of(Path::new(stem)));
+        }
+
+        let compression = match m.remove_optional::<String>("compression")? {
+            Some(compression) => compression.parse::<CopyToCompression>().map_err(|e| {
+                PlanError::InvalidCopyToStatement {
+                    source: Box::new(e),
+                }
+            })?,
+            None => ext_compression.unwrap_or_default(),
+        };
 
         let format = match stmt
             .format
@@ -2010,14 +2890,63 @@ impl<'a> SessionPlanner<'a> {
         {
             None => {
                 // TODO: Choose the default based on destination.
-                CopyToFormatOptions::default()
+                CopyToFormatOptions::Csv(CopyToFormatOptionsCsv {
+                    compression,
+                    ..Default::default()
+                })
             }
             Some(CopyToFormatOptions::CSV) => {
-                let delim = m.remove_optional::<char>("delimeter")?.unwrap_or(',');
-                let header = m.remove_optional::<bool>("header")?.unwrap_or(true);
+                let defaults = CopyToFormatOptionsCsv::default();
+
+                let single_byte = |name: &str, c: char| {
+                    u8::try_from(c).ok().filter(u8::is_ascii).ok_or_else(|| {
+                        PlanError::InvalidCopyToStatement {
+                            source: format!("{name} must be a single byte character, got: {c}")
+                                .into(),
+                        }
+                    })
+                };
+
+                // "delimeter" is still accepted for backwards compatibility.
+                let delim = match m.remove_optional::<char>("delimiter")? {
+                    Some(delim) => Some(delim),
+                    None => m.remove_optional::<char>("delimeter")?,
+                };
+                let delim = match delim {
+                    Some(delim) => single_byte("delimiter", delim)?,
+                    None => defaults.delim,
+                };
+                let quote = match m.remove_optional::<char>("quote")? {
+                    Some(quote) => single_byte("quote", quote)?,
+                    None => defaults.quote,
+                };
+                let crlf = match m.remove_optional::<String>("line_terminator")? {
+                    None => defaults.crlf,
+                    Some(term) => match term.to_lowercase().as_str() {
+                        "lf" | "\n" | "\\n" => false,
+                        "crlf" | "\r\n" | "\\r\\n" => true,
+                        _ => {
+                            return Err(PlanError::InvalidCopyToStatement {
+                                source: format!(
+                                    "line_terminator must be one of 'lf' or 'crlf', got: {term}"
+                                )
+                                .into(),
+                            })
+                        }
+                    },
+                };
+
                 CopyToFormatOptions::Csv(CopyToFormatOptionsCsv {
-                    delim: delim as u8,
-                    header,
+                    delim,
+                    header: m
+                        .remove_optional::<bool>("header")?
+                        .unwrap_or(defaults.header),
+                    quote,
+                    null: m
+                        .remove_optional::<String>("null")?
+                        .unwrap_or(defaults.null),
+                    crlf,
+                    compression,
                 })
             }
             Some(CopyToFormatOptions::PARQUET) => {
@@ -2028,7 +2957,7 @@ impl<'a> SessionPlanner<'a> {
             }
             Some(CopyToFormatOptions::JSON) => {
                 let array = m.remove_optional::<bool>("array")?.unwrap_or(false);
-                CopyToFormatOptions::Json(CopyToFormatOptionsJson { array })
+                CopyToFormatOptions::Json(CopyToFormatOptionsJson { array, compression })
             }
             Some(CopyToFormatOptions::BSON) => {
                 CopyToFormatOptions::Bson(CopyToFormatOptionsBson {})
@@ -2047,6 +2976,27 @@ impl<'a> SessionPlanner<'a> {
             Some(other) => return Err(internal!("unsupported output format: {other}")),
         };
 
+        match &format {
+            CopyToFormatOptions::Csv(_) | CopyToFormatOptions::Json(_) => {
+                // Make sure the file name reflects the codec.
+                if let Some(ext) = compression.extension() {
+                    if ext_compression != Some(compression) {
+                        dest.location_mut().push_str(&format!(".{ext}"));
+                    }
+                }
+            }
+            _ if compression != CopyToCompression::None => {
+                return Err(PlanError::InvalidCopyToStatement {
+                    source: format!(
+                        "compression is not supported for format: {}",
+                        format.as_str()
+                    )
+                    .into(),
+                })
+            }
+            _ => (),
+        }
+
         validate_copyto_dest_format_support(dest.as_str(), format.as_str()).map_err(|e| {
             PlanError::InvalidExternalTable {
                 source: Box::new(e),
@@ -2216,6 +3166,38 @@ async fn validate_and_get_file_type_and_compression(
     Ok((file_type, compression))
 }
 
+/// Build a plan returning the name, type and nullability of every output
+/// column of `plan`, in the same shape as `DESCRIBE <table>`.
+fn describe_plan_schema(plan: &DfLogicalPlan) -> Result<DfLogicalPlan> {
+    let rows: Vec<_> = plan
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| {
+            vec![
+                lit(field.name().as_str()),
+                lit(fmt_dtype(field.data_type())),
+                lit(field.is_nullable()),
+            ]
+        })
+        .collect();
+
+    if rows.is_empty() {
+        return Ok(DfLogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            schema: DESCRIBE_TABLE_LOGICAL_SCHEMA.clone(),
+        }));
+    }
+
+    Ok(LogicalPlanBuilder::values(rows)?
+        .project(vec![
+            col("column1").alias("column_name"),
+            col("column2").alias("data_type"),
+            col("column3").alias("is_nullable"),
+        ])?
+        .build()?)
+}
+
 /// Resolves an ident (unquoted -> lowercase else case sensitive).
 fn normalize_ident(ident: Ident) -> String {
     let normalizer = IdentNormalizer::new(/* normalize = */ true);
@@ -2520,6 +3502,26 @@ fn make_decimal_type(precision: Option<u64>, scale: Option<u64>) -> Result<DataT
     }
 }
 
+/// Quote a string as a SQL string literal.
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// Convert the filter of a "SHOW ..." statement into a `WHERE` clause. `LIKE`
+/// patterns are matched against `name_column`.
+fn show_filter_predicate(filter: Option<ShowStatementFilter>, name_column: &str) -> String {
+    match filter {
+        Some(ShowStatementFilter::Like(pattern)) => {
+            format!(" WHERE {name_column} LIKE {}", quote_literal(&pattern))
+        }
+        Some(ShowStatementFilter::ILike(pattern)) => {
+            format!(" WHERE {name_column} ILIKE {}", quote_literal(&pattern))
+        }
+        Some(ShowStatementFilter::Where(expr)) => format!(" WHERE {expr}"),
+        None => String::new(),
+    }
+}
+
 /// If the "SHOW ..." statement equivalent to "SHOW TRANSACTION ISOLATION
 /// LEVEL", return the variable for which to show the value.
 fn is_show_transaction_isolation_level(variable: &[String]) -> bool {
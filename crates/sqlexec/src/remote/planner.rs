@@ -25,6 +25,7 @@ use crate::planner::logical_plan::{
     AlterDatabase,
     AlterTable,
     AlterTunnelRotateKeys,
+    CancelQuery,
     CopyTo,
     CreateCredentials,
     CreateExternalDatabase,
@@ -43,13 +44,18 @@ use crate::planner::logical_plan::{
     DropTunnel,
     DropViews,
     Insert,
+    InsertOverwrite,
+    OptimizeTable,
     SetVariable,
     ShowVariable,
+    TruncateTables,
     Update,
+    VacuumTable,
 };
 use crate::planner::physical_plan::alter_database::AlterDatabaseExec;
 use crate::planner::physical_plan::alter_table::AlterTableExec;
 use crate::planner::physical_plan::alter_tunnel_rotate_keys::AlterTunnelRotateKeysExec;
+use crate::planner::physical_plan::cancel_query::CancelQueryExec;
 use crate::planner::physical_plan::client_recv::ClientExchangeRecvExec;
 use crate::planner::physical_plan::client_send::ClientExchangeSendExec;
 use crate::planner::physical_plan::copy_to::CopyToExec;
@@ -71,12 +77,16 @@ use crate::planner::physical_plan::drop_temp_tables::DropTempTablesExec;
 use crate::planner::physical_plan::drop_tunnel::DropTunnelExec;
 use crate::planner::physical_plan::drop_views::DropViewsExec;
 use crate::planner::physical_plan::insert::InsertExec;
+use crate::planner::physical_plan::insert_overwrite::InsertOverwriteExec;
+use crate::planner::physical_plan::optimize_table::OptimizeTableExec;
 use crate::planner::physical_plan::remote_exec::RemoteExecutionExec;
 use crate::planner::physical_plan::remote_scan::ProviderReference;
 use crate::planner::physical_plan::send_recv::SendRecvJoinExec;
 use crate::planner::physical_plan::set_var::SetVarExec;
 use crate::planner::physical_plan::show_var::ShowVarExec;
+use crate::planner::physical_plan::truncate_tables::TruncateTablesExec;
 use crate::planner::physical_plan::update::UpdateExec;
+use crate::planner::physical_plan::vacuum_table::VacuumTableExec;
 
 pub struct DDLExtensionPlanner {
     catalog: SessionCatalog,
@@ -182,6 +192,7 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     or_replace: lp.or_replace,
                     arrow_schema: Arc::new(lp.schema.as_ref().into()),
                     source: physical_inputs.first().cloned(),
+                    partition_by: lp.partition_by.clone(),
                 };
                 RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
             }
@@ -302,9 +313,17 @@ impl ExtensionPlanner for DDLExtensionPlanner {
             }
             ExtensionType::DropSchemas => {
                 let lp = require_downcast_lp::<DropSchemas>(node);
+                // Skip schemas that don't exist so that the reported count
+                // only includes schemas that were actually dropped.
+                let schema_references = lp
+                    .schema_references
+                    .iter()
+                    .filter(|r| !lp.if_exists || self.catalog.resolve_schema(&r.schema).is_some())
+                    .cloned()
+                    .collect();
                 let exec = DropSchemasExec {
                     catalog_version: self.catalog.version(),
-                    schema_references: lp.schema_references.clone(),
+                    schema_references,
                     if_exists: lp.if_exists,
                     cascade: lp.cascade,
                 };
@@ -379,13 +398,27 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                     other => other.clone(),
                 };
                 let exec = Arc::new(InsertExec {
+                    catalog_version: self.catalog.version(),
                     provider,
                     source: Arc::new(WriteOnlyDataSourceMetricsExecAdapter::new(
                         physical_inputs.first().unwrap().clone(),
                     )),
+                    copy_from: lp.copy_from,
+                    schema_evolution: lp.schema_evolution.clone(),
+                    returning: lp.returning,
+                    on_conflict: lp.on_conflict.clone(),
                 });
                 RuntimeGroupExec::new(lp.runtime_preference, exec)
             }
+            ExtensionType::InsertOverwrite => {
+                let lp = require_downcast_lp::<InsertOverwrite>(node);
+                let exec = InsertOverwriteExec {
+                    table: lp.table.clone(),
+                    source: physical_inputs.first().unwrap().clone(),
+                    replace_where: lp.replace_where.clone(),
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
             ExtensionType::Delete => {
                 let lp = require_downcast_lp::<Delete>(node);
                 let exec = DeleteExec {
@@ -394,6 +427,37 @@ impl ExtensionPlanner for DDLExtensionPlanner {
                 };
                 RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
             }
+            ExtensionType::VacuumTable => {
+                let lp = require_downcast_lp::<VacuumTable>(node);
+                let exec = VacuumTableExec {
+                    table: lp.table.clone(),
+                    retain_hours: lp.retain_hours,
+                    dry_run: lp.dry_run,
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
+            ExtensionType::OptimizeTable => {
+                let lp = require_downcast_lp::<OptimizeTable>(node);
+                let exec = OptimizeTableExec {
+                    table: lp.table.clone(),
+                    zorder_by: lp.zorder_by.clone(),
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
+            ExtensionType::TruncateTables => {
+                let lp = require_downcast_lp::<TruncateTables>(node);
+                let exec = TruncateTablesExec {
+                    tables: lp.tables.clone(),
+                };
+                RuntimeGroupExec::new(RuntimePreference::Remote, Arc::new(exec))
+            }
+            ExtensionType::CancelQuery => {
+                let lp = require_downcast_lp::<CancelQuery>(node);
+                let exec = CancelQueryExec {
+                    connection_id: lp.connection_id,
+                };
+                RuntimeGroupExec::new(RuntimePreference::Local, Arc::new(exec))
+            }
         };
 
         Ok(Some(Arc::new(runtime_group_exec)))
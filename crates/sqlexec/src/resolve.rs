@@ -155,7 +155,7 @@ impl<'a> EntryResolver<'a> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
     use std::sync::Arc;
 
     use catalog::session_catalog::ResolveConfig;
@@ -211,6 +211,8 @@ mod tests {
                 tunnel_id: None,
                 access_mode: SourceAccessMode::ReadWrite,
                 columns: None,
+                comment: None,
+                column_comments: BTreeMap::new(),
             }),
             // Tables
             CatalogEntry::Table(TableEntry {
@@ -227,6 +229,8 @@ mod tests {
                 tunnel_id: None,
                 access_mode: SourceAccessMode::ReadWrite,
                 columns: None,
+                comment: None,
+                column_comments: BTreeMap::new(),
             }),
         ];
 
@@ -1,18 +1,31 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
+use std::future::Future;
 use std::path::PathBuf;
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use catalog::mutator::CatalogMutator;
 use catalog::session_catalog::SessionCatalog;
+use datafusion::arrow::array::as_string_array;
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::{DataFusionError, Result as DataFusionResult};
-use datafusion::logical_expr::LogicalPlan as DfLogicalPlan;
+use datafusion::logical_expr::{
+    Explain,
+    LogicalPlan as DfLogicalPlan,
+    PlanType,
+    StringifiedPlan,
+    ToStringifiedPlan,
+};
 use datafusion::physical_plan::empty::EmptyExec;
+use datafusion::physical_plan::explain::ExplainExec;
+use datafusion::physical_plan::memory::MemoryStream;
 use datafusion::physical_plan::{
+    displayable,
     execute_stream,
     ExecutionPlan,
     RecordBatchStream,
@@ -25,26 +38,46 @@ use datafusion_ext::session_metrics::{
     BatchStreamWithMetricSender,
     ExecutionStatus,
     QueryMetrics,
+    ServerMetrics,
     SessionMetricsHandler,
 };
+use datafusion_ext::session_registry::{SessionOwner, SessionRegistry};
 use datafusion_ext::vars::SessionVars;
-use datasources::native::access::NativeTableStorage;
+use datasources::native::access::{NativeTable, NativeTableStorage, NativeTableVersion};
+use datasources::native::errors::NativeError;
+use datasources::native::optimize::OPTIMIZE_SCHEMA;
 use distexec::scheduler::{OutputSink, Scheduler};
 use distexec::stream::create_coalescing_adapter;
-use futures::{Stream, StreamExt};
+use futures::{Stream, StreamExt, TryStreamExt};
 use once_cell::sync::Lazy;
 use parser::StatementWithExtensions;
 use pgrepr::format::Format;
 use pgrepr::notice::{Notice, NoticeSeverity, SqlState};
+use protogen::metastore::types::catalog::TableEntry;
 use sqlbuiltins::functions::BuiltinScalarUDF;
 use telemetry::Tracker;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
 use url::Url;
 use uuid::Uuid;
 
 use crate::context::local::{LocalSessionContext, Portal, PreparedStatement};
 use crate::environment::EnvironmentReader;
 use crate::errors::{ExecError, Result};
-use crate::planner::logical_plan::{LogicalPlan, OperationInfo, TransactionPlan};
+use crate::planner::extension::ExtensionType;
+use crate::planner::logical_plan::{
+    CursorPlan,
+    Delete,
+    Insert,
+    InsertOverwrite,
+    LogicalPlan,
+    OperationInfo,
+    TransactionPlan,
+    TruncateTables,
+    Update,
+};
+use crate::planner::physical_plan::explain::render_physical_tree;
+use crate::planner::physical_plan::remote_scan::ProviderReference;
+use crate::planner::physical_plan::set_var::SET_VARIABLE_PHYSICAL_SCHEMA;
 use crate::planner::physical_plan::{
     get_count_from_batch,
     get_operation_from_batch,
@@ -65,6 +98,14 @@ pub enum ExecutionResult {
         /// Inner results stream from execution.
         stream: SendableRecordBatchStream,
     },
+    /// The rendered output of an `EXPLAIN` statement.
+    Explain {
+        /// Logical and physical plans, one section per plan type.
+        plan: String,
+        /// If the query was executed (`EXPLAIN ANALYZE`), in which case the
+        /// physical plan is annotated with the metrics of each operator.
+        analyze: bool,
+    },
     /// Execution errored.
     Error(DataFusionError),
     /// No batches returned.
@@ -75,6 +116,16 @@ pub enum ExecutionResult {
     Commit,
     /// Transaction rolled back.
     Rollback,
+    /// Savepoint created.
+    Savepoint,
+    /// Savepoint released.
+    ReleaseSavepoint,
+    /// Transaction rolled back to a savepoint.
+    RollbackToSavepoint,
+    /// Cursor declared.
+    DeclareCursor,
+    /// Cursor (or all cursors) closed.
+    CloseCursor,
     /// Data successfully inserted.
     InsertSuccess { rows_inserted: usize },
     /// Data successfully deleted.
@@ -82,7 +133,18 @@ pub enum ExecutionResult {
     /// Data successfully updated.
     UpdateSuccess { updated_rows: usize },
     /// Data successfully copied.
-    CopySuccess,
+    CopySuccess { copied_rows: usize },
+    /// Table contents successfully overwritten.
+    OverwriteSuccess { rows_written: usize },
+    /// Table files compacted.
+    OptimizeSuccess {
+        files_removed: usize,
+        files_added: usize,
+    },
+    /// Tables truncated.
+    Truncate,
+    /// A query running on another connection was canceled.
+    CancelQuery,
     /// Table created.
     CreateTable,
     /// Database created.
@@ -97,20 +159,22 @@ pub enum ExecutionResult {
     CreateSchema,
     /// A view was created.
     CreateView,
-    /// A table was renamed.
+    /// A table was altered.
     AlterTable,
+    /// A comment was set on a table or column.
+    Comment,
     /// A database was renamed.
     AlterDatabase,
     /// A tunnel was altered.
     AlterTunnelRotateKeys,
     /// A client local variable was set.
-    Set,
+    Set { variable: String, value: String },
     /// Tables dropped.
-    DropTables,
+    DropTables { dropped_tables: usize },
     /// Views dropped.
     DropViews,
     /// Schemas dropped.
-    DropSchemas,
+    DropSchemas { dropped_schemas: usize },
     /// Database dropped.
     DropDatabase,
     /// Tunnel is dropped.
@@ -170,7 +234,9 @@ impl ExecutionResult {
         // If we don't match either of these schemas, just assume these results
         // are from a normal SELECT query.
         if !(schema.eq(&GENERIC_OPERATION_PHYSICAL_SCHEMA)
-            || schema.eq(&GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA))
+            || schema.eq(&GENERIC_OPERATION_AND_COUNT_PHYSICAL_SCHEMA)
+            || schema.eq(&OPTIMIZE_SCHEMA)
+            || schema.eq(&SET_VARIABLE_PHYSICAL_SCHEMA))
         {
             return ExecutionResult::Query { stream };
         }
@@ -198,6 +264,31 @@ impl ExecutionResult {
             };
         }
 
+        // Optimize reports the number of files removed and added instead of an
+        // operation name.
+        if schema.eq(&OPTIMIZE_SCHEMA) {
+            let count_at = |idx: usize| match ScalarValue::try_from_array(batch.column(idx), 0) {
+                Ok(ScalarValue::UInt64(Some(val))) => val as usize,
+                _ => 0,
+            };
+            return ExecutionResult::OptimizeSuccess {
+                files_removed: count_at(0),
+                files_added: count_at(1),
+            };
+        }
+
+        // SET reports the variable and value that were set.
+        if schema.eq(&SET_VARIABLE_PHYSICAL_SCHEMA) {
+            let string_at = |idx: usize| match ScalarValue::try_from_array(batch.column(idx), 0) {
+                Ok(ScalarValue::Utf8(Some(val))) => val,
+                _ => String::new(),
+            };
+            return ExecutionResult::Set {
+                variable: string_at(1),
+                value: string_at(2),
+            };
+        }
+
         // Try to get the execution result type from the batch. Default to
         // `Query` if we don't know how to translate it into a result.
         let op = get_operation_from_batch(&batch).unwrap_or_default();
@@ -212,14 +303,24 @@ impl ExecutionResult {
         match self {
             ExecutionResult::Error(_) => "error",
             ExecutionResult::Query { .. } => "query",
+            ExecutionResult::Explain { .. } => "explain",
             ExecutionResult::EmptyQuery => "empty_query",
             ExecutionResult::Begin => "begin",
             ExecutionResult::Commit => "commit",
             ExecutionResult::Rollback => "rollback",
+            ExecutionResult::Savepoint => "savepoint",
+            ExecutionResult::ReleaseSavepoint => "release_savepoint",
+            ExecutionResult::RollbackToSavepoint => "rollback_to_savepoint",
+            ExecutionResult::DeclareCursor => "declare_cursor",
+            ExecutionResult::CloseCursor => "close_cursor",
             ExecutionResult::InsertSuccess { .. } => "insert",
             ExecutionResult::DeleteSuccess { .. } => "delete",
             ExecutionResult::UpdateSuccess { .. } => "update",
-            ExecutionResult::CopySuccess => "copy",
+            ExecutionResult::CopySuccess { .. } => "copy",
+            ExecutionResult::OverwriteSuccess { .. } => "overwrite",
+            ExecutionResult::OptimizeSuccess { .. } => "optimize",
+            ExecutionResult::Truncate => "truncate",
+            ExecutionResult::CancelQuery => "cancel_query",
             ExecutionResult::CreateTable => "create_table",
             ExecutionResult::CreateDatabase => "create_database",
             ExecutionResult::CreateTunnel => "create_tunnel",
@@ -228,12 +329,13 @@ impl ExecutionResult {
             ExecutionResult::CreateSchema => "create_schema",
             ExecutionResult::CreateView => "create_view",
             ExecutionResult::AlterTable => "alter_table",
+            ExecutionResult::Comment => "comment",
             ExecutionResult::AlterDatabase => "alter_database",
             ExecutionResult::AlterTunnelRotateKeys => "alter_tunnel_rotate_keys",
-            ExecutionResult::Set => "set_local",
-            ExecutionResult::DropTables => "drop_tables",
+            ExecutionResult::Set { .. } => "set_local",
+            ExecutionResult::DropTables { .. } => "drop_tables",
             ExecutionResult::DropViews => "drop_views",
-            ExecutionResult::DropSchemas => "drop_schemas",
+            ExecutionResult::DropSchemas { .. } => "drop_schemas",
             ExecutionResult::DropDatabase => "drop_database",
             ExecutionResult::DropTunnel => "drop_tunnel",
             ExecutionResult::DropCredentials => "drop_credentials",
@@ -251,11 +353,12 @@ impl ExecutionResult {
                 | ExecutionResult::CreateSchema
                 | ExecutionResult::CreateView
                 | ExecutionResult::AlterTable
+                | ExecutionResult::Comment
                 | ExecutionResult::AlterDatabase
                 | ExecutionResult::AlterTunnelRotateKeys
-                | ExecutionResult::DropTables
+                | ExecutionResult::DropTables { .. }
                 | ExecutionResult::DropViews
-                | ExecutionResult::DropSchemas
+                | ExecutionResult::DropSchemas { .. }
                 | ExecutionResult::DropDatabase
                 | ExecutionResult::DropTunnel
                 | ExecutionResult::DropCredentials
@@ -271,6 +374,11 @@ impl ExecutionResult {
             "begin" => ExecutionResult::Begin,
             "commit" => ExecutionResult::Commit,
             "rollback" => ExecutionResult::Rollback,
+            "savepoint" => ExecutionResult::Savepoint,
+            "release_savepoint" => ExecutionResult::ReleaseSavepoint,
+            "rollback_to_savepoint" => ExecutionResult::RollbackToSavepoint,
+            "declare_cursor" => ExecutionResult::DeclareCursor,
+            "close_cursor" => ExecutionResult::CloseCursor,
             "insert" => ExecutionResult::InsertSuccess {
                 rows_inserted: count.unwrap_or_default() as usize,
             },
@@ -280,7 +388,14 @@ impl ExecutionResult {
             "update" => ExecutionResult::UpdateSuccess {
                 updated_rows: count.unwrap_or_default() as usize,
             },
-            "copy" => ExecutionResult::CopySuccess,
+            "copy" => ExecutionResult::CopySuccess {
+                copied_rows: count.unwrap_or_default() as usize,
+            },
+            "overwrite" => ExecutionResult::OverwriteSuccess {
+                rows_written: count.unwrap_or_default() as usize,
+            },
+            "truncate" => ExecutionResult::Truncate,
+            "cancel_query" => ExecutionResult::CancelQuery,
             "create_table" => ExecutionResult::CreateTable,
             "create_database" => ExecutionResult::CreateDatabase,
             "create_tunnel" => ExecutionResult::CreateTunnel,
@@ -289,12 +404,16 @@ impl ExecutionResult {
             "create_schema" => ExecutionResult::CreateSchema,
             "create_view" => ExecutionResult::CreateView,
             "alter_table" => ExecutionResult::AlterTable,
+            "comment" => ExecutionResult::Comment,
             "alter_database" => ExecutionResult::AlterDatabase,
             "alter_tunnel_rotate_keys" => ExecutionResult::AlterTunnelRotateKeys,
-            "set" => ExecutionResult::Set,
-            "drop_tables" => ExecutionResult::DropTables,
+            "drop_tables" => ExecutionResult::DropTables {
+                dropped_tables: count.unwrap_or_default() as usize,
+            },
             "drop_views" => ExecutionResult::DropViews,
-            "drop_schemas" => ExecutionResult::DropSchemas,
+            "drop_schemas" => ExecutionResult::DropSchemas {
+                dropped_schemas: count.unwrap_or_default() as usize,
+            },
             "drop_database" => ExecutionResult::DropDatabase,
             "drop_tunnel" => ExecutionResult::DropTunnel,
             "drop_credentials" => ExecutionResult::DropCredentials,
@@ -312,10 +431,16 @@ impl fmt::Display for ExecutionResult {
             ExecutionResult::Query { .. } => {
                 write!(f, "Query")
             }
+            ExecutionResult::Explain { plan, .. } => write!(f, "{plan}"),
             ExecutionResult::EmptyQuery => write!(f, "No results"),
             ExecutionResult::Begin => write!(f, "Begin"),
             ExecutionResult::Commit => write!(f, "Commit"),
             ExecutionResult::Rollback => write!(f, "Rollback"),
+            ExecutionResult::Savepoint => write!(f, "Savepoint"),
+            ExecutionResult::ReleaseSavepoint => write!(f, "Release"),
+            ExecutionResult::RollbackToSavepoint => write!(f, "Rollback"),
+            ExecutionResult::DeclareCursor => write!(f, "Declare cursor"),
+            ExecutionResult::CloseCursor => write!(f, "Close cursor"),
             ExecutionResult::InsertSuccess { rows_inserted, .. } => {
                 if *rows_inserted == 1 {
                     write!(f, "Inserted 1 row")
@@ -337,7 +462,29 @@ impl fmt::Display for ExecutionResult {
                     write!(f, "Updated {} rows", updated_rows)
                 }
             }
-            ExecutionResult::CopySuccess => write!(f, "Copy success"),
+            ExecutionResult::CopySuccess { copied_rows } => {
+                if *copied_rows == 1 {
+                    write!(f, "Copied 1 row")
+                } else {
+                    write!(f, "Copied {} rows", copied_rows)
+                }
+            }
+            ExecutionResult::OverwriteSuccess { rows_written } => {
+                if *rows_written == 1 {
+                    write!(f, "Overwrote table with 1 row")
+                } else {
+                    write!(f, "Overwrote table with {} rows", rows_written)
+                }
+            }
+            ExecutionResult::OptimizeSuccess {
+                files_removed,
+                files_added,
+            } => write!(
+                f,
+                "Optimized table, removed {files_removed} file(s) and added {files_added} file(s)"
+            ),
+            ExecutionResult::Truncate => write!(f, "Table(s) truncated"),
+            ExecutionResult::CancelQuery => write!(f, "Query canceled"),
             ExecutionResult::CreateTable => write!(f, "Table created"),
             ExecutionResult::CreateDatabase => write!(f, "Database created"),
             ExecutionResult::CreateTunnel => write!(f, "Tunnel created"),
@@ -346,12 +493,19 @@ impl fmt::Display for ExecutionResult {
             ExecutionResult::CreateSchema => write!(f, "Schema create"),
             ExecutionResult::CreateView => write!(f, "View created"),
             ExecutionResult::AlterTable => write!(f, "Table altered"),
+            ExecutionResult::Comment => write!(f, "Comment set"),
             ExecutionResult::AlterDatabase => write!(f, "Database altered"),
             ExecutionResult::AlterTunnelRotateKeys => write!(f, "Keys rotated"),
-            ExecutionResult::Set => write!(f, "Local variable set"),
-            ExecutionResult::DropTables => write!(f, "Table(s) dropped"),
+            ExecutionResult::Set { variable, value } => {
+                write!(f, "Local variable set: {variable} = {value}")
+            }
+            ExecutionResult::DropTables { dropped_tables } => {
+                write!(f, "{dropped_tables} table(s) dropped")
+            }
             ExecutionResult::DropViews => write!(f, "View(s) dropped"),
-            ExecutionResult::DropSchemas => write!(f, "Schema(s) dropped"),
+            ExecutionResult::DropSchemas { dropped_schemas } => {
+                write!(f, "{dropped_schemas} schema(s) dropped")
+            }
             ExecutionResult::DropDatabase => write!(f, "Database(s) dropped"),
             ExecutionResult::DropTunnel => write!(f, "Tunnel(s) dropped"),
             ExecutionResult::DropCredentials => write!(f, "Credentials dropped"),
@@ -359,6 +513,89 @@ impl fmt::Display for ExecutionResult {
     }
 }
 
+impl fmt::Debug for ExecutionResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("ExecutionResult");
+        s.field("type", &self.result_type_str());
+        match self {
+            ExecutionResult::Error(e) => s.field("error", e),
+            ExecutionResult::InsertSuccess { rows_inserted } => s.field("count", rows_inserted),
+            ExecutionResult::DeleteSuccess { deleted_rows } => s.field("count", deleted_rows),
+            ExecutionResult::UpdateSuccess { updated_rows } => s.field("count", updated_rows),
+            ExecutionResult::CopySuccess { copied_rows } => s.field("count", copied_rows),
+            ExecutionResult::OverwriteSuccess { rows_written } => s.field("count", rows_written),
+            ExecutionResult::DropTables { dropped_tables } => s.field("count", dropped_tables),
+            ExecutionResult::DropSchemas { dropped_schemas } => s.field("count", dropped_schemas),
+            ExecutionResult::Set { variable, value } => {
+                s.field("variable", variable).field("value", value)
+            }
+            ExecutionResult::OptimizeSuccess {
+                files_removed,
+                files_added,
+            } => s
+                .field("files_removed", files_removed)
+                .field("files_added", files_added),
+            _ => &mut s,
+        };
+        s.finish()
+    }
+}
+
+/// Get the name of the operation if executing the plan would modify data or
+/// the catalog.
+fn mutating_operation(plan: &DfLogicalPlan) -> Option<String> {
+    // EXPLAIN ANALYZE executes its input, and an insert with a RETURNING
+    // clause sits beneath a projection.
+    let plan = match plan {
+        DfLogicalPlan::Analyze(analyze) => analyze.input.as_ref(),
+        plan => plan,
+    };
+    let plan = match plan {
+        DfLogicalPlan::Projection(projection) => projection.input.as_ref(),
+        plan => plan,
+    };
+    match plan {
+        DfLogicalPlan::Extension(ext) => match ExtensionType::from_str(ext.node.name()) {
+            Ok(ext_type) if !ext_type.is_read_only() => Some(ext.node.name().to_string()),
+            _ => None,
+        },
+        DfLogicalPlan::Dml(dml) => Some(dml.op.to_string()),
+        DfLogicalPlan::Ddl(_) => Some("DDL".to_string()),
+        DfLogicalPlan::Copy(_) => Some("COPY".to_string()),
+        _ => None,
+    }
+}
+
+/// Render the output stream of an `EXPLAIN` plan into text.
+///
+/// The stream is expected to have the `(plan_type, plan)` columns produced by
+/// datafusion. Each plan is written under a heading for its plan type.
+async fn render_explain(stream: SendableRecordBatchStream) -> DataFusionResult<String> {
+    let batches: Vec<RecordBatch> = stream.try_collect().await?;
+
+    let mut rendered = String::new();
+    for batch in batches {
+        let plan_types = as_string_array(batch.column(0));
+        let plans = as_string_array(batch.column(1));
+
+        for (plan_type, plan) in plan_types.iter().zip(plans.iter()) {
+            if !rendered.is_empty() {
+                rendered.push('\n');
+            }
+            rendered.push_str(plan_type.unwrap_or_default());
+            rendered.push_str(":\n");
+            for line in plan.unwrap_or_default().lines() {
+                rendered.push_str("  ");
+                rendered.push_str(line);
+                rendered.push('\n');
+            }
+        }
+    }
+    rendered.truncate(rendered.trim_end().len());
+
+    Ok(rendered)
+}
+
 /// Simple stream adapter to use after we've inspected the first batch in a
 /// stream.
 struct StreamAndFirstResult {
@@ -383,6 +620,312 @@ impl RecordBatchStream for StreamAndFirstResult {
     }
 }
 
+/// Stream adapter that ends the stream with an error once the query has been
+/// canceled.
+///
+/// Dropping the inner stream aborts any work DataFusion is doing for it.
+struct CancellableStream {
+    stream: Option<SendableRecordBatchStream>,
+    schema: Arc<Schema>,
+    canceled: Pin<Box<WaitForCancellationFutureOwned>>,
+}
+
+impl CancellableStream {
+    fn new(stream: SendableRecordBatchStream, cancel: CancellationToken) -> Self {
+        CancellableStream {
+            schema: stream.schema(),
+            stream: Some(stream),
+            canceled: Box::pin(cancel.cancelled_owned()),
+        }
+    }
+}
+
+impl Stream for CancellableStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.stream.is_none() {
+            return Poll::Ready(None);
+        }
+
+        if self.canceled.as_mut().poll(cx).is_ready() {
+            self.stream = None;
+            return Poll::Ready(Some(Err(DataFusionError::External(Box::new(
+                ExecError::QueryCanceled,
+            )))));
+        }
+
+        self.stream.as_mut().unwrap().poll_next_unpin(cx)
+    }
+}
+
+impl RecordBatchStream for CancellableStream {
+    fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+}
+
+/// A stream that ends with a `StatementTimeout` error once the deadline has
+/// passed.
+struct TimeoutStream {
+    stream: Option<SendableRecordBatchStream>,
+    schema: Arc<Schema>,
+    deadline: Pin<Box<tokio::time::Sleep>>,
+}
+
+impl TimeoutStream {
+    fn new(stream: SendableRecordBatchStream, deadline: tokio::time::Instant) -> Self {
+        TimeoutStream {
+            schema: stream.schema(),
+            stream: Some(stream),
+            deadline: Box::pin(tokio::time::sleep_until(deadline)),
+        }
+    }
+}
+
+impl Stream for TimeoutStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.stream.is_none() {
+            return Poll::Ready(None);
+        }
+
+        if self.deadline.as_mut().poll(cx).is_ready() {
+            // Dropping the stream aborts execution.
+            self.stream = None;
+            return Poll::Ready(Some(Err(DataFusionError::External(Box::new(
+                ExecError::StatementTimeout,
+            )))));
+        }
+
+        self.stream.as_mut().unwrap().poll_next_unpin(cx)
+    }
+}
+
+impl RecordBatchStream for TimeoutStream {
+    fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+}
+
+/// The transaction state of a session, as reported to pg clients in
+/// `ReadyForQuery`.
+///
+/// Outside of an explicit `BEGIN`, every batch of statements runs as an
+/// implicit transaction, matching Postgres: the first failing statement ends
+/// the batch and the statements after it are never executed. Inside of an
+/// explicit transaction block, a failing statement moves the session to
+/// `Failed`, and every statement other than `COMMIT` or `ROLLBACK` is rejected
+/// until the block is ended. A `COMMIT` of a failed block is reported as a
+/// `ROLLBACK`.
+///
+/// Savepoints may be created within a transaction block. `ROLLBACK TO
+/// SAVEPOINT` destroys the savepoints created after the named one and returns
+/// a failed block to `InBlock`.
+///
+/// Note that the native storage layer is not transactional. Rolling back
+/// (including failing a batch, and committing a failed block) undoes inserts,
+/// updates and deletes of native tables by undoing the transaction's commits
+/// to the tables (see `WriteLog`). Other changes, such as DDL, can't be
+/// undone, and neither can writes to tables that other connections have
+/// since written to. If a transaction made any, none of its changes are
+/// rolled back and a warning is sent. `ROLLBACK TO SAVEPOINT` instead errors
+/// if such changes were made since the savepoint.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionState {
+    /// Not in an explicit transaction block.
+    #[default]
+    Idle,
+    /// In an explicit transaction block.
+    InBlock,
+    /// In an explicit transaction block that had a statement fail.
+    Failed,
+}
+
+/// A savepoint in the current transaction block.
+struct Savepoint {
+    name: String,
+    /// Position of the write log when the savepoint was created.
+    writes: WritePosition,
+}
+
+/// Changes made by the statements of the current transaction, used to undo
+/// them when rolling back.
+///
+/// Writes to native tables are undone by committing new versions of the
+/// tables with the files from before the writes. This is only done if every
+/// commit since was made by this session's connection, so that changes made
+/// by other connections are never undone. Other changes (e.g. to the
+/// catalog) can't be undone, so they're only counted.
+#[derive(Debug, Default)]
+struct WriteLog {
+    /// Versions of native tables from before each write, oldest first.
+    versions: Vec<NativeTableVersion>,
+    /// Number of statements that made changes that can't be undone.
+    irreversible: usize,
+}
+
+/// Why changes that aren't to native tables can't be rolled back.
+const NON_NATIVE_CHANGES: &str = "only inserts, updates and deletes of native tables can be undone";
+
+/// Why changes to tables that other connections changed since can't be
+/// rolled back.
+const CONCURRENT_CHANGES: &str =
+    "another connection changed a table written in this transaction since";
+
+/// A position in the write log.
+#[derive(Debug, Default, Clone, Copy)]
+struct WritePosition {
+    versions: usize,
+    irreversible: usize,
+}
+
+impl WriteLog {
+    fn position(&self) -> WritePosition {
+        WritePosition {
+            versions: self.versions.len(),
+            irreversible: self.irreversible,
+        }
+    }
+
+    /// Check if every change recorded after `position` can be undone.
+    fn can_undo_since(&self, position: WritePosition) -> bool {
+        self.irreversible == position.irreversible
+    }
+
+    /// Undo the writes recorded after `position` by the connection
+    /// `connection_id`, removing them from the log.
+    ///
+    /// Errors without undoing anything if another connection committed to
+    /// one of the written tables since (see `NativeTableVersion::check_undo`).
+    async fn undo_since(
+        &mut self,
+        position: WritePosition,
+        connection_id: Uuid,
+        target: &str,
+    ) -> Result<()> {
+        // Undo each table's commits since the oldest version recorded for
+        // it, checking all tables before changing any of them.
+        let mut seen = HashSet::new();
+        let oldest: Vec<_> = self.versions[position.versions..]
+            .iter()
+            .filter(|version| seen.insert(version.location()))
+            .cloned()
+            .collect();
+        for version in &oldest {
+            match version.check_undo(connection_id).await {
+                Ok(_) => (),
+                Err(NativeError::CommittedByOtherConnection { .. }) => {
+                    return Err(ExecError::IrreversibleChanges(
+                        target.to_string(),
+                        CONCURRENT_CHANGES,
+                    ))
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        self.versions.truncate(position.versions);
+        self.irreversible = position.irreversible;
+        for version in oldest {
+            version.undo(connection_id).await?;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.versions.clear();
+        self.irreversible = 0;
+    }
+}
+
+/// What a mutating plan is about to change, see `WriteLog`.
+enum WriteTarget {
+    /// A native table, as loaded when planning.
+    Version(NativeTableVersion),
+    /// Native tables, which need loading to get their current versions.
+    Tables(Vec<TableEntry>),
+    /// Changes that can't be undone.
+    Irreversible,
+    /// Changes that don't need undoing, e.g. compacting a table's files.
+    Unchanged,
+}
+
+impl WriteTarget {
+    /// Get what `plan` changes. Expects `plan` to be mutating (see
+    /// `mutating_operation`).
+    fn from_plan(plan: &DfLogicalPlan) -> WriteTarget {
+        let plan = match plan {
+            DfLogicalPlan::Analyze(analyze) => analyze.input.as_ref(),
+            plan => plan,
+        };
+        let plan = match plan {
+            DfLogicalPlan::Projection(projection) => projection.input.as_ref(),
+            plan => plan,
+        };
+        let ext = match plan {
+            DfLogicalPlan::Extension(ext) => ext,
+            _ => return WriteTarget::Irreversible,
+        };
+
+        let node = ext.node.as_any();
+        if let Some(insert) = node.downcast_ref::<Insert>() {
+            // Evolving the schema alters the table in the catalog.
+            if insert.schema_evolution.is_some() {
+                return WriteTarget::Irreversible;
+            }
+            match &insert.provider {
+                ProviderReference::Provider(provider) => {
+                    match provider.as_any().downcast_ref::<NativeTable>() {
+                        Some(table) => WriteTarget::Version(table.current_version()),
+                        None => WriteTarget::Irreversible,
+                    }
+                }
+                ProviderReference::RemoteReference(_) => WriteTarget::Irreversible,
+            }
+        } else if let Some(delete) = node.downcast_ref::<Delete>() {
+            Self::tables([&delete.table])
+        } else if let Some(update) = node.downcast_ref::<Update>() {
+            Self::tables([&update.table])
+        } else if let Some(overwrite) = node.downcast_ref::<InsertOverwrite>() {
+            Self::tables([&overwrite.table])
+        } else if let Some(truncate) = node.downcast_ref::<TruncateTables>() {
+            Self::tables(&truncate.tables)
+        } else {
+            match ExtensionType::from_str(ext.node.name()) {
+                Ok(ExtensionType::VacuumTable | ExtensionType::OptimizeTable) => {
+                    WriteTarget::Unchanged
+                }
+                _ => WriteTarget::Irreversible,
+            }
+        }
+    }
+
+    /// Writes to `tables`, which can only be undone for native tables.
+    fn tables<'a>(tables: impl IntoIterator<Item = &'a TableEntry>) -> WriteTarget {
+        let tables: Vec<_> = tables.into_iter().cloned().collect();
+        if tables
+            .iter()
+            .all(|table| !table.meta.external && !table.meta.is_temp)
+        {
+            WriteTarget::Tables(tables)
+        } else {
+            WriteTarget::Irreversible
+        }
+    }
+}
+
+/// A cursor declared with `DECLARE ... CURSOR FOR <query>`.
+///
+/// Rows are only pulled from the query's stream as they're fetched. Closing
+/// the cursor drops the stream.
+struct Cursor {
+    stream: SendableRecordBatchStream,
+    /// Rows pulled from the stream but not yet fetched.
+    buffered: Option<RecordBatch>,
+}
+
 /// A per-client user session.
 ///
 /// This is a thin wrapper around a session context. Having a layer between
@@ -390,6 +933,16 @@ impl RecordBatchStream for StreamAndFirstResult {
 /// in the future (e.g. consensus).
 pub struct Session {
     pub(crate) ctx: LocalSessionContext,
+    transaction: TransactionState,
+    /// Savepoints in the current transaction block, oldest first.
+    savepoints: Vec<Savepoint>,
+    /// Changes made in the current transaction block or implicit
+    /// transaction.
+    writes: WriteLog,
+    /// If a batch of statements is running as an implicit transaction.
+    implicit: bool,
+    /// Cursors open in the current transaction block, keyed by name.
+    cursors: HashMap<String, Cursor>,
 }
 
 impl Session {
@@ -404,15 +957,25 @@ impl Session {
         catalog_mutator: CatalogMutator,
         native_tables: NativeTableStorage,
         tracker: Arc<Tracker>,
+        server_metrics: Arc<ServerMetrics>,
+        session_registry: Arc<SessionRegistry>,
         spill_path: Option<PathBuf>,
         task_scheduler: Scheduler,
     ) -> Result<Session> {
+        let session_handle = session_registry.register(
+            vars.connection_id(),
+            SessionOwner::from_vars(&vars),
+            vars.user_name(),
+            vars.database_name(),
+        );
         let metrics_handler = SessionMetricsHandler::new(
             vars.user_id(),
             vars.database_id(),
             vars.connection_id(),
             tracker,
-        );
+            server_metrics,
+        )
+        .with_session_handle(session_handle);
 
         let ctx = LocalSessionContext::new(
             vars,
@@ -420,11 +983,19 @@ impl Session {
             catalog_mutator,
             native_tables,
             metrics_handler,
+            session_registry,
             spill_path,
             task_scheduler,
         )?;
 
-        Ok(Session { ctx })
+        Ok(Session {
+            ctx,
+            transaction: TransactionState::default(),
+            savepoints: Vec::new(),
+            writes: WriteLog::default(),
+            implicit: false,
+            cursors: HashMap::new(),
+        })
     }
 
     pub async fn register_function(&mut self, udf: Arc<dyn BuiltinScalarUDF>) -> Result<()> {
@@ -481,8 +1052,20 @@ impl Session {
         plan: DfLogicalPlan,
         op: &OperationInfo,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        let state = self.ctx.df_ctx().state();
-        let plan = state.optimize(&plan)?;
+        let state = self.ctx.df_state();
+        match state.optimize(&plan)? {
+            DfLogicalPlan::Explain(explain) => self.create_explain_plan(explain, op).await,
+            plan => self.create_optimized_physical_plan(&plan, op).await,
+        }
+    }
+
+    /// Create a physical plan from an already optimized logical plan.
+    async fn create_optimized_physical_plan(
+        &self,
+        plan: &DfLogicalPlan,
+        op: &OperationInfo,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let state = self.ctx.df_state();
         if let Some(client) = self.ctx.exec_client() {
             let planner = RemotePhysicalPlanner {
                 database_id: self.ctx.get_database_id(),
@@ -490,19 +1073,120 @@ impl Session {
                 remote_client: client,
                 catalog: self.ctx.get_session_catalog(),
             };
-            let plan = planner.create_physical_plan(&plan, &state).await?;
+            let plan = planner.create_physical_plan(plan, &state).await?;
             Ok(plan)
         } else {
-            // TODO: Possible to not require a catalog clone here?
-            let ddl_planner = DDLExtensionPlanner::new(self.ctx.get_session_catalog().clone());
-            let planner =
-                DefaultPhysicalPlanner::with_extension_planners(vec![Arc::new(ddl_planner)]);
-            let plan = planner.create_physical_plan(&plan, &state).await?;
+            let plan = self
+                .local_physical_planner()
+                .create_physical_plan(plan, &state)
+                .await?;
 
             Ok(plan)
         }
     }
 
+    /// Get the planner for physical plans executed by this session.
+    fn local_physical_planner(&self) -> DefaultPhysicalPlanner {
+        // TODO: Possible to not require a catalog clone here?
+        let ddl_planner = DDLExtensionPlanner::new(self.ctx.get_session_catalog().clone());
+        DefaultPhysicalPlanner::with_extension_planners(vec![Arc::new(ddl_planner)])
+    }
+
+    /// Create the physical plan of an explained statement from its already
+    /// optimized logical plan.
+    ///
+    /// For verbose explains of locally executed plans, this also returns the
+    /// intermediate plans that DataFusion shows: the initial physical plan,
+    /// and the plan after each physical optimizer rule.
+    async fn create_explained_physical_plan(
+        &self,
+        plan: &DfLogicalPlan,
+        verbose: bool,
+        op: &OperationInfo,
+    ) -> Result<(Vec<StringifiedPlan>, Arc<dyn ExecutionPlan>)> {
+        if !verbose || self.ctx.exec_client().is_some() {
+            let plan = self.create_optimized_physical_plan(plan, op).await?;
+            return Ok((Vec::new(), plan));
+        }
+
+        // Plan without any optimizer rules, then apply them one at a time
+        // the same way DataFusion's planner does.
+        let state = self.ctx.df_state();
+        let rules = state.physical_optimizers().to_vec();
+        let unoptimized = state.clone().with_physical_optimizer_rules(Vec::new());
+        let mut physical = self
+            .local_physical_planner()
+            .create_physical_plan(plan, &unoptimized)
+            .await?;
+
+        let mut plans = vec![
+            displayable(physical.as_ref()).to_stringified(true, PlanType::InitialPhysicalPlan)
+        ];
+        for rule in rules {
+            let schema = physical.schema();
+            physical = rule.optimize(physical, state.config_options())?;
+            if rule.schema_check() && physical.schema() != schema {
+                return Err(DataFusionError::Internal(format!(
+                    "PhysicalOptimizer rule '{}' failed, due to generate a different schema",
+                    rule.name()
+                ))
+                .into());
+            }
+            let plan_type = PlanType::OptimizedPhysicalPlan {
+                optimizer_name: rule.name().to_string(),
+            };
+            plans.push(displayable(physical.as_ref()).to_stringified(true, plan_type));
+        }
+
+        Ok((plans, physical))
+    }
+
+    /// Create the physical plan for an already optimized EXPLAIN.
+    ///
+    /// The final physical plan of the explained statement is rendered as a
+    /// tree with our own renderer, which shows more detail for verbose
+    /// explains than DataFusion's. Verbose explains also include the
+    /// intermediate plans, see `create_explained_physical_plan`.
+    async fn create_explain_plan(
+        &self,
+        explain: Explain,
+        op: &OperationInfo,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let options = self.ctx.df_state().config_options().explain.clone();
+
+        let mut plans = Vec::new();
+        if !options.physical_plan_only {
+            plans = explain.stringified_plans;
+            if explain.logical_optimization_succeeded {
+                plans.push(explain.plan.to_stringified(PlanType::FinalLogicalPlan));
+            }
+        }
+        if !options.logical_plan_only && explain.logical_optimization_succeeded {
+            match self
+                .create_explained_physical_plan(explain.plan.as_ref(), explain.verbose, op)
+                .await
+            {
+                Ok((intermediate, input)) => {
+                    plans.extend(intermediate);
+                    plans.push(StringifiedPlan::new(
+                        PlanType::FinalPhysicalPlan,
+                        render_physical_tree(input.as_ref(), explain.verbose),
+                    ));
+                }
+                Err(e) => plans.push(StringifiedPlan::new(
+                    PlanType::PhysicalPlanError,
+                    e.to_string(),
+                )),
+            }
+        }
+
+        Ok(Arc::new(ExplainExec::new(
+            Arc::new(explain.schema.as_ref().clone().into()),
+            plans,
+            explain.verbose,
+        )))
+    }
+
     /// Execute a datafusion physical plan.
     pub async fn execute_physical_plan(
         &self,
@@ -577,6 +1261,163 @@ impl Session {
         self.ctx.remove_portal(name);
     }
 
+    /// Get the current transaction state of the session.
+    pub fn transaction_state(&self) -> TransactionState {
+        self.transaction
+    }
+
+    /// Mark the current transaction as failed.
+    ///
+    /// This should be called whenever a statement errors. Outside of an
+    /// explicit transaction block, this rolls back the implicit transaction
+    /// of the batch that failed, if any.
+    pub async fn fail_transaction(&mut self) {
+        match self.transaction {
+            TransactionState::InBlock => self.transaction = TransactionState::Failed,
+            TransactionState::Idle if self.implicit => {
+                self.rollback_writes().await;
+                self.implicit = false;
+            }
+            _ => (),
+        }
+    }
+
+    /// Run the following statements as an implicit transaction, undoing
+    /// their writes if one of them fails (see `fail_transaction`).
+    ///
+    /// The writes are only undone if no other connection wrote to the same
+    /// tables in the meantime (see `WriteLog`). Otherwise, as with other
+    /// changes that can't be undone, the first error stops the batch and
+    /// the writes of the statements before it stay committed, with a
+    /// warning sent.
+    ///
+    /// This should be called before executing a batch of multiple
+    /// statements. Does nothing within an explicit transaction block.
+    pub fn begin_implicit_transaction(&mut self) {
+        if self.transaction == TransactionState::Idle {
+            self.implicit = true;
+            self.writes.clear();
+        }
+    }
+
+    /// End the implicit transaction started by `begin_implicit_transaction`,
+    /// keeping its writes.
+    pub fn end_implicit_transaction(&mut self) {
+        if self.implicit {
+            self.implicit = false;
+            if self.transaction == TransactionState::Idle {
+                self.writes.clear();
+            }
+        }
+    }
+
+    /// Undo the changes made in the current transaction, clearing the write
+    /// log.
+    ///
+    /// Nothing is undone if some of the changes can't be. A warning is sent
+    /// instead so that the reported rollback isn't mistaken for one that
+    /// happened.
+    async fn rollback_writes(&mut self) {
+        let start = WritePosition::default();
+        let message = if !self.writes.can_undo_since(start) {
+            Some(format!(
+                "no changes were rolled back, since {NON_NATIVE_CHANGES}"
+            ))
+        } else {
+            let connection_id = self.ctx.get_session_vars().connection_id();
+            match self
+                .writes
+                .undo_since(start, connection_id, "transaction")
+                .await
+            {
+                Ok(()) => None,
+                Err(ExecError::IrreversibleChanges(_, reason)) => {
+                    Some(format!("no changes were rolled back, since {reason}"))
+                }
+                Err(e) => Some(format!("failed to roll back changes: {e}")),
+            }
+        };
+        self.writes.clear();
+
+        if let Some(message) = message {
+            self.ctx.push_notice(Notice {
+                severity: NoticeSeverity::Warning,
+                code: SqlState::FeatureNotSupported,
+                message,
+            });
+        }
+    }
+
+    /// Find the index of the most recent savepoint with the given name.
+    fn find_savepoint(&self, name: &str) -> Result<usize> {
+        self.savepoints
+            .iter()
+            .rposition(|s| s.name == name)
+            .ok_or_else(|| ExecError::UnknownSavepoint(name.to_string()))
+    }
+
+    /// Record the changes `plan` is about to make in the write log.
+    async fn record_write(&mut self, plan: &DfLogicalPlan) -> Result<()> {
+        match WriteTarget::from_plan(plan) {
+            WriteTarget::Version(version) => self.writes.versions.push(version),
+            WriteTarget::Tables(tables) => {
+                for table in tables {
+                    let version = self.ctx.get_native_tables().table_version(&table).await?;
+                    self.writes.versions.push(version);
+                }
+            }
+            WriteTarget::Irreversible => self.writes.irreversible += 1,
+            WriteTarget::Unchanged => (),
+        }
+        Ok(())
+    }
+
+    /// Fetch up to `count` rows from a cursor, or all remaining rows if
+    /// `count` is `None`.
+    async fn fetch_cursor(
+        &mut self,
+        name: &str,
+        count: Option<u64>,
+        cancel: &CancellationToken,
+    ) -> Result<ExecutionResult> {
+        let cursor = self
+            .cursors
+            .get_mut(name)
+            .ok_or_else(|| ExecError::UnknownCursor(name.to_string()))?;
+        let schema = cursor.stream.schema();
+
+        let mut remaining = count.map(|count| count as usize).unwrap_or(usize::MAX);
+        let mut batches = Vec::new();
+        while remaining > 0 {
+            let batch = match cursor.buffered.take() {
+                Some(batch) => batch,
+                None => {
+                    let next = tokio::select! {
+                        next = cursor.stream.next() => next,
+                        _ = cancel.cancelled() => return Err(ExecError::QueryCanceled),
+                    };
+                    match next {
+                        Some(batch) => batch?,
+                        None => break,
+                    }
+                }
+            };
+
+            // Keep the rows past the requested count for the next fetch.
+            if batch.num_rows() > remaining {
+                cursor.buffered = Some(batch.slice(remaining, batch.num_rows() - remaining));
+                batches.push(batch.slice(0, remaining));
+                break;
+            }
+            remaining -= batch.num_rows();
+            batches.push(batch);
+        }
+
+        Ok(ExecutionResult::Query {
+            stream: Box::pin(MemoryStream::try_new(batches, schema, None)?),
+        })
+    }
+
     pub fn take_notices(&mut self) -> Vec<Notice> {
         self.ctx.take_notices()
     }
@@ -602,6 +1443,48 @@ impl Session {
         plan: LogicalPlan,
         op: &OperationInfo,
     ) -> Result<(Arc<dyn ExecutionPlan>, ExecutionResult)> {
+        self.execute_logical_plan_with_cancel(plan, op, CancellationToken::new())
+            .await
+    }
+
+    /// Execute a logical plan, stopping early if `cancel` is canceled.
+    ///
+    /// Cancellation is checked before physical planning and before execution.
+    /// Once execution has started, the resulting stream ends with a
+    /// `QueryCanceled` error.
+    ///
+    /// If `statement_timeout` is set, the stream ends with a
+    /// `StatementTimeout` error once the timeout has elapsed since this was
+    /// called.
+    pub async fn execute_logical_plan_with_cancel(
+        &mut self,
+        plan: LogicalPlan,
+        op: &OperationInfo,
+        cancel: CancellationToken,
+    ) -> Result<(Arc<dyn ExecutionPlan>, ExecutionResult)> {
+        if cancel.is_cancelled() {
+            return Err(ExecError::QueryCanceled);
+        }
+
+        // A timeout of zero (or less) disables the timeout.
+        let deadline = match self.ctx.get_session_vars().statement_timeout() {
+            ms if ms > 0 => Some(tokio::time::Instant::now() + Duration::from_millis(ms as u64)),
+            _ => None,
+        };
+
+        if self.transaction == TransactionState::Failed
+            && !matches!(
+                plan,
+                LogicalPlan::Transaction(
+                    TransactionPlan::Commit
+                        | TransactionPlan::Abort
+                        | TransactionPlan::RollbackToSavepoint(_)
+                )
+            )
+        {
+            return Err(ExecError::TransactionAborted);
+        }
+
         // Note that transaction support is fake, in that we don't currently do
         // anything and do not provide any transactional semantics.
         //
@@ -618,20 +1501,213 @@ impl Session {
                     message: "GlareDB does not support proper transactional semantics. Do not rely on transactions for correctness. Transactions are stubbed out to enable compatability with existing Postgres tools.".to_string(),
                 });
 
-                Ok((
-                    EMPTY_EXEC_PLAN.clone(),
-                    match plan {
-                        TransactionPlan::Begin => ExecutionResult::Begin,
-                        TransactionPlan::Commit => ExecutionResult::Commit,
-                        TransactionPlan::Abort => ExecutionResult::Rollback,
-                    },
-                ))
+                let result = match (plan, self.transaction) {
+                    (TransactionPlan::Begin, state) => {
+                        if state == TransactionState::InBlock {
+                            self.ctx.push_notice(Notice {
+                                severity: NoticeSeverity::Warning,
+                                code: SqlState::ActiveSqlTransaction,
+                                message: "there is already a transaction in progress".to_string(),
+                            });
+                        }
+                        self.transaction = TransactionState::InBlock;
+                        ExecutionResult::Begin
+                    }
+                    // Committing a failed transaction rolls it back.
+                    (TransactionPlan::Commit, TransactionState::Failed) => {
+                        self.transaction = TransactionState::Idle;
+                        self.savepoints.clear();
+                        self.rollback_writes().await;
+                        self.cursors.clear();
+                        ExecutionResult::Rollback
+                    }
+                    (TransactionPlan::Savepoint(_), TransactionState::Idle) => {
+                        return Err(ExecError::NotInTransactionBlock("SAVEPOINT"));
+                    }
+                    (TransactionPlan::ReleaseSavepoint(_), TransactionState::Idle) => {
+                        return Err(ExecError::NotInTransactionBlock("RELEASE SAVEPOINT"));
+                    }
+                    (TransactionPlan::RollbackToSavepoint(_), TransactionState::Idle) => {
+                        return Err(ExecError::NotInTransactionBlock("ROLLBACK TO SAVEPOINT"));
+                    }
+                    (plan, TransactionState::Idle) => {
+                        self.ctx.push_notice(Notice {
+                            severity: NoticeSeverity::Warning,
+                            code: SqlState::NoActiveSqlTransaction,
+                            message: "there is no transaction in progress".to_string(),
+                        });
+                        match plan {
+                            TransactionPlan::Commit => ExecutionResult::Commit,
+                            _ => ExecutionResult::Rollback,
+                        }
+                    }
+                    (TransactionPlan::Commit, _) => {
+                        self.transaction = TransactionState::Idle;
+                        self.savepoints.clear();
+                        self.writes.clear();
+                        self.cursors.clear();
+                        ExecutionResult::Commit
+                    }
+                    (TransactionPlan::Abort, _) => {
+                        self.transaction = TransactionState::Idle;
+                        self.savepoints.clear();
+                        self.rollback_writes().await;
+                        self.cursors.clear();
+                        ExecutionResult::Rollback
+                    }
+                    (TransactionPlan::Savepoint(name), _) => {
+                        self.savepoints.push(Savepoint {
+                            name,
+                            writes: self.writes.position(),
+                        });
+                        ExecutionResult::Savepoint
+                    }
+                    // Releasing a savepoint also releases every savepoint
+                    // created after it.
+                    (TransactionPlan::ReleaseSavepoint(name), _) => {
+                        let idx = self.find_savepoint(&name)?;
+                        self.savepoints.truncate(idx);
+                        ExecutionResult::ReleaseSavepoint
+                    }
+                    // Rolling back keeps the savepoint itself so it can be
+                    // rolled back to again.
+                    (TransactionPlan::RollbackToSavepoint(name), _) => {
+                        let idx = self.find_savepoint(&name)?;
+                        let position = self.savepoints[idx].writes;
+                        if !self.writes.can_undo_since(position) {
+                            return Err(ExecError::IrreversibleChanges(
+                                format!("to savepoint \"{name}\""),
+                                NON_NATIVE_CHANGES,
+                            ));
+                        }
+                        let connection_id = self.ctx.get_session_vars().connection_id();
+                        self.writes
+                            .undo_since(
+                                position,
+                                connection_id,
+                                &format!("to savepoint \"{name}\""),
+                            )
+                            .await?;
+                        self.savepoints.truncate(idx + 1);
+                        self.transaction = TransactionState::InBlock;
+                        ExecutionResult::RollbackToSavepoint
+                    }
+                };
+
+                Ok((EMPTY_EXEC_PLAN.clone(), result))
+            }
+            // Cursors are closed when the transaction block ends.
+            LogicalPlan::Cursor(plan) => {
+                let result = match plan {
+                    CursorPlan::Declare { .. } if self.transaction == TransactionState::Idle => {
+                        return Err(ExecError::NotInTransactionBlock("DECLARE CURSOR"));
+                    }
+                    CursorPlan::Declare { name, .. } if self.cursors.contains_key(&name) => {
+                        return Err(ExecError::DuplicateCursor(name));
+                    }
+                    CursorPlan::Declare { name, plan } => {
+                        let physical = self.create_physical_plan(plan, op).await?;
+                        let stream = self.execute_physical_plan(physical).await?;
+                        self.cursors.insert(
+                            name,
+                            Cursor {
+                                stream,
+                                buffered: None,
+                            },
+                        );
+                        ExecutionResult::DeclareCursor
+                    }
+                    CursorPlan::Fetch { name, count } => {
+                        self.fetch_cursor(&name, count, &cancel).await?
+                    }
+                    CursorPlan::Close(Some(name)) => {
+                        if self.cursors.remove(&name).is_none() {
+                            return Err(ExecError::UnknownCursor(name));
+                        }
+                        ExecutionResult::CloseCursor
+                    }
+                    CursorPlan::Close(None) => {
+                        self.cursors.clear();
+                        ExecutionResult::CloseCursor
+                    }
+                };
+
+                Ok((EMPTY_EXEC_PLAN.clone(), result))
             }
             LogicalPlan::Datafusion(plan) => {
+                if self.ctx.get_session_vars().read_only() {
+                    if let Some(op) = mutating_operation(&plan) {
+                        return Err(ExecError::ReadOnlySession(op));
+                    }
+                }
+
+                if (self.transaction != TransactionState::Idle || self.implicit)
+                    && mutating_operation(&plan).is_some()
+                {
+                    self.record_write(&plan).await?;
+                }
+
+                let analyze = matches!(plan, DfLogicalPlan::Analyze(_));
+                let is_explain = analyze || matches!(plan, DfLogicalPlan::Explain(_));
+                // An insert with a RETURNING clause sits beneath a
+                // projection. Inserts alter the table when evolving its schema.
+                let insert = match &plan {
+                    DfLogicalPlan::Projection(projection) => projection.input.as_ref(),
+                    plan => plan,
+                };
+                let insert = match insert {
+                    DfLogicalPlan::Extension(ext) => ext.node.as_any().downcast_ref::<Insert>(),
+                    _ => None,
+                };
+                let returns_inserted = insert.is_some_and(|insert| insert.returning);
+                let evolves_schema = insert.is_some_and(|insert| insert.schema_evolution.is_some());
+
                 let physical = self.create_physical_plan(plan, op).await?;
-                let stream = self.execute_physical_plan(physical.clone()).await?;
+                if cancel.is_cancelled() {
+                    return Err(ExecError::QueryCanceled);
+                }
+                if deadline.is_some_and(|deadline| deadline <= tokio::time::Instant::now()) {
+                    return Err(ExecError::StatementTimeout);
+                }
 
-                let stream = ExecutionResult::from_stream(stream).await;
+                let stream = self.execute_physical_plan(physical.clone()).await?;
+                let stream: SendableRecordBatchStream =
+                    Box::pin(CancellableStream::new(stream, cancel));
+                let stream: SendableRecordBatchStream = match deadline {
+                    Some(deadline) => Box::pin(TimeoutStream::new(stream, deadline)),
+                    None => stream,
+                };
+
+                // Rendering consumes the entire stream. For analyze, this runs
+                // the query to completion so that the metrics are populated.
+                let stream = if is_explain {
+                    match render_explain(stream).await {
+                        Ok(plan) => ExecutionResult::Explain { plan, analyze },
+                        Err(e) => ExecutionResult::Error(e),
+                    }
+                } else {
+                    ExecutionResult::from_stream(stream).await
+                };
+
+                // The insert only runs once the stream is polled, which
+                // `from_stream` doesn't do for an insert returning rows. Wait
+                // for the first returned batch, which comes once the insert
+                // has completed, so that the insert happens even if the rows
+                // are never fetched.
+                let stream = match stream {
+                    ExecutionResult::Query { mut stream } if returns_inserted => {
+                        match stream.next().await {
+                            Some(Err(e)) => ExecutionResult::Error(e),
+                            first_result => ExecutionResult::Query {
+                                stream: Box::pin(StreamAndFirstResult {
+                                    stream,
+                                    first_result,
+                                }),
+                            },
+                        }
+                    }
+                    stream => stream,
+                };
 
                 // If we're attached to a remote node, and the result indicates
                 // the operation was a DDL operation, then fetch the newer
@@ -650,7 +1726,7 @@ impl Session {
                     // detail on the grpc response stream from the remote node
                     // to provide a better hint of what we should be doing on
                     // error.
-                    if stream.is_ddl() || stream.is_error() {
+                    if stream.is_ddl() || stream.is_error() || evolves_schema {
                         // TODO: Instead of swapping here, I'd like to if we
                         // could go towards collecting a "diff" of a session
                         // (including new catalog states, variable changes, etc)
@@ -690,13 +1766,81 @@ impl Session {
             op = op.with_query_text(stmt.to_string());
         }
 
+        self.execute_with_metrics(plan, &op, CancellationToken::new())
+            .await
+    }
+
+    /// Parse and plan a single statement for repeated execution.
+    ///
+    /// Unlike `prepare_statement`, the statement isn't stored on the session,
+    /// and can be executed any number of times with `execute_prepared`
+    /// without being parsed or planned again.
+    pub async fn prepare(&mut self, query: &str) -> Result<PreparedStatement> {
+        let mut statements = self.parse_query(query)?;
+        if statements.len() > 1 {
+            return Err(ExecError::String(
+                "More than one statement in query".to_string(),
+            ));
+        }
+
+        self.ctx
+            .plan_prepared_statement(statements.pop_front())
+            .await
+    }
+
+    /// Execute a statement created with `prepare`, binding `params` to its
+    /// placeholders (`$1` is `params[0]`, and so on).
+    pub async fn execute_prepared(
+        &mut self,
+        stmt: &PreparedStatement,
+        params: &[ScalarValue],
+    ) -> Result<ExecutionResult> {
+        self.execute_prepared_with_cancel(stmt, params, CancellationToken::new())
+            .await
+    }
+
+    /// Like `execute_prepared`, but stops early if `cancel` is canceled.
+    pub async fn execute_prepared_with_cancel(
+        &mut self,
+        stmt: &PreparedStatement,
+        params: &[ScalarValue],
+        cancel: CancellationToken,
+    ) -> Result<ExecutionResult> {
+        let mut plan = match &stmt.plan {
+            Some(plan) => plan.clone(),
+            None => return Ok(ExecutionResult::EmptyQuery),
+        };
+        let params = stmt.check_parameters(params.to_vec())?;
+        plan.replace_placeholders(params)?;
+
+        let mut op = OperationInfo::default();
+        if let Some(stmt) = &stmt.stmt {
+            op = op.with_query_text(stmt.to_string());
+        }
+
+        self.execute_with_metrics(plan, &op, cancel).await
+    }
+
+    /// Execute a logical plan, tracking metrics for the execution.
+    async fn execute_with_metrics(
+        &mut self,
+        plan: LogicalPlan,
+        op: &OperationInfo,
+        cancel: CancellationToken,
+    ) -> Result<ExecutionResult> {
         // Create "base" metrics.
         let mut metrics = QueryMetrics {
             query_text: op.query_text().to_owned(),
             ..Default::default()
         };
+        self.ctx
+            .get_metrics_handler()
+            .query_started(op.query_text(), cancel.clone());
 
-        let stream = match self.execute_logical_plan(plan, &op).await {
+        let stream = match self
+            .execute_logical_plan_with_cancel(plan, op, cancel)
+            .await
+        {
             Ok((plan, result)) => match result {
                 ExecutionResult::Error(e) => {
                     metrics.execution_status = ExecutionStatus::Fail;
@@ -722,8 +1866,10 @@ impl Session {
                             }
                         }
                         write_result @ ExecutionResult::CreateTable
-                        | write_result @ ExecutionResult::CopySuccess
+                        | write_result @ ExecutionResult::CopySuccess { .. }
+                        | write_result @ ExecutionResult::OptimizeSuccess { .. }
                         | write_result @ ExecutionResult::InsertSuccess { .. }
+                        | write_result @ ExecutionResult::OverwriteSuccess { .. }
                         | write_result @ ExecutionResult::UpdateSuccess { .. }
                         | write_result @ ExecutionResult::DeleteSuccess { .. } => {
                             // Push the metrics from the plan since the stream
@@ -735,7 +1881,10 @@ impl Session {
                             self.ctx.get_metrics_handler().push_metric(metrics);
                             write_result
                         }
-                        other => other,
+                        other => {
+                            self.ctx.get_metrics_handler().query_finished();
+                            other
+                        }
                     }
                 }
             },
@@ -810,6 +1959,45 @@ impl Session {
         }
     }
 
+    /// Plan every statement in a query without executing anything.
+    ///
+    /// Useful for validating SQL. Planning never modifies the catalog, so DDL
+    /// statements are only checked. Each statement is planned against the
+    /// current catalog, so a statement referencing an object created by an
+    /// earlier statement in the same query will fail to plan.
+    pub async fn plan_only(&mut self, query: &str) -> Result<Vec<LogicalPlan>> {
+        self.ctx.maybe_refresh_state().await?;
+        let statements = self.parse_query(query)?;
+
+        let planner = SessionPlanner::new(&self.ctx);
+        let mut plans = Vec::with_capacity(statements.len());
+        for stmt in statements {
+            plans.push(planner.plan_ast(stmt).await?);
+        }
+        Ok(plans)
+    }
+
+    /// Like `plan_only`, but also creates the physical plan for every
+    /// statement.
+    ///
+    /// Statements without a physical plan (e.g. transaction statements) get
+    /// an empty plan.
+    pub async fn physical_plan_only(&mut self, query: &str) -> Result<Vec<Arc<dyn ExecutionPlan>>> {
+        let plans = self.plan_only(query).await?;
+
+        let op = OperationInfo::new().with_query_text(query);
+        let mut physical = Vec::with_capacity(plans.len());
+        for plan in plans {
+            physical.push(match plan {
+                LogicalPlan::Datafusion(plan) => self.create_physical_plan(plan, &op).await?,
+                LogicalPlan::Transaction(_) | LogicalPlan::Cursor(_) | LogicalPlan::Noop => {
+                    EMPTY_EXEC_PLAN.clone()
+                }
+            });
+        }
+        Ok(physical)
+    }
+
     pub fn parse_query(&self, query: &str) -> Result<VecDeque<StatementWithExtensions>> {
         Ok(match self.get_session_vars().dialect() {
             datafusion_ext::vars::Dialect::Sql => parser::parse_sql(query)?,
@@ -830,3 +2018,451 @@ impl Session {
         Ok(stream)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::array::Int64Array;
+    use datafusion::arrow::datatypes::{DataType, Field};
+    use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+    use datafusion::prelude::SessionContext as DfSessionContext;
+    use datafusion::variable::VarType;
+    use datafusion_proto::physical_plan::AsExecutionPlan;
+    use datafusion_proto::protobuf::PhysicalPlanNode;
+    use sqlbuiltins::builtins::{DEFAULT_CATALOG, DEFAULT_SCHEMA};
+
+    use super::*;
+    use crate::engine::{Engine, SessionStorageConfig};
+    use crate::extension_codec::GlareDBExtensionCodec;
+    use crate::remote::provider_cache::ProviderCache;
+
+    /// Collect the values of the single Int64 column returned by a query.
+    async fn query_values(result: ExecutionResult) -> Vec<i64> {
+        match result {
+            ExecutionResult::Query { stream } => stream_values(stream).await,
+            other => panic!("expected query result, got {other:?}"),
+        }
+    }
+
+    /// Collect the values of the single Int64 column in a stream.
+    async fn stream_values(stream: SendableRecordBatchStream) -> Vec<i64> {
+        let batches: Vec<RecordBatch> = stream.try_collect().await.unwrap();
+        batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn execute_prepared_binds_params() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        let stmt = session
+            .prepare("select a from (values (1), (2), (3)) as t(a) where a = $1")
+            .await
+            .unwrap();
+
+        // The same statement can be executed any number of times with
+        // different parameters.
+        for val in [2, 3, 4] {
+            let result = session
+                .execute_prepared(&stmt, &[ScalarValue::Int64(Some(val))])
+                .await
+                .unwrap();
+            let expected = if val <= 3 { vec![val] } else { Vec::new() };
+            assert_eq!(expected, query_values(result).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_prepared_checks_params() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        let stmt = session
+            .prepare("select a from (values (1), (2), (3)) as t(a) where a = $1")
+            .await
+            .unwrap();
+
+        let err = session
+            .execute_prepared(&stmt, &[ScalarValue::Utf8(Some("2".to_string()))])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ExecError::InvalidParameterType { .. }),
+            "{err}"
+        );
+
+        let err = session.execute_prepared(&stmt, &[]).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ExecError::InvalidParameterCount {
+                    expected: 1,
+                    got: 0
+                }
+            ),
+            "{err}"
+        );
+
+        // Failed executions don't affect later ones.
+        let result = session
+            .execute_prepared(&stmt, &[ScalarValue::Int64(Some(1))])
+            .await
+            .unwrap();
+        assert_eq!(vec![1], query_values(result).await);
+    }
+
+    #[tokio::test]
+    async fn plan_only_does_not_execute() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        let plans = session
+            .plan_only("create table t1 (a int); select 1")
+            .await
+            .unwrap();
+        assert_eq!(2, plans.len());
+
+        let plans = session
+            .physical_plan_only("create table t1 (a int); begin")
+            .await
+            .unwrap();
+        assert_eq!(2, plans.len());
+
+        // Neither call should've created the table.
+        assert!(session
+            .get_session_catalog()
+            .resolve_table(DEFAULT_CATALOG, DEFAULT_SCHEMA, "t1")
+            .is_none());
+        session.execute_sql("select * from t1").await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn physical_plan_only_does_not_write() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        let stream = session
+            .execute_sql("create table t1 (a bigint)")
+            .await
+            .unwrap();
+        let _: Vec<RecordBatch> = stream.try_collect().await.unwrap();
+
+        session
+            .plan_only("insert into t1 values (1), (2)")
+            .await
+            .unwrap();
+        session
+            .physical_plan_only("insert into t1 values (1), (2); drop table t1")
+            .await
+            .unwrap();
+
+        let stream = session
+            .execute_sql("select count(*) from t1")
+            .await
+            .unwrap();
+        assert_eq!(vec![0], stream_values(stream).await);
+    }
+
+    #[tokio::test]
+    async fn optimize_reports_file_counts() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        async fn execute(session: &mut Session, sql: &str) -> ExecutionResult {
+            let stream = session.execute_sql(sql).await.unwrap();
+            ExecutionResult::from_stream(stream).await
+        }
+
+        // Each insert writes a new file.
+        execute(&mut session, "create table t1 (a int)").await;
+        for val in 1..=3 {
+            let result = execute(&mut session, &format!("insert into t1 values ({val})")).await;
+            assert!(
+                matches!(result, ExecutionResult::InsertSuccess { rows_inserted: 1 }),
+                "{result:?}"
+            );
+        }
+
+        let result = execute(&mut session, "optimize t1").await;
+        assert!(
+            matches!(
+                result,
+                ExecutionResult::OptimizeSuccess {
+                    files_removed: 3,
+                    files_added: 1
+                }
+            ),
+            "{result:?}"
+        );
+
+        let result = execute(&mut session, "optimize t1").await;
+        assert!(
+            matches!(
+                result,
+                ExecutionResult::OptimizeSuccess {
+                    files_removed: 0,
+                    files_added: 0
+                }
+            ),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn rollback_keeps_other_connections_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = Engine::from_data_dir(Some(&dir.path().to_path_buf()))
+            .await
+            .unwrap();
+        let new_session = || {
+            let vars = SessionVars::default().with_connection_id(Uuid::new_v4(), VarType::System);
+            engine.new_local_session_context(vars, SessionStorageConfig::default())
+        };
+        let mut s1 = new_session().await.unwrap();
+        let mut s2 = new_session().await.unwrap();
+
+        async fn execute(session: &mut Session, sql: &str) -> Result<ExecutionResult> {
+            let stream = session.execute_sql(sql).await?;
+            Ok(ExecutionResult::from_stream(stream).await)
+        }
+
+        execute(&mut s1, "create table t1 (a bigint)")
+            .await
+            .unwrap();
+        execute(&mut s1, "begin").await.unwrap();
+        execute(&mut s1, "insert into t1 values (1)").await.unwrap();
+        execute(&mut s1, "savepoint sp1").await.unwrap();
+        execute(&mut s1, "insert into t1 values (2)").await.unwrap();
+        execute(&mut s2, "insert into t1 values (10)")
+            .await
+            .unwrap();
+
+        // Undoing the first session's inserts would also undo the second
+        // session's.
+        let err = execute(&mut s1, "rollback to savepoint sp1")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ExecError::IrreversibleChanges(_, CONCURRENT_CHANGES)),
+            "{err}"
+        );
+        execute(&mut s1, "rollback").await.unwrap();
+
+        let stream = s2.execute_sql("select a from t1 order by a").await.unwrap();
+        assert_eq!(vec![1, 2, 10], stream_values(stream).await);
+
+        // Commits made before the transaction started don't prevent undoing
+        // the transaction's own commits.
+        execute(&mut s1, "begin").await.unwrap();
+        execute(&mut s1, "insert into t1 values (3)").await.unwrap();
+        execute(&mut s1, "delete from t1 where a = 10")
+            .await
+            .unwrap();
+        execute(&mut s1, "rollback").await.unwrap();
+
+        let stream = s2.execute_sql("select a from t1 order by a").await.unwrap();
+        assert_eq!(vec![1, 2, 10], stream_values(stream).await);
+    }
+
+    /// Plan `sql`, then encode and decode the plan the same way remote
+    /// execution does.
+    async fn codec_round_trip(session: &mut Session, sql: &str) -> Arc<dyn ExecutionPlan> {
+        let plan = session
+            .physical_plan_only(sql)
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        let node =
+            PhysicalPlanNode::try_from_physical_plan(plan, &GlareDBExtensionCodec::new_encoder())
+                .unwrap();
+        let mut buf = Vec::new();
+        node.try_encode(&mut buf).unwrap();
+
+        let providers = ProviderCache::default();
+        let ctx = DfSessionContext::new();
+        PhysicalPlanNode::try_decode(&buf)
+            .unwrap()
+            .try_into_physical_plan(
+                &ctx,
+                ctx.runtime_env().as_ref(),
+                &GlareDBExtensionCodec::new_decoder(&providers),
+            )
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn optimize_round_trips_through_extension_codec() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        for sql in [
+            "create table t1 (a int)",
+            "insert into t1 values (1)",
+            "insert into t1 values (2)",
+        ] {
+            let stream = session.execute_sql(sql).await.unwrap();
+            ExecutionResult::from_stream(stream).await;
+        }
+
+        let plan = codec_round_trip(&mut session, "optimize t1").await;
+        let stream = session.execute_physical_plan(plan).await.unwrap();
+        let result = ExecutionResult::from_stream(stream).await;
+        assert!(
+            matches!(
+                result,
+                ExecutionResult::OptimizeSuccess {
+                    files_removed: 2,
+                    files_added: 1
+                }
+            ),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn insert_overwrite_round_trips_through_extension_codec() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        for sql in [
+            "create table t1 (a bigint)",
+            "insert into t1 values (1), (2), (3)",
+        ] {
+            let stream = session.execute_sql(sql).await.unwrap();
+            ExecutionResult::from_stream(stream).await;
+        }
+
+        // Only the rows matching the predicate are replaced, so the
+        // predicate has to survive the encoding.
+        let plan =
+            codec_round_trip(&mut session, "insert overwrite t1 where a >= 2 values (4)").await;
+        let stream = session.execute_physical_plan(plan).await.unwrap();
+        ExecutionResult::from_stream(stream).await;
+
+        let stream = session
+            .execute_sql("select a from t1 order by a")
+            .await
+            .unwrap();
+        assert_eq!(vec![1, 4], stream_values(stream).await);
+    }
+
+    /// Execute an EXPLAIN, returning the rendered plans.
+    async fn explain(session: &mut Session, sql: &str) -> String {
+        let stmt = session.prepare(sql).await.unwrap();
+        match session.execute_prepared(&stmt, &[]).await.unwrap() {
+            ExecutionResult::Explain { plan, .. } => plan,
+            other => panic!("expected explain result, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn explain_renders_physical_tree() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        let plan = explain(&mut session, "explain select 1").await;
+        assert_eq!(
+            "logical_plan:\n  Projection: Int64(1)\n    EmptyRelation\nphysical_plan:\n  ProjectionExec: expr=[1 as Int64(1)]\n    PlaceholderRowExec",
+            plan
+        );
+    }
+
+    #[tokio::test]
+    async fn explain_verbose_shows_intermediate_plans() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        let plan = explain(
+            &mut session,
+            "explain verbose select a from (values (2), (1)) as t(a) order by a",
+        )
+        .await;
+        assert!(plan.contains("\ninitial_physical_plan:\n"), "{plan}");
+        assert!(plan.contains("\nphysical_plan after "), "{plan}");
+
+        // The final plan shows the partitioning and ordering of each
+        // operator.
+        let (_, final_plan) = plan.split_once("\nphysical_plan:\n").unwrap();
+        assert!(final_plan.contains("partitioning="), "{plan}");
+        assert!(
+            final_plan.contains("ordering=[a@0 ASC NULLS LAST]"),
+            "{plan}"
+        );
+    }
+
+    #[tokio::test]
+    async fn explain_analyze_shows_metrics() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        let stmt = session
+            .prepare("explain analyze select count(*) from generate_series(1, 10)")
+            .await
+            .unwrap();
+        let plan = match session.execute_prepared(&stmt, &[]).await.unwrap() {
+            ExecutionResult::Explain {
+                plan,
+                analyze: true,
+            } => plan,
+            other => panic!("expected explain analyze result, got {other:?}"),
+        };
+        assert!(plan.contains("output_rows="), "{plan}");
+        assert!(plan.contains("elapsed_compute="), "{plan}");
+    }
+
+    #[tokio::test]
+    async fn prepare_rejects_multiple_statements() {
+        let engine = Engine::from_data_dir(None).await.unwrap();
+        let mut session = engine.default_local_session_context().await.unwrap();
+
+        let err = session.prepare("select 1; select 2").await.unwrap_err();
+        assert!(err.to_string().contains("More than one statement"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn cancel_stream() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        // Never produces a batch, the stream can only end through
+        // cancellation.
+        let inner = Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::pending(),
+        ));
+
+        let cancel = CancellationToken::new();
+        let mut stream = CancellableStream::new(inner, cancel.clone());
+
+        cancel.cancel();
+
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("Query canceled"), "{err}");
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn timeout_stream() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let inner = Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            futures::stream::pending(),
+        ));
+
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(100);
+        let mut stream = TimeoutStream::new(inner, deadline);
+
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("statement timeout"), "{err}");
+        assert!(stream.next().await.is_none());
+    }
+}